@@ -1,3 +1,4 @@
+use scrypto::engine::{api::*, call_engine};
 use scrypto::prelude::*;
 
 blueprint! {
@@ -27,6 +28,25 @@ blueprint! {
             borrow_resource_manager!(resource_address).mint(amount)
         }
 
+        /// Mints a non-fungible with raw data. TODO: Remove
+        pub fn mint_non_fungible(
+            resource_address: ResourceAddress,
+            id: NonFungibleId,
+            immutable_data: Vec<u8>,
+            mutable_data: Vec<u8>,
+        ) -> Bucket {
+            let mut entries = HashMap::new();
+            entries.insert(id, (immutable_data, mutable_data));
+
+            let input = InvokeSNodeInput {
+                snode_ref: SNodeRef::ResourceRef(resource_address),
+                function: "mint".to_string(),
+                args: args![MintParams::NonFungible { entries }],
+            };
+            let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+            scrypto_decode(&output.rtn).unwrap()
+        }
+
         /// Burns bucket. TODO: Remove
         pub fn burn(bucket: Bucket) {
             bucket.burn()
@@ -36,5 +56,10 @@ blueprint! {
         pub fn free_xrd(&mut self) -> Bucket {
             self.xrd.take(1_000_000)
         }
+
+        /// Gives away the given amount of XRD tokens for testing. TODO: Remove
+        pub fn free_xrd_amount(&mut self, amount: Decimal) -> Bucket {
+            self.xrd.take(amount)
+        }
     }
 }
@@ -0,0 +1,12 @@
+use scrypto::engine::types::*;
+
+/// Block-level context of a transaction, fixed for its whole execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockContext {
+    /// The epoch the transaction is executed in.
+    pub epoch: u64,
+    /// The time of the block, in milliseconds since the Unix epoch, or zero if unknown.
+    pub timestamp: u64,
+    /// The hash of the transaction being executed.
+    pub transaction_hash: Hash,
+}
@@ -1,10 +1,19 @@
 use scrypto::engine::types::*;
 use scrypto::rust::collections::*;
+use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
+use scrypto::values::*;
 
 use crate::errors::*;
 use crate::model::*;
 
+/// Identifies a value which can be owned by a component or lazy map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueId {
+    Vault(VaultId),
+    LazyMap(LazyMapId),
+}
+
 #[derive(Debug)]
 pub struct UnclaimedLazyMap {
     pub lazy_map: HashMap<Vec<u8>, Vec<u8>>,
@@ -53,6 +62,40 @@ impl UnclaimedLazyMap {
         }
     }
 
+    /// Returns the first descendent which can't be reached from the root lazy map, if any.
+    pub fn find_orphan(&self) -> Result<Option<ValueId>, RuntimeError> {
+        let mut reachable_vaults = HashSet::new();
+        let mut reachable_lazy_maps = HashSet::new();
+        let mut to_visit = vec![&self.lazy_map];
+        while let Some(entries) = to_visit.pop() {
+            for value in entries.values() {
+                let validated =
+                    ScryptoValue::from_slice(value).map_err(RuntimeError::ParseScryptoValueError)?;
+                reachable_vaults.extend(validated.vault_ids);
+                for lazy_map_id in validated.lazy_map_ids {
+                    if reachable_lazy_maps.insert(lazy_map_id) {
+                        if let Some(child) = self.descendent_lazy_maps.get(&lazy_map_id) {
+                            to_visit.push(child);
+                        }
+                    }
+                }
+            }
+        }
+
+        for vault_id in self.descendent_vaults.keys() {
+            if !reachable_vaults.contains(vault_id) {
+                return Ok(Some(ValueId::Vault(*vault_id)));
+            }
+        }
+        for lazy_map_id in self.descendent_lazy_maps.keys() {
+            if !reachable_lazy_maps.contains(lazy_map_id) {
+                return Ok(Some(ValueId::LazyMap(*lazy_map_id)));
+            }
+        }
+
+        Ok(None)
+    }
+
     pub fn insert_descendents(&mut self, new_descendents: ComponentObjects) {
         for (vault_id, vault) in new_descendents.vaults {
             self.insert_vault(vault_id, vault);
@@ -148,6 +191,70 @@ impl ComponentObjects {
         Ok(ComponentObjects { vaults, lazy_maps, borrowed_vault: None })
     }
 
+    /// Checks that every descendent of the given unclaimed lazy map is reachable from it.
+    pub fn check_reachable(&self, root: &LazyMapId) -> Result<(), RuntimeError> {
+        let unclaimed = self
+            .lazy_maps
+            .get(root)
+            .ok_or(RuntimeError::LazyMapNotFound(*root))?;
+        match unclaimed.find_orphan()? {
+            Some(orphan) => Err(RuntimeError::OrphanedValue(orphan)),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that no value is owned twice, borrowed across a frame boundary or both owned
+    /// and referenced.
+    pub fn check_invariants(&self, refs: &ComponentObjectRefs) -> Result<(), RuntimeError> {
+        if let Some((vault_id, _)) = self.borrowed_vault {
+            return Err(RuntimeError::EngineInvariantViolated(ValueId::Vault(
+                vault_id,
+            )));
+        }
+
+        let mut owned = HashSet::new();
+        for vault_id in self.vaults.keys() {
+            owned.insert(ValueId::Vault(*vault_id));
+        }
+        for (lazy_map_id, unclaimed) in &self.lazy_maps {
+            let descendents = unclaimed
+                .descendent_vaults
+                .keys()
+                .map(|vault_id| ValueId::Vault(*vault_id))
+                .chain(
+                    unclaimed
+                        .descendent_lazy_maps
+                        .keys()
+                        .map(|lazy_map_id| ValueId::LazyMap(*lazy_map_id)),
+                );
+            for value_id in vec![ValueId::LazyMap(*lazy_map_id)]
+                .into_iter()
+                .chain(descendents)
+            {
+                if !owned.insert(value_id) {
+                    return Err(RuntimeError::EngineInvariantViolated(value_id));
+                }
+            }
+        }
+
+        for vault_id in &refs.vault_ids {
+            if owned.contains(&ValueId::Vault(*vault_id)) {
+                return Err(RuntimeError::EngineInvariantViolated(ValueId::Vault(
+                    *vault_id,
+                )));
+            }
+        }
+        for lazy_map_id in &refs.lazy_map_ids {
+            if owned.contains(&ValueId::LazyMap(*lazy_map_id)) {
+                return Err(RuntimeError::EngineInvariantViolated(ValueId::LazyMap(
+                    *lazy_map_id,
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn insert_objects_into_map(
         &mut self,
         new_objects: ComponentObjects,
@@ -0,0 +1,13 @@
+use scrypto::engine::types::*;
+
+/// Decides what happens to resources left dangling when the root process ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DanglingPolicy {
+    /// Fails the transaction with a resource check failure.
+    Fail,
+    /// Deposits dangling buckets and worktop resources into the given account.
+    ///
+    /// Only available with the `sweep_dangling` feature, which validators must never enable.
+    #[cfg(feature = "sweep_dangling")]
+    SweepTo(ComponentAddress),
+}
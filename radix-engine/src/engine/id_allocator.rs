@@ -33,6 +33,11 @@ impl IdAllocator {
         }
     }
 
+    /// Creates an ID allocator which hands out IDs from the given range.
+    pub fn with_range(available: Range<u32>) -> Self {
+        Self { available }
+    }
+
     fn next(&mut self) -> Result<u32, IdAllocatorError> {
         if self.available.len() > 0 {
             let id = self.available.start;
@@ -63,6 +68,17 @@ impl IdAllocator {
         Ok(ComponentAddress(hash(data).lower_26_bytes()))
     }
 
+    /// Derives the address of a named component from its package address and name.
+    pub fn new_named_component_address(
+        &self,
+        package_address: PackageAddress,
+        name: &str,
+    ) -> ComponentAddress {
+        let mut data = package_address.to_vec();
+        data.extend(name.as_bytes());
+        ComponentAddress(hash(data).lower_26_bytes())
+    }
+
     /// Creates a new resource address.
     pub fn new_resource_address(
         &mut self,
@@ -10,6 +10,8 @@ pub enum IdValidatorError {
     BucketNotFound(BucketId),
     ProofNotFound(ProofId),
     BucketLocked(BucketId),
+    BucketAlreadyConsumed(BucketId),
+    ProofAlreadyConsumed(ProofId),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -20,12 +22,16 @@ pub enum ProofKind {
     BucketProof(BucketId),
     /// Proof taken or derived from auth zone.
     AuthZoneProof,
+    /// Proof of resources on the worktop.
+    WorktopProof,
 }
 
 pub struct IdValidator {
     id_allocator: IdAllocator,
     bucket_ids: HashMap<BucketId, usize>,
     proof_ids: HashMap<ProofId, ProofKind>,
+    consumed_bucket_ids: HashSet<BucketId>,
+    consumed_proof_ids: HashSet<ProofId>,
 }
 
 impl IdValidator {
@@ -34,6 +40,8 @@ impl IdValidator {
             id_allocator: IdAllocator::new(IdSpace::Transaction),
             bucket_ids: HashMap::new(),
             proof_ids: HashMap::new(),
+            consumed_bucket_ids: HashSet::new(),
+            consumed_proof_ids: HashSet::new(),
         }
     }
 
@@ -50,12 +58,13 @@ impl IdValidator {
         if let Some(cnt) = self.bucket_ids.get(&bucket_id) {
             if *cnt == 0 {
                 self.bucket_ids.remove(&bucket_id);
+                self.consumed_bucket_ids.insert(bucket_id);
                 Ok(())
             } else {
                 Err(IdValidatorError::BucketLocked(bucket_id))
             }
         } else {
-            Err(IdValidatorError::BucketNotFound(bucket_id))
+            Err(self.bucket_not_found(bucket_id))
         }
     }
 
@@ -65,10 +74,10 @@ impl IdValidator {
                 if let Some(cnt) = self.bucket_ids.get_mut(bucket_id) {
                     *cnt += 1;
                 } else {
-                    return Err(IdValidatorError::BucketNotFound(*bucket_id));
+                    return Err(self.bucket_not_found(*bucket_id));
                 }
             }
-            ProofKind::AuthZoneProof | ProofKind::VirtualProof => {}
+            ProofKind::AuthZoneProof | ProofKind::WorktopProof | ProofKind::VirtualProof => {}
         }
 
         let proof_id = self
@@ -95,7 +104,7 @@ impl IdValidator {
             self.proof_ids.insert(proof_id, kind);
             Ok(proof_id)
         } else {
-            Err(IdValidatorError::ProofNotFound(proof_id))
+            Err(self.proof_not_found(proof_id))
         }
     }
 
@@ -108,15 +117,18 @@ impl IdValidator {
                     panic!("Illegal state");
                 }
             }
+            self.consumed_proof_ids.insert(proof_id);
             Ok(())
         } else {
-            Err(IdValidatorError::ProofNotFound(proof_id))
+            Err(self.proof_not_found(proof_id))
         }
     }
 
     pub fn move_all_resources(&mut self) -> Result<(), IdValidatorError> {
-        self.proof_ids.clear();
-        self.bucket_ids.clear();
+        self.consumed_proof_ids
+            .extend(self.proof_ids.drain().map(|(id, _)| id));
+        self.consumed_bucket_ids
+            .extend(self.bucket_ids.drain().map(|(id, _)| id));
         Ok(())
     }
 
@@ -129,4 +141,22 @@ impl IdValidator {
         }
         Ok(())
     }
+
+    /// Distinguishes a bucket which was already consumed from one which never existed.
+    fn bucket_not_found(&self, bucket_id: BucketId) -> IdValidatorError {
+        if self.consumed_bucket_ids.contains(&bucket_id) {
+            IdValidatorError::BucketAlreadyConsumed(bucket_id)
+        } else {
+            IdValidatorError::BucketNotFound(bucket_id)
+        }
+    }
+
+    /// Distinguishes a proof which was already consumed from one which never existed.
+    fn proof_not_found(&self, proof_id: ProofId) -> IdValidatorError {
+        if self.consumed_proof_ids.contains(&proof_id) {
+            IdValidatorError::ProofAlreadyConsumed(proof_id)
+        } else {
+            IdValidatorError::ProofNotFound(proof_id)
+        }
+    }
 }
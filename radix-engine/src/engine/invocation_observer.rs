@@ -0,0 +1,13 @@
+use scrypto::core::SNodeRef;
+
+/// Observes SNode invocations, e.g. for tracing or profiling.
+///
+/// Calls to `on_enter` and `on_exit` are always paired, with nested invocations in between.
+pub trait InvocationObserver {
+    /// Called before an SNode is invoked.
+    fn on_enter(&mut self, snode_ref: &SNodeRef, function: &str, depth: usize);
+
+    /// Called after an SNode invocation returns, with the elapsed time in nanoseconds
+    /// if available.
+    fn on_exit(&mut self, depth: usize, succeeded: bool, elapsed: Option<u128>);
+}
@@ -1,13 +1,27 @@
+mod block_context;
 mod component_objects;
+mod dangling_policy;
 mod id_allocator;
 mod id_validator;
+mod invocation_observer;
 mod process;
+mod reentrant_method;
 mod track;
+mod unknown_substate_policy;
 mod wasm_env;
 
+pub use block_context::BlockContext;
 pub use component_objects::*;
+pub use dangling_policy::DanglingPolicy;
 pub use id_allocator::*;
 pub use id_validator::*;
+pub use invocation_observer::InvocationObserver;
 pub use process::{Process, SNodeState, SystemApi};
-pub use track::{CommitReceipt, Track};
+pub use reentrant_method::ReentrantMethod;
+pub use track::{
+    CommitReceipt, Track, DEFAULT_MAX_CREATED_SUBSTATES, DEFAULT_MAX_CREATED_VALUES,
+    DEFAULT_MAX_LOG_BYTES, DEFAULT_MAX_LOG_COUNT, DEFAULT_MAX_MINT_BATCH_SIZE,
+    LOGS_TRUNCATED_MESSAGE, NO_LOG_COMPONENT,
+};
+pub use unknown_substate_policy::UnknownSubstatePolicy;
 pub use wasm_env::{EnvModuleResolver, ENGINE_FUNCTION_INDEX, ENGINE_FUNCTION_NAME};
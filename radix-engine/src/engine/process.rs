@@ -2,10 +2,12 @@ use colored::*;
 
 use sbor::*;
 use sbor::path::SborPath;
+use scrypto::abi;
 use scrypto::buffer::*;
 use scrypto::core::{SNodeRef, ScryptoActor};
 use scrypto::engine::api::*;
 use scrypto::engine::types::*;
+use scrypto::resource::{AccessRule, AccessRules};
 use scrypto::rust::borrow::ToOwned;
 use scrypto::rust::collections::*;
 use scrypto::rust::fmt;
@@ -25,7 +27,7 @@ use crate::model::*;
 
 macro_rules! re_trace {
     ($proc:expr, $($args: expr),+) => {
-        if $proc.trace {
+        if $proc.is_traced(Level::Trace) {
             $proc.log(Level::Trace, format!($($args),+));
         }
     };
@@ -33,7 +35,7 @@ macro_rules! re_trace {
 
 macro_rules! re_debug {
     ($proc:expr, $($args: expr),+) => {
-        if $proc.trace {
+        if $proc.is_traced(Level::Debug) {
             $proc.log(Level::Debug, format!($($args),+));
         }
     };
@@ -41,7 +43,7 @@ macro_rules! re_debug {
 
 macro_rules! re_info {
     ($proc:expr, $($args: expr),+) => {
-        if $proc.trace {
+        if $proc.is_traced(Level::Info) {
             $proc.log(Level::Info, format!($($args),+));
         }
     };
@@ -49,12 +51,18 @@ macro_rules! re_info {
 
 macro_rules! re_warn {
     ($proc:expr, $($args: expr),+) => {
-        if $proc.trace {
+        if $proc.is_traced(Level::Warn) {
             $proc.log(Level::Warn, format!($($args),+));
         }
     };
 }
 
+/// The blueprint function which converts component state from a previous package version.
+const MIGRATE_STATE_FUNCTION: &str = "migrate_state";
+
+/// The only component method which may be called while the component is frozen.
+const UNFREEZE_METHOD: &str = "unfreeze";
+
 pub trait SystemApi {
     fn invoke_snode(
         &mut self,
@@ -66,13 +74,13 @@ pub trait SystemApi {
     fn get_non_fungible(
         &mut self,
         non_fungible_address: &NonFungibleAddress,
-    ) -> Option<&NonFungible>;
+    ) -> Result<Option<&NonFungible>, RuntimeError>;
 
     fn set_non_fungible(
         &mut self,
         non_fungible_address: NonFungibleAddress,
         non_fungible: Option<NonFungible>,
-    );
+    ) -> Result<(), RuntimeError>;
 
     fn borrow_global_mut_resource_manager(
         &mut self,
@@ -93,9 +101,48 @@ pub trait SystemApi {
 
     fn take_proof(&mut self, proof_id: ProofId) -> Result<Proof, RuntimeError>;
 
-    fn create_resource(&mut self, resource_manager: ResourceManager) -> ResourceAddress;
+    fn create_resource(
+        &mut self,
+        resource_manager: ResourceManager,
+    ) -> Result<ResourceAddress, RuntimeError>;
+
+    fn create_package(&mut self, package: Package) -> Result<PackageAddress, RuntimeError>;
+
+    fn create_named_component(
+        &mut self,
+        name: &str,
+        component: Component,
+    ) -> Result<ComponentAddress, RuntimeError>;
+
+    /// Returns the addresses of all resources created so far in this transaction.
+    fn new_resources_this_txn(&self) -> Vec<ResourceAddress>;
+
+    /// Sets the index of the transaction instruction about to be executed.
+    fn set_current_instruction_index(&mut self, index: Option<usize>);
+
+    /// Returns the index of the transaction instruction which directly invoked this call,
+    /// or `None` for nested calls.
+    fn current_instruction_index(&self) -> Option<usize>;
 
-    fn create_package(&mut self, package: Package) -> PackageAddress;
+    /// Returns the maximum number of non-fungibles which may be minted in one call.
+    fn max_mint_batch_size(&self) -> usize;
+
+    /// Records a resource movement in the transaction's audit log.
+    fn record_resource_movement(&mut self, movement: ResourceMovement);
+
+    /// Returns the ABI of a blueprint, or `None` if the package or blueprint doesn't exist.
+    fn get_blueprint_abi(
+        &mut self,
+        package_address: PackageAddress,
+        blueprint_name: &str,
+    ) -> Result<Option<abi::Blueprint>, RuntimeError>;
+
+    /// Checks each access rule against the given proofs, using a single simulated auth zone.
+    fn check_access_rules(
+        &mut self,
+        access_rules: Vec<AccessRule>,
+        proof_ids: Vec<ProofId>,
+    ) -> Result<Vec<bool>, RuntimeError>;
 }
 
 pub enum SNodeState {
@@ -139,7 +186,7 @@ enum InterpreterState<'a> {
 struct WasmProcess<'a> {
     /// The call depth
     depth: usize,
-    trace: bool,
+    trace_level: Option<Level>,
     vm: Interpreter,
     interpreter_state: InterpreterState<'a>,
 }
@@ -156,26 +203,36 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         &mut self,
         new_objects: ComponentObjects,
         component_address: ComponentAddress,
-    ) {
-        for (vault_id, vault) in new_objects.vaults {
+    ) -> Result<(), RuntimeError> {
+        // Insert objects in id order, so their substates are put up in a canonical order
+        for (vault_id, vault) in sorted_by_id(new_objects.vaults) {
             self.put_vault(component_address, vault_id, vault);
         }
-        for (lazy_map_id, unclaimed) in new_objects.lazy_maps {
-            for (k, v) in unclaimed.lazy_map {
-                self.put_lazy_map_entry(component_address, lazy_map_id, k, v);
+        for (lazy_map_id, unclaimed) in sorted_by_id(new_objects.lazy_maps) {
+            for (k, v) in unclaimed.lazy_map.into_iter().collect::<BTreeMap<_, _>>() {
+                self.put_lazy_map_entry(component_address, lazy_map_id, k, v)?;
             }
-            for (child_lazy_map_id, child_lazy_map) in unclaimed.descendent_lazy_maps {
-                for (k, v) in child_lazy_map {
-                    self.put_lazy_map_entry(component_address, child_lazy_map_id, k, v);
+            let descendent_lazy_maps = sorted_by_id(unclaimed.descendent_lazy_maps);
+            for (child_lazy_map_id, child_lazy_map) in descendent_lazy_maps {
+                for (k, v) in child_lazy_map.into_iter().collect::<BTreeMap<_, _>>() {
+                    self.put_lazy_map_entry(component_address, child_lazy_map_id, k, v)?;
                 }
             }
-            for (vault_id, vault) in unclaimed.descendent_vaults {
+            for (vault_id, vault) in sorted_by_id(unclaimed.descendent_vaults) {
                 self.put_vault(component_address, vault_id, vault);
             }
         }
+        Ok(())
     }
 }
 
+/// Sorts vaults or lazy maps by their id.
+fn sorted_by_id<V>(objects: HashMap<(Hash, u32), V>) -> Vec<((Hash, u32), V)> {
+    let mut objects: Vec<((Hash, u32), V)> = objects.into_iter().collect();
+    objects.sort_by_key(|((hash, index), _)| (hash.0, *index));
+    objects
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MoveMethod {
     AsReturn,
@@ -186,8 +243,8 @@ pub enum MoveMethod {
 pub struct Process<'r, 'l, L: SubstateStore> {
     /// The call depth
     depth: usize,
-    /// Whether to show trace messages
-    trace: bool,
+    /// The most verbose level of trace messages to show, if any
+    trace_level: Option<Level>,
     /// Transactional state updates
     track: &'r mut Track<'l, L>,
 
@@ -213,7 +270,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     /// Create a new process, which is not started.
     pub fn new(
         depth: usize,
-        trace: bool,
+        trace_level: Option<Level>,
         track: &'r mut Track<'l, L>,
         auth_zone: Option<AuthZone>,
         worktop: Option<Worktop>,
@@ -222,7 +279,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     ) -> Self {
         Self {
             depth,
-            trace,
+            trace_level,
             track,
             buckets,
             proofs,
@@ -236,11 +293,11 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     }
 
     fn new_bucket_id(&mut self) -> Result<BucketId, RuntimeError> {
-        Ok(self.track.new_bucket_id())
+        self.track.new_bucket_id()
     }
 
     fn new_proof_id(&mut self) -> Result<ProofId, RuntimeError> {
-        Ok(self.track.new_proof_id())
+        self.track.new_proof_id()
     }
 
     /// Runs the given export within this process.
@@ -280,7 +337,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     .map_err(RuntimeError::WorktopError)
             }
             SNodeState::Scrypto(actor, component_state) => {
-                let package = self.track.get_package(actor.package_address()).ok_or(
+                let package = self.track.get_package(actor.package_address())?.ok_or(
                     RuntimeError::PackageNotFound(actor.package_address().clone()),
                 )?;
 
@@ -291,7 +348,9 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     ));
                 }
 
-                let (module, memory) = package.load_module().unwrap();
+                let (module, memory) = package
+                    .load_module()
+                    .map_err(RuntimeError::PackageError)?;
 
                 let (interpreter_state, args) = if let Some(component) = component_state {
                     let component_address = actor.component_address().unwrap().clone();
@@ -315,7 +374,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
 
                 self.wasm_process_state = Some(WasmProcess {
                     depth: self.depth,
-                    trace: self.trace,
+                    trace_level: self.trace_level,
                     vm: Interpreter {
                         function,
                         args,
@@ -383,6 +442,12 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         if let Some(_) = &mut self.auth_zone {
             self.invoke_snode(SNodeRef::AuthZoneRef, "clear".to_string(), vec![])?;
         }
+        #[cfg(feature = "sweep_dangling")]
+        if self.depth == 0 {
+            if let DanglingPolicy::SweepTo(account) = self.track.dangling_policy() {
+                self.sweep_dangling_resources(account)?;
+            }
+        }
         self.check_resource()?;
 
         #[cfg(not(feature = "alloc"))]
@@ -403,6 +468,60 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         snode_ref: SNodeRef,
         function: String,
         args: Vec<ScryptoValue>,
+    ) -> Result<ScryptoValue, RuntimeError> {
+        let depth = self.depth;
+        let component_call = match &snode_ref {
+            SNodeRef::Scrypto(ScryptoActor::Component(component_address)) => {
+                self.track
+                    .enter_component_call(*component_address, &function)?;
+                Some((*component_address, function.clone()))
+            }
+            _ => None,
+        };
+
+        let result = match self.track.invocation_observer() {
+            None => self.invoke_snode_internal(snode_ref, function, args),
+            Some(observer) => {
+                observer.on_enter(&snode_ref, &function, depth);
+                #[cfg(not(feature = "alloc"))]
+                let now = std::time::Instant::now();
+
+                let result = self.invoke_snode_internal(snode_ref, function, args);
+
+                #[cfg(feature = "alloc")]
+                let elapsed = None;
+                #[cfg(not(feature = "alloc"))]
+                let elapsed = Some(now.elapsed().as_nanos());
+                if let Some(observer) = self.track.invocation_observer() {
+                    observer.on_exit(depth, result.is_ok(), elapsed);
+                }
+                result
+            }
+        };
+
+        if let Some((component_address, function)) = component_call {
+            self.track.exit_component_call(component_address, &function);
+        }
+
+        #[cfg(debug_assertions)]
+        if result.is_ok() {
+            self.assert_invariants()?;
+        }
+
+        result
+    }
+
+    /// Checks that the values owned by this process are consistent with the ones it references.
+    #[cfg(debug_assertions)]
+    fn assert_invariants(&self) -> Result<(), RuntimeError> {
+        self.owned_snodes.check_invariants(&self.snode_refs)
+    }
+
+    fn invoke_snode_internal(
+        &mut self,
+        snode_ref: SNodeRef,
+        function: String,
+        args: Vec<ScryptoValue>,
     ) -> Result<ScryptoValue, RuntimeError> {
         // Authorization and state load
         let (mut snode, method_auths) = match &snode_ref {
@@ -438,24 +557,40 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                         ))
                     }
                     ScryptoActor::Component(component_address) => {
-                        let component = self
+                        let mut component = self
                             .track
                             .borrow_global_mut_component(component_address.clone())?;
+                        if component.is_frozen() && function != UNFREEZE_METHOD {
+                            return Err(RuntimeError::ComponentFrozen(*component_address));
+                        }
                         let package_address = component.package_address();
                         let blueprint_name = component.blueprint_name().to_string();
                         let export_name = format!("{}_main", blueprint_name);
 
                         let package = self
                             .track
-                            .get_package(&package_address)
+                            .get_package(&package_address)?
                             .ok_or(RuntimeError::PackageNotFound(package_address))?;
+                        let package_version = package.version();
                         // TODO: Remove clone
                         let schema = package
                             .load_blueprint_schema(&blueprint_name)
-                            .unwrap()
+                            .map_err(|_| {
+                                RuntimeError::BlueprintNotFound(
+                                    package_address,
+                                    blueprint_name.clone(),
+                                )
+                            })?
                             .clone();
 
+                        if component.package_version() != package_version {
+                            self.migrate_component_state(&mut component, package_version)?;
+                        }
+
                         let (_, method_auths) = component.method_authorization(&schema, &function);
+                        if method_auths.contains(&MethodAuthorization::Public) {
+                            re_debug!(self, "Method is public: {}", function);
+                        }
                         Ok((
                             SNodeState::Scrypto(
                                 ScryptoActorInfo::component(
@@ -490,7 +625,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                 let resource_address = bucket.resource_address();
                 let method_auth = self
                     .track
-                    .get_resource_manager(&resource_address)
+                    .get_resource_manager(&resource_address)?
                     .unwrap()
                     .get_auth(&function, &args);
                 Ok((SNodeState::Bucket(bucket), vec![method_auth.clone()]))
@@ -503,7 +638,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                 let resource_address = bucket.resource_address();
                 let method_auth = self
                     .track
-                    .get_resource_manager(&resource_address)
+                    .get_resource_manager(&resource_address)?
                     .unwrap()
                     .get_auth(&function, &args);
                 Ok((
@@ -524,17 +659,16 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     (None, vault)
                 } else if !self.snode_refs.vault_ids.contains(vault_id) {
                     return Err(RuntimeError::VaultNotFound(*vault_id));
-                } else if let Some(WasmProcess { interpreter_state: InterpreterState::Component { component_address, .. }, .. }) = &self.wasm_process_state {
-                    let vault = self.track.borrow_vault_mut(component_address, vault_id);
-                    (Some(*component_address), vault)
                 } else {
-                    panic!("Should never get here");
+                    let component_address = self.assert_visible(ValueId::Vault(*vault_id))?;
+                    let vault = self.track.borrow_vault_mut(&component_address, vault_id)?;
+                    (Some(component_address), vault)
                 };
 
                 let resource_address = vault.resource_address();
                 let method_auth = self
                     .track
-                    .get_resource_manager(&resource_address)
+                    .get_resource_manager(&resource_address)?
                     .unwrap()
                     .get_auth(&function, &args);
                 Ok((
@@ -567,6 +701,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     .check(&auth_zones)
                     .map_err(|error| RuntimeError::AuthorizationError {
                         function: function.clone(),
+                        failed_rule: method_auth.failed_rule(&auth_zones),
                         authorization: method_auth,
                         error
                     })?;
@@ -576,12 +711,18 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         // Execution
         let result = match snode {
             SNodeState::Proof(proof) => {
-                proof.main_consume(function.as_str())
-                    .map_err(RuntimeError::ProofError)
+                let output = proof.main_consume(function.as_str())
+                    .map_err(RuntimeError::ProofError)?;
+                self.process_consumed_return_data(&output)?;
+                Ok(output)
             },
-            SNodeState::Bucket(bucket) => match function.as_str() {
-                "burn" => bucket.drop(self).map_err(RuntimeError::BucketError),
-                _ => Err(RuntimeError::IllegalSystemCall),
+            SNodeState::Bucket(bucket) => {
+                let output = match function.as_str() {
+                    "burn" => bucket.drop(self).map_err(RuntimeError::BucketError),
+                    _ => Err(RuntimeError::IllegalSystemCall),
+                }?;
+                self.process_consumed_return_data(&output)?;
+                Ok(output)
             },
             _ => {
                 // Figure out what buckets and proofs to move from this process
@@ -602,7 +743,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
 
                 let mut process = Process::new(
                     self.depth + 1,
-                    self.trace,
+                    self.trace_level,
                     self.track,
                     process_auth_zone,
                     None,
@@ -684,7 +825,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             None,
         );
 
-        let mut process = Process::new(self.depth + 1, self.trace, self.track, None, None, HashMap::new(), HashMap::new());
+        let mut process = Process::new(self.depth + 1, self.trace_level, self.track, None, None, HashMap::new(), HashMap::new());
         let result = process
             .run(&mut snode, String::new(), Vec::new())
             .map(|(r, _, _)| r);
@@ -693,6 +834,96 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         result
     }
 
+    /// Migrates the state of a component instantiated from a previous version of its package.
+    fn migrate_component_state(
+        &mut self,
+        component: &mut Component,
+        package_version: u32,
+    ) -> Result<(), RuntimeError> {
+        let package_address = component.package_address();
+        let blueprint_name = component.blueprint_name().to_string();
+        let (_, functions, _): (Type, Vec<abi::Function>, Vec<abi::Method>) = self
+            .call_abi(package_address, &blueprint_name)
+            .and_then(|rtn| scrypto_decode(&rtn.raw).map_err(RuntimeError::AbiValidationError))?;
+
+        if functions.iter().any(|f| f.name == MIGRATE_STATE_FUNCTION) {
+            re_debug!(self, "Migrating component state: version = {}", package_version);
+            let mut snode = SNodeState::Scrypto(
+                ScryptoActorInfo::blueprint(
+                    package_address,
+                    blueprint_name.clone(),
+                    format!("{}_main", blueprint_name),
+                ),
+                None,
+            );
+            let mut process = Process::new(self.depth + 1, self.trace_level, self.track, None, None, HashMap::new(), HashMap::new());
+            let (rtn, _, _) = process.run(
+                &mut snode,
+                MIGRATE_STATE_FUNCTION.to_string(),
+                vec![ScryptoValue::from_value(&component.state().to_vec())],
+            )?;
+            let new_state: Vec<u8> =
+                scrypto_decode(&rtn.raw).map_err(RuntimeError::InvalidMigratedState)?;
+
+            // The layout may change, but vaults and lazy maps must be carried over as-is
+            let old_refs = Self::process_entry_data(component.state())?;
+            let mut new_refs = Self::process_entry_data(&new_state)?;
+            new_refs.remove(&old_refs)?;
+            if !new_refs.vault_ids.is_empty() {
+                return Err(RuntimeError::VaultNotAllowed);
+            }
+            if !new_refs.lazy_map_ids.is_empty() {
+                return Err(RuntimeError::LazyMapNotAllowed);
+            }
+
+            component.set_state(new_state);
+        }
+
+        component.set_package_version(package_version);
+        Ok(())
+    }
+
+    /// Returns the component through which a referenced value is visible to this process.
+    pub fn assert_visible(&self, value_id: ValueId) -> Result<ComponentAddress, RuntimeError> {
+        match &self.wasm_process_state {
+            Some(WasmProcess {
+                interpreter_state: InterpreterState::Component { component_address, .. },
+                ..
+            }) => Ok(*component_address),
+            _ => Err(RuntimeError::InvalidDataAccess(value_id)),
+        }
+    }
+
+    /// Deposits all dangling buckets and worktop resources into the given account.
+    #[cfg(feature = "sweep_dangling")]
+    fn sweep_dangling_resources(&mut self, account: ComponentAddress) -> Result<(), RuntimeError> {
+        if let Some(worktop) = &self.worktop {
+            if !worktop.is_empty() {
+                self.invoke_snode(SNodeRef::WorktopRef, "drain".to_string(), vec![])?;
+            }
+        }
+
+        let buckets: Vec<scrypto::resource::Bucket> = self
+            .buckets
+            .keys()
+            .map(|bucket_id| scrypto::resource::Bucket(*bucket_id))
+            .collect();
+        if !buckets.is_empty() {
+            re_warn!(
+                self,
+                "Sweeping {} dangling buckets into {}",
+                buckets.len(),
+                account
+            );
+            self.invoke_snode(
+                SNodeRef::Scrypto(ScryptoActor::Component(account)),
+                "deposit_batch".to_string(),
+                vec![ScryptoValue::from_value(&buckets)],
+            )?;
+        }
+        Ok(())
+    }
+
     /// Checks resource leak.
     fn check_resource(&self) -> Result<(), RuntimeError> {
         re_debug!(self, "Resource check started");
@@ -731,6 +962,14 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         }
     }
 
+    /// Returns whether trace messages of the given level are shown.
+    pub fn is_traced(&self, level: Level) -> bool {
+        match self.trace_level {
+            Some(trace_level) => level <= trace_level,
+            None => false,
+        }
+    }
+
     /// Logs a message to the console.
     #[allow(unused_variables)]
     pub fn log(&self, level: Level, msg: String) {
@@ -766,6 +1005,21 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(())
     }
 
+    /// Checks the output of a consumed snode, which has no process to move buckets or proofs from
+    fn process_consumed_return_data(
+        &mut self,
+        validated: &ScryptoValue,
+    ) -> Result<(), RuntimeError> {
+        self.process_return_data(validated)?;
+        if !validated.bucket_ids.is_empty() {
+            return Err(RuntimeError::BucketNotAllowed);
+        }
+        if !validated.proof_ids.is_empty() {
+            return Err(RuntimeError::ProofNotAllowed);
+        }
+        Ok(())
+    }
+
     /// Process and parse entry data from any component object (components and maps)
     fn process_entry_data(data: &[u8]) -> Result<ComponentObjectRefs, RuntimeError> {
         let validated =
@@ -913,6 +1167,8 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         }
 
         let output: O = handler(self, input).map_err(Trap::from)?;
+        #[cfg(debug_assertions)]
+        self.assert_invariants().map_err(Trap::from)?;
         let output_bytes = scrypto_encode(&output);
         let output_ptr = self.send_bytes(&output_bytes).map_err(Trap::from)?;
         if output_bytes.len() <= 1024 {
@@ -933,6 +1189,35 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     // SYSTEM CALL HANDLERS START
     //============================
 
+    fn new_component(
+        &mut self,
+        blueprint_name: String,
+        access_rules_list: Vec<AccessRules>,
+        state: Vec<u8>,
+    ) -> Result<Component, RuntimeError> {
+        let wasm_process = self
+            .wasm_process_state
+            .as_mut()
+            .ok_or(RuntimeError::IllegalSystemCall)?;
+        let package_address = wasm_process.vm.actor.package_address().clone();
+        let mut component =
+            Component::new(package_address, blueprint_name, access_rules_list, state);
+        let package_version = self
+            .track
+            .get_package(&package_address)?
+            .ok_or(RuntimeError::PackageNotFound(package_address))?
+            .version();
+        component.set_package_version(package_version);
+        if component.authorization().is_empty() {
+            re_warn!(
+                self,
+                "No access rules configured for new component of blueprint: {}",
+                component.blueprint_name()
+            );
+        }
+        Ok(component)
+    }
+
     fn handle_create_component(
         &mut self,
         input: CreateComponentInput,
@@ -940,20 +1225,26 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         let data = Self::process_entry_data(&input.state)?;
         let new_objects = self.owned_snodes.take(data)?;
 
-        let wasm_process = self
-            .wasm_process_state
-            .as_mut()
-            .ok_or(RuntimeError::IllegalSystemCall)?;
-        let package_address = wasm_process.vm.actor.package_address().clone();
-        let component = Component::new(
-            package_address,
-            input.blueprint_name,
-            input.access_rules_list,
-            input.state,
-        );
-        let component_address = self.track.create_component(component);
+        let component =
+            self.new_component(input.blueprint_name, input.access_rules_list, input.state)?;
+        let component_address = self.track.create_component(component)?;
         self.track
-            .insert_objects_into_component(new_objects, component_address);
+            .insert_objects_into_component(new_objects, component_address)?;
+
+        Ok(CreateComponentOutput { component_address })
+    }
+
+    fn handle_create_named_component(
+        &mut self,
+        input: CreateNamedComponentInput,
+    ) -> Result<CreateComponentOutput, RuntimeError> {
+        let data = Self::process_entry_data(&input.state)?;
+        let component =
+            self.new_component(input.blueprint_name, input.access_rules_list, input.state)?;
+        let component_address = self.create_named_component(&input.name, component)?;
+        let new_objects = self.owned_snodes.take(data)?;
+        self.track
+            .insert_objects_into_component(new_objects, component_address)?;
 
         Ok(CreateComponentOutput { component_address })
     }
@@ -964,7 +1255,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     ) -> Result<GetComponentInfoOutput, RuntimeError> {
         let component = self
             .track
-            .get_component(input.component_address)
+            .get_component(input.component_address)?
             .ok_or(RuntimeError::ComponentNotFound(input.component_address))?;
 
         Ok(GetComponentInfoOutput {
@@ -1015,7 +1306,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         }?;
 
         let new_objects = self.owned_snodes.take(new_set)?;
-        self.track.insert_objects_into_component(new_objects, *component_address);
+        self.track.insert_objects_into_component(new_objects, *component_address)?;
 
         // TODO: Verify that process_owned_objects is empty
 
@@ -1028,7 +1319,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         &mut self,
         _input: CreateLazyMapInput,
     ) -> Result<CreateLazyMapOutput, RuntimeError> {
-        let lazy_map_id = self.track.new_lazy_map_id();
+        let lazy_map_id = self.track.new_lazy_map_id()?;
         self
             .owned_snodes
             .lazy_maps
@@ -1050,22 +1341,20 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             return Err(RuntimeError::LazyMapNotFound(input.lazy_map_id));
         }
 
-        if let Some(WasmProcess { interpreter_state: InterpreterState::Component { component_address, .. }, .. }) = &self.wasm_process_state {
-            let value = self.track.get_lazy_map_entry(
-                *component_address,
-                &input.lazy_map_id,
-                &input.key,
-            );
-            if value.is_some() {
-                let map_entry_objects =
-                    Self::process_entry_data(&value.as_ref().unwrap()).unwrap();
-                self.snode_refs.extend(map_entry_objects);
-            }
-
-            return Ok(GetLazyMapEntryOutput { value });
+        let component_address = self.assert_visible(ValueId::LazyMap(input.lazy_map_id))?;
+        let value = self.track.get_lazy_map_entry(
+            component_address,
+            &input.lazy_map_id,
+            &input.key,
+        )?;
+        if let Some(entry) = &value {
+            let map_entry_objects = Self::process_entry_data(entry).map_err(|_| {
+                RuntimeError::InvalidLazyMapEntry(input.lazy_map_id, input.key.clone())
+            })?;
+            self.snode_refs.extend(map_entry_objects);
         }
 
-        panic!("Should not get here.");
+        Ok(GetLazyMapEntryOutput { value })
     }
 
     fn handle_put_lazy_map_entry(
@@ -1095,7 +1384,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                         *component_address,
                         &input.lazy_map_id,
                         &input.key,
-                    );
+                    )?;
                     Ok((
                         old_value,
                         Committed {
@@ -1110,7 +1399,9 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         let mut new_entry_object_refs = Self::process_entry_data(&input.value)?;
         let old_entry_object_refs = match old_value {
             None => ComponentObjectRefs::new(),
-            Some(e) => Self::process_entry_data(&e).unwrap(),
+            Some(e) => Self::process_entry_data(&e).map_err(|_| {
+                RuntimeError::InvalidLazyMapEntry(input.lazy_map_id, input.key.clone())
+            })?,
         };
         new_entry_object_refs.remove(&old_entry_object_refs)?;
 
@@ -1135,6 +1426,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                 self
                     .owned_snodes
                     .insert_objects_into_map(new_objects, &root);
+                self.owned_snodes.check_reachable(&root)?;
             }
             Committed { component_address } => {
                 self.track.put_lazy_map_entry(
@@ -1142,9 +1434,9 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     input.lazy_map_id,
                     input.key,
                     input.value,
-                );
+                )?;
                 self.track
-                    .insert_objects_into_component(new_objects, component_address);
+                    .insert_objects_into_component(new_objects, component_address)?;
             }
         }
 
@@ -1157,7 +1449,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     ) -> Result<CreateEmptyVaultOutput, RuntimeError> {
         let definition = self
             .track
-            .get_resource_manager(&input.resource_address)
+            .get_resource_manager(&input.resource_address)?
             .ok_or(RuntimeError::ResourceManagerNotFound(
                 input.resource_address,
             ))?;
@@ -1166,7 +1458,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             input.resource_address,
             definition.resource_type(),
         ));
-        let vault_id = self.track.new_vault_id();
+        let vault_id = self.track.new_vault_id()?;
         self
             .owned_snodes
             .vaults
@@ -1191,7 +1483,13 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     }
 
     fn handle_emit_log(&mut self, input: EmitLogInput) -> Result<EmitLogOutput, RuntimeError> {
-        self.track.add_log(input.level, input.message);
+        let component_address = self
+            .wasm_process_state
+            .as_ref()
+            .and_then(|wasm_process| wasm_process.vm.actor.component_address())
+            .unwrap_or(NO_LOG_COMPONENT);
+        self.track
+            .add_log(input.level, component_address, input.message);
 
         Ok(EmitLogOutput {})
     }
@@ -1221,7 +1519,16 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         _input: GetTransactionHashInput,
     ) -> Result<GetTransactionHashOutput, RuntimeError> {
         Ok(GetTransactionHashOutput {
-            transaction_hash: self.track.transaction_hash(),
+            transaction_hash: self.track.block_context().transaction_hash,
+        })
+    }
+
+    fn handle_get_transaction_signers(
+        &mut self,
+        _input: GetTransactionSignersInput,
+    ) -> Result<GetTransactionSignersOutput, RuntimeError> {
+        Ok(GetTransactionSignersOutput {
+            transaction_signers: self.track.transaction_signers(),
         })
     }
 
@@ -1230,7 +1537,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         _input: GetCurrentEpochInput,
     ) -> Result<GetCurrentEpochOutput, RuntimeError> {
         Ok(GetCurrentEpochOutput {
-            current_epoch: self.track.current_epoch(),
+            current_epoch: self.track.block_context().epoch,
         })
     }
 
@@ -1239,7 +1546,57 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         _input: GenerateUuidInput,
     ) -> Result<GenerateUuidOutput, RuntimeError> {
         Ok(GenerateUuidOutput {
-            uuid: self.track.new_uuid(),
+            uuid: self.track.new_uuid()?,
+        })
+    }
+
+    fn handle_set_component_frozen(
+        &mut self,
+        input: SetComponentFrozenInput,
+    ) -> Result<SetComponentFrozenOutput, RuntimeError> {
+        let wasm_process = self
+            .wasm_process_state
+            .as_mut()
+            .ok_or(RuntimeError::IllegalSystemCall)?;
+        match &mut wasm_process.interpreter_state {
+            InterpreterState::Component {
+                ref mut component,
+                component_address,
+                ..
+            } => {
+                if *component_address != input.component_address {
+                    return Err(RuntimeError::ComponentNotActor(input.component_address));
+                }
+                component.set_frozen(input.frozen);
+                Ok(SetComponentFrozenOutput {})
+            }
+            _ => Err(RuntimeError::IllegalSystemCall),
+        }
+    }
+
+    fn handle_get_blueprint_abi(
+        &mut self,
+        input: GetBlueprintAbiInput,
+    ) -> Result<GetBlueprintAbiOutput, RuntimeError> {
+        let abi = self.get_blueprint_abi(input.package_address, &input.blueprint_name)?;
+        Ok(GetBlueprintAbiOutput { abi })
+    }
+
+    fn handle_get_new_resources(
+        &mut self,
+        _input: GetNewResourcesInput,
+    ) -> Result<GetNewResourcesOutput, RuntimeError> {
+        Ok(GetNewResourcesOutput {
+            resource_addresses: self.new_resources_this_txn(),
+        })
+    }
+
+    fn handle_get_instruction_index(
+        &mut self,
+        _input: GetInstructionIndexInput,
+    ) -> Result<GetInstructionIndexOutput, RuntimeError> {
+        Ok(GetInstructionIndexOutput {
+            instruction_index: self.current_instruction_index(),
         })
     }
 
@@ -1254,20 +1611,20 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         });
     }
     
-    fn handle_check_access_rule(&mut self, input: CheckAccessRuleInput) -> Result<CheckAccessRuleOutput, RuntimeError> {
-        let proofs = input.proof_ids
-            .iter()
-            .map(|proof_id| self.proofs.get(&proof_id).ok_or(RuntimeError::ProofNotFound(proof_id.clone())).unwrap().clone())
-            .collect::<Vec<Proof>>();
-        let mut simulated_auth_zone = AuthZone::new_with_proofs(proofs);
-
-        let method_authorization = convert(&Type::Unit, &Value::Unit, &input.access_rule);
-        let is_authorized = method_authorization.check(&[&simulated_auth_zone]).is_ok();
-        simulated_auth_zone.main("clear", Vec::new(), self).map_err(RuntimeError::AuthZoneError)?;
+    fn handle_check_access_rule(
+        &mut self,
+        input: CheckAccessRuleInput,
+    ) -> Result<CheckAccessRuleOutput, RuntimeError> {
+        let is_authorized = self.check_access_rules(vec![input.access_rule], input.proof_ids)?[0];
+        Ok(CheckAccessRuleOutput { is_authorized })
+    }
 
-        return Ok(CheckAccessRuleOutput{
-            is_authorized
-        });
+    fn handle_check_access_rules(
+        &mut self,
+        input: CheckAccessRulesInput,
+    ) -> Result<CheckAccessRulesOutput, RuntimeError> {
+        let results = self.check_access_rules(input.access_rules, input.proof_ids)?;
+        Ok(CheckAccessRulesOutput { results })
     }
 
     //============================
@@ -1288,7 +1645,7 @@ impl<'r, 'l, L: SubstateStore> SystemApi for Process<'r, 'l, L> {
     fn get_non_fungible(
         &mut self,
         non_fungible_address: &NonFungibleAddress,
-    ) -> Option<&NonFungible> {
+    ) -> Result<Option<&NonFungible>, RuntimeError> {
         self.track.get_non_fungible(non_fungible_address)
     }
 
@@ -1296,7 +1653,7 @@ impl<'r, 'l, L: SubstateStore> SystemApi for Process<'r, 'l, L> {
         &mut self,
         non_fungible_address: NonFungibleAddress,
         non_fungible: Option<NonFungible>,
-    ) {
+    ) -> Result<(), RuntimeError> {
         self.track
             .set_non_fungible(non_fungible_address, non_fungible)
     }
@@ -1344,13 +1701,105 @@ impl<'r, 'l, L: SubstateStore> SystemApi for Process<'r, 'l, L> {
             .ok_or(RuntimeError::BucketNotFound(bucket_id))
     }
 
-    fn create_resource(&mut self, resource_manager: ResourceManager) -> ResourceAddress {
+    fn create_resource(
+        &mut self,
+        resource_manager: ResourceManager,
+    ) -> Result<ResourceAddress, RuntimeError> {
         self.track.create_resource_manager(resource_manager)
     }
 
-    fn create_package(&mut self, package: Package) -> PackageAddress {
+    fn create_package(&mut self, package: Package) -> Result<PackageAddress, RuntimeError> {
         self.track.create_package(package)
     }
+
+    fn create_named_component(
+        &mut self,
+        name: &str,
+        component: Component,
+    ) -> Result<ComponentAddress, RuntimeError> {
+        self.track.create_named_component(component, name)
+    }
+
+    fn new_resources_this_txn(&self) -> Vec<ResourceAddress> {
+        self.track.new_resource_addresses()
+    }
+
+    fn set_current_instruction_index(&mut self, index: Option<usize>) {
+        self.track.set_instruction_index(index);
+    }
+
+    fn current_instruction_index(&self) -> Option<usize> {
+        // The transaction processor runs at depth 0, so only its direct callees are at depth 1
+        if self.depth == 1 {
+            self.track.instruction_index()
+        } else {
+            None
+        }
+    }
+
+    fn max_mint_batch_size(&self) -> usize {
+        self.track.max_mint_batch_size()
+    }
+
+    fn record_resource_movement(&mut self, movement: ResourceMovement) {
+        self.track.record_resource_movement(movement);
+    }
+
+    fn get_blueprint_abi(
+        &mut self,
+        package_address: PackageAddress,
+        blueprint_name: &str,
+    ) -> Result<Option<abi::Blueprint>, RuntimeError> {
+        let blueprint_exists = self
+            .track
+            .get_package(&package_address)?
+            .map_or(false, |package| {
+                package.load_blueprint_schema(blueprint_name).is_ok()
+            });
+        if !blueprint_exists {
+            return Ok(None);
+        }
+
+        let output: (Type, Vec<abi::Function>, Vec<abi::Method>) = self
+            .call_abi(package_address, blueprint_name)
+            .and_then(|rtn| scrypto_decode(&rtn.raw).map_err(RuntimeError::AbiValidationError))?;
+
+        Ok(Some(abi::Blueprint {
+            package_address: package_address.to_string(),
+            blueprint_name: blueprint_name.to_owned(),
+            functions: output.1,
+            methods: output.2,
+        }))
+    }
+
+    fn check_access_rules(
+        &mut self,
+        access_rules: Vec<AccessRule>,
+        proof_ids: Vec<ProofId>,
+    ) -> Result<Vec<bool>, RuntimeError> {
+        let mut proofs = Vec::new();
+        for proof_id in proof_ids {
+            let proof = self
+                .proofs
+                .get(&proof_id)
+                .ok_or(RuntimeError::ProofNotFound(proof_id))?;
+            proofs.push(proof.clone());
+        }
+        let mut simulated_auth_zone = AuthZone::new_with_proofs(proofs);
+
+        let results = access_rules
+            .iter()
+            .map(|access_rule| {
+                let method_authorization = convert(&Type::Unit, &Value::Unit, access_rule);
+                method_authorization.check(&[&simulated_auth_zone]).is_ok()
+            })
+            .collect();
+        simulated_auth_zone
+            .main("clear", Vec::new(), self)
+            .map_err(RuntimeError::AuthZoneError)?;
+
+        Ok(results)
+    }
 }
 
 impl<'r, 'l, L: SubstateStore> Externals for Process<'r, 'l, L> {
@@ -1364,9 +1813,14 @@ impl<'r, 'l, L: SubstateStore> Externals for Process<'r, 'l, L> {
                 let operation: u32 = args.nth_checked(0)?;
                 match operation {
                     CREATE_COMPONENT => self.handle(args, Self::handle_create_component),
+                    CREATE_NAMED_COMPONENT => {
+                        self.handle(args, Self::handle_create_named_component)
+                    }
                     GET_COMPONENT_INFO => self.handle(args, Self::handle_get_component_info),
                     GET_COMPONENT_STATE => self.handle(args, Self::handle_get_component_state),
                     PUT_COMPONENT_STATE => self.handle(args, Self::handle_put_component_state),
+                    SET_COMPONENT_FROZEN => self.handle(args, Self::handle_set_component_frozen),
+                    GET_BLUEPRINT_ABI => self.handle(args, Self::handle_get_blueprint_abi),
 
                     CREATE_LAZY_MAP => self.handle(args, Self::handle_create_lazy_map),
                     GET_LAZY_MAP_ENTRY => self.handle(args, Self::handle_get_lazy_map_entry),
@@ -1379,11 +1833,17 @@ impl<'r, 'l, L: SubstateStore> Externals for Process<'r, 'l, L> {
                     EMIT_LOG => self.handle(args, Self::handle_emit_log),
                     GET_CALL_DATA => self.handle(args, Self::handle_get_call_data),
                     GET_TRANSACTION_HASH => self.handle(args, Self::handle_get_transaction_hash),
+                    GET_TRANSACTION_SIGNERS => {
+                        self.handle(args, Self::handle_get_transaction_signers)
+                    }
                     GET_CURRENT_EPOCH => self.handle(args, Self::handle_get_current_epoch),
                     GENERATE_UUID => self.handle(args, Self::handle_generate_uuid),
                     GET_ACTOR => self.handle(args, Self::handle_get_actor),
+                    GET_NEW_RESOURCES => self.handle(args, Self::handle_get_new_resources),
+                    GET_INSTRUCTION_INDEX => self.handle(args, Self::handle_get_instruction_index),
 
                     CHECK_ACCESS_RULE => self.handle(args, Self::handle_check_access_rule),
+                    CHECK_ACCESS_RULES => self.handle(args, Self::handle_check_access_rules),
 
                     _ => Err(RuntimeError::InvalidRequestCode(operation).into()),
                 }
@@ -1392,3 +1852,39 @@ impl<'r, 'l, L: SubstateStore> Externals for Process<'r, 'l, L> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scrypto::crypto::hash;
+    use scrypto::resource::{Bucket, Proof, Vault};
+
+    #[test]
+    fn consumed_snode_cannot_return_values() {
+        // Arrange
+        let mut substate_store = InMemorySubstateStore::with_bootstrap();
+        let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+        let mut process = Process::new(
+            0,
+            None,
+            &mut track,
+            None,
+            None,
+            HashMap::new(),
+            HashMap::new(),
+        );
+
+        // Act
+        let unit = process.process_consumed_return_data(&ScryptoValue::from_value(&()));
+        let bucket = process.process_consumed_return_data(&ScryptoValue::from_value(&Bucket(1024)));
+        let proof = process.process_consumed_return_data(&ScryptoValue::from_value(&Proof(1024)));
+        let vault = process
+            .process_consumed_return_data(&ScryptoValue::from_value(&Vault((hash([0u8]), 1024))));
+
+        // Assert
+        assert_eq!(unit, Ok(()));
+        assert_eq!(bucket, Err(RuntimeError::BucketNotAllowed));
+        assert_eq!(proof, Err(RuntimeError::ProofNotAllowed));
+        assert_eq!(vault, Err(RuntimeError::VaultNotAllowed));
+    }
+}
@@ -0,0 +1,25 @@
+use scrypto::engine::types::*;
+use scrypto::rust::string::String;
+
+/// A blueprint method which may be called again while it is already on the call stack.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReentrantMethod {
+    pub package_address: PackageAddress,
+    pub blueprint_name: String,
+    /// The allowed method, or `None` to allow every method of the blueprint.
+    pub method: Option<String>,
+}
+
+impl ReentrantMethod {
+    /// Returns whether this entry allows re-entering the given method.
+    pub fn allows(
+        &self,
+        package_address: PackageAddress,
+        blueprint_name: &str,
+        method: &str,
+    ) -> bool {
+        self.package_address == package_address
+            && self.blueprint_name == blueprint_name
+            && self.method.as_ref().map_or(true, |m| m == method)
+    }
+}
@@ -1,8 +1,12 @@
 use indexmap::IndexMap;
+use sbor::{Decode, DecodeError, Encode};
+use scrypto::buffer::scrypto_encode;
 use scrypto::constants::*;
 use scrypto::engine::types::*;
+use scrypto::rust::boxed::Box;
 use scrypto::rust::collections::*;
 use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
 use scrypto::rust::vec::Vec;
 
 use crate::engine::*;
@@ -10,6 +14,7 @@ use crate::errors::RuntimeError;
 use crate::ledger::*;
 use crate::model::*;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CommitReceipt {
     pub down_substates: HashSet<(Hash, u32)>,
     pub up_substates: Vec<(Hash, u32)>,
@@ -32,6 +37,29 @@ impl CommitReceipt {
     }
 }
 
+/// The default maximum number of log messages kept by a track.
+pub const DEFAULT_MAX_LOG_COUNT: usize = 1024;
+
+/// The default maximum total size, in bytes, of log messages kept by a track.
+pub const DEFAULT_MAX_LOG_BYTES: usize = 1024 * 1024;
+
+/// The default maximum number of values (buckets, proofs, vaults, lazy maps and components)
+/// a transaction may create.
+pub const DEFAULT_MAX_CREATED_VALUES: usize = 64 * 1024;
+
+/// The default maximum number of non-fungibles which may be minted in one call.
+pub const DEFAULT_MAX_MINT_BATCH_SIZE: usize = 10_000;
+
+/// The default maximum number of new substates a transaction may write to the ledger.
+pub const DEFAULT_MAX_CREATED_SUBSTATES: usize = 64 * 1024;
+
+/// The message recorded once logs start being dropped.
+pub const LOGS_TRUNCATED_MESSAGE: &str = "Logs truncated";
+
+/// The component recorded for logs not emitted by a component, e.g. by blueprint functions
+/// or the engine itself.
+pub const NO_LOG_COMPONENT: ComponentAddress = ComponentAddress([0u8; 26]);
+
 struct SubstateUpdate<T> {
     prev_id: Option<(Hash, u32)>,
     value: T,
@@ -46,10 +74,24 @@ struct SubstateUpdate<T> {
 ///
 pub struct Track<'s, S: SubstateStore> {
     substate_store: &'s mut S,
-    transaction_hash: Hash,
+    block_context: BlockContext,
     transaction_signers: Vec<EcdsaPublicKey>,
     id_allocator: IdAllocator,
-    logs: Vec<(Level, String)>,
+    logs: Vec<(Level, ComponentAddress, String)>,
+    resource_movements: Vec<ResourceMovement>,
+    logs_size: usize,
+    logs_truncated: bool,
+    max_log_count: usize,
+    max_log_bytes: usize,
+    invocation_observer: Option<Box<dyn InvocationObserver>>,
+    dangling_policy: DanglingPolicy,
+    unknown_substate_policy: UnknownSubstatePolicy,
+    created_values: usize,
+    max_created_values: usize,
+    max_mint_batch_size: usize,
+    max_created_substates: usize,
+    max_worktop_resources: usize,
+    instruction_index: Option<usize>,
 
     packages: IndexMap<PackageAddress, SubstateUpdate<Package>>,
 
@@ -59,40 +101,96 @@ pub struct Track<'s, S: SubstateStore> {
     resource_managers: IndexMap<ResourceAddress, SubstateUpdate<ResourceManager>>,
     borrowed_resource_managers: HashMap<ResourceAddress, Option<(Hash, u32)>>,
 
-    vaults: HashMap<(ComponentAddress, VaultId), SubstateUpdate<Vault>>,
+    vaults: IndexMap<(ComponentAddress, VaultId), SubstateUpdate<Vault>>,
     borrowed_vaults: HashMap<(ComponentAddress, VaultId), Option<(Hash, u32)>>,
 
-    non_fungibles: HashMap<NonFungibleAddress, SubstateUpdate<Option<NonFungible>>>,
+    non_fungibles: IndexMap<NonFungibleAddress, SubstateUpdate<Option<NonFungible>>>,
+
+    lazy_map_entries: IndexMap<(ComponentAddress, LazyMapId, Vec<u8>), SubstateUpdate<Vec<u8>>>,
 
-    lazy_map_entries: HashMap<(ComponentAddress, LazyMapId, Vec<u8>), SubstateUpdate<Vec<u8>>>,
+    raw_substates: IndexMap<Vec<u8>, SubstateUpdate<Vec<u8>>>,
+    borrowed_raw_substates: HashMap<Vec<u8>, Option<(Hash, u32)>>,
+
+    /// Substates of unknown kinds loaded as raw bytes, by encoded address
+    #[cfg(feature = "unknown_substates_as_raw")]
+    unknown_substates: HashMap<Vec<u8>, Vec<u8>>,
+
+    /// Encoded addresses which may only be read in this transaction
+    readonly_addresses: HashSet<Vec<u8>>,
+
+    /// Component methods currently on the call stack, with how many times each was entered
+    active_component_calls: HashMap<(ComponentAddress, String), usize>,
+
+    /// Blueprint methods which may be re-entered
+    allowed_reentrant_methods: Vec<ReentrantMethod>,
+
+    /// Package and blueprint of every component borrowed in this transaction
+    component_blueprints: HashMap<ComponentAddress, (PackageAddress, String)>,
 }
 
 impl<'s, S: SubstateStore> Track<'s, S> {
+    /// Creates a track for the given transaction, in the store's current epoch.
     pub fn new(
         substate_store: &'s mut S,
         transaction_hash: Hash,
         transaction_signers: Vec<EcdsaPublicKey>,
+    ) -> Self {
+        let block_context = BlockContext {
+            epoch: substate_store.get_epoch(),
+            timestamp: 0,
+            transaction_hash,
+        };
+        Self::with_block_context(substate_store, block_context, transaction_signers)
+    }
+
+    /// Creates a track which executes within the given block context.
+    pub fn with_block_context(
+        substate_store: &'s mut S,
+        block_context: BlockContext,
+        transaction_signers: Vec<EcdsaPublicKey>,
     ) -> Self {
         Self {
             substate_store,
-            transaction_hash,
+            block_context,
             transaction_signers,
             id_allocator: IdAllocator::new(IdSpace::Application),
             logs: Vec::new(),
+            resource_movements: Vec::new(),
+            logs_size: 0,
+            logs_truncated: false,
+            max_log_count: DEFAULT_MAX_LOG_COUNT,
+            max_log_bytes: DEFAULT_MAX_LOG_BYTES,
+            invocation_observer: None,
+            dangling_policy: DanglingPolicy::Fail,
+            unknown_substate_policy: UnknownSubstatePolicy::Reject,
+            created_values: 0,
+            max_created_values: DEFAULT_MAX_CREATED_VALUES,
+            max_mint_batch_size: DEFAULT_MAX_MINT_BATCH_SIZE,
+            max_created_substates: DEFAULT_MAX_CREATED_SUBSTATES,
+            max_worktop_resources: DEFAULT_MAX_WORKTOP_RESOURCES,
+            instruction_index: None,
             packages: IndexMap::new(),
             components: IndexMap::new(),
             borrowed_components: HashMap::new(),
             resource_managers: IndexMap::new(),
             borrowed_resource_managers: HashMap::new(),
-            lazy_map_entries: HashMap::new(),
-            vaults: HashMap::new(),
+            lazy_map_entries: IndexMap::new(),
+            vaults: IndexMap::new(),
             borrowed_vaults: HashMap::new(),
-            non_fungibles: HashMap::new(),
+            non_fungibles: IndexMap::new(),
+            raw_substates: IndexMap::new(),
+            borrowed_raw_substates: HashMap::new(),
+            #[cfg(feature = "unknown_substates_as_raw")]
+            unknown_substates: HashMap::new(),
+            readonly_addresses: HashSet::new(),
+            active_component_calls: HashMap::new(),
+            allowed_reentrant_methods: Vec::new(),
+            component_blueprints: HashMap::new(),
         }
     }
 
     /// Start a process.
-    pub fn start_process<'r>(&'r mut self, verbose: bool) -> Process<'r, 's, S> {
+    pub fn start_process<'r>(&'r mut self, trace_level: Option<Level>) -> Process<'r, 's, S> {
         let signers: BTreeSet<NonFungibleId> = self
             .transaction_signers
             .clone()
@@ -118,30 +216,35 @@ impl<'s, S: SubstateStore> Track<'s, S> {
 
         Process::new(
             0,
-            verbose,
+            trace_level,
             self,
             Some(AuthZone::new_with_proofs(initial_auth_zone_proofs)),
-            Some(Worktop::new()),
+            Some(Worktop::with_max_resources(self.max_worktop_resources)),
             HashMap::new(),
             HashMap::new(),
         )
     }
 
-    /// Returns the transaction hash.
-    pub fn transaction_hash(&self) -> Hash {
-        self.transaction_hash
+    /// Returns the block context this transaction executes within.
+    pub fn block_context(&self) -> &BlockContext {
+        &self.block_context
     }
 
-    /// Returns the current epoch.
-    pub fn current_epoch(&self) -> u64 {
-        self.substate_store.get_epoch()
+    /// Returns the public keys which signed this transaction.
+    pub fn transaction_signers(&self) -> Vec<EcdsaPublicKey> {
+        self.transaction_signers.clone()
     }
 
     /// Returns the logs collected so far.
-    pub fn logs(&self) -> &Vec<(Level, String)> {
+    pub fn logs(&self) -> &Vec<(Level, ComponentAddress, String)> {
         &self.logs
     }
 
+    /// Returns the resource movements recorded so far.
+    pub fn resource_movements(&self) -> &Vec<ResourceMovement> {
+        &self.resource_movements
+    }
+
     /// Returns new packages created so far.
     pub fn new_package_addresses(&self) -> Vec<PackageAddress> {
         let mut package_addresses = Vec::new();
@@ -175,19 +278,353 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         resource_addresses
     }
 
-    /// Adds a log message.
-    pub fn add_log(&mut self, level: Level, message: String) {
-        self.logs.push((level, message));
+    /// Returns the net change of vault balances per component made so far.
+    pub fn balance_changes(&self) -> BalanceChanges {
+        let mut balance_changes = BalanceChanges::default();
+        for ((component_address, vault_id), update) in self.vaults.iter() {
+            let previous_amount = match update.prev_id {
+                Some(_) => self
+                    .substate_store
+                    .get_decoded_child_substate(component_address, vault_id)
+                    .ok()
+                    .flatten()
+                    .map(|(vault, _): (Vault, _)| vault.total_amount())
+                    .unwrap_or_default(),
+                None => Decimal::zero(),
+            };
+            balance_changes.add(
+                *component_address,
+                update.value.resource_address(),
+                update.value.total_amount() - previous_amount,
+            );
+        }
+        balance_changes
+    }
+
+    /// Returns the net change of total supply per resource made so far.
+    pub fn supply_changes(&self) -> HashMap<ResourceAddress, Decimal> {
+        let mut supply_changes = HashMap::new();
+        for (resource_address, update) in self.resource_managers.iter() {
+            let previous_supply = match update.prev_id {
+                Some(_) => self
+                    .substate_store
+                    .get_decoded_substate(resource_address)
+                    .ok()
+                    .flatten()
+                    .map(|(resource_manager, _): (ResourceManager, _)| {
+                        resource_manager.total_supply()
+                    })
+                    .unwrap_or_default(),
+                None => Decimal::zero(),
+            };
+            let change = update.value.total_supply() - previous_supply;
+            if !change.is_zero() {
+                supply_changes.insert(*resource_address, change);
+            }
+        }
+        supply_changes
+    }
+
+    /// Sets the maximum number and total size of log messages to keep.
+    pub fn set_log_limits(&mut self, max_log_count: usize, max_log_bytes: usize) {
+        self.max_log_count = max_log_count;
+        self.max_log_bytes = max_log_bytes;
+    }
+
+    /// Sets the observer notified around every SNode invocation.
+    pub fn set_invocation_observer(&mut self, observer: Box<dyn InvocationObserver>) {
+        self.invocation_observer = Some(observer);
+    }
+
+    /// Returns the invocation observer, if any.
+    pub fn invocation_observer(&mut self) -> Option<&mut dyn InvocationObserver> {
+        self.invocation_observer.as_deref_mut()
+    }
+
+    /// Sets what happens to resources left dangling by the root process.
+    pub fn set_dangling_policy(&mut self, dangling_policy: DanglingPolicy) {
+        self.dangling_policy = dangling_policy;
+    }
+
+    /// Returns what happens to resources left dangling by the root process.
+    pub fn dangling_policy(&self) -> DanglingPolicy {
+        self.dangling_policy
+    }
+
+    /// Sets what happens when a stored substate can't be decoded.
+    pub fn set_unknown_substate_policy(&mut self, unknown_substate_policy: UnknownSubstatePolicy) {
+        self.unknown_substate_policy = unknown_substate_policy;
+    }
+
+    /// Returns the raw bytes of a substate of unknown kind loaded in this transaction.
+    #[cfg(feature = "unknown_substates_as_raw")]
+    pub fn get_unknown_substate(&self, address: &[u8]) -> Option<&[u8]> {
+        self.unknown_substates.get(address).map(|v| v.as_slice())
+    }
+
+    /// Sets the index of the transaction instruction being executed, if any.
+    pub fn set_instruction_index(&mut self, instruction_index: Option<usize>) {
+        self.instruction_index = instruction_index;
+    }
+
+    /// Returns the index of the transaction instruction being executed, if any.
+    pub fn instruction_index(&self) -> Option<usize> {
+        self.instruction_index
+    }
+
+    /// Sets the maximum number of values this transaction may create.
+    pub fn set_max_created_values(&mut self, max_created_values: usize) {
+        self.max_created_values = max_created_values;
+    }
+
+    /// Sets the maximum number of non-fungibles which may be minted in one call.
+    pub fn set_max_mint_batch_size(&mut self, max_mint_batch_size: usize) {
+        self.max_mint_batch_size = max_mint_batch_size;
+    }
+
+    /// Returns the maximum number of non-fungibles which may be minted in one call.
+    pub fn max_mint_batch_size(&self) -> usize {
+        self.max_mint_batch_size
+    }
+
+    /// Sets the maximum number of new substates this transaction may write to the ledger.
+    pub fn set_max_created_substates(&mut self, max_created_substates: usize) {
+        self.max_created_substates = max_created_substates;
+    }
+
+    /// Sets the maximum number of distinct resources the transaction worktop may hold.
+    pub fn set_max_worktop_resources(&mut self, max_worktop_resources: usize) {
+        self.max_worktop_resources = max_worktop_resources;
+    }
+
+    /// Checks that committing would not write more new substates than allowed.
+    ///
+    /// Substates which replace a previous version don't count, as they don't grow the ledger.
+    pub fn check_created_substates(&self) -> Result<(), RuntimeError> {
+        fn count_new<K, T>(updates: &IndexMap<K, SubstateUpdate<T>>) -> usize {
+            updates
+                .values()
+                .filter(|update| update.prev_id.is_none())
+                .count()
+        }
+
+        let created_substates = count_new(&self.packages)
+            + count_new(&self.components)
+            + count_new(&self.resource_managers)
+            + count_new(&self.vaults)
+            + count_new(&self.non_fungibles)
+            + count_new(&self.lazy_map_entries)
+            + count_new(&self.raw_substates);
+        if created_substates > self.max_created_substates {
+            return Err(RuntimeError::TooManySubstatesCreated);
+        }
+        Ok(())
+    }
+
+    fn count_created_value(&mut self) -> Result<(), RuntimeError> {
+        if self.created_values >= self.max_created_values {
+            return Err(RuntimeError::TooManyValuesCreated);
+        }
+        self.created_values += 1;
+        Ok(())
+    }
+
+    /// Applies the unknown substate policy to a stored value which failed to decode.
+    fn unknown_substate(
+        &mut self,
+        address: Vec<u8>,
+        value: Vec<u8>,
+        e: DecodeError,
+    ) -> RuntimeError {
+        match self.unknown_substate_policy {
+            UnknownSubstatePolicy::Panic => {
+                panic!("Attempting to borrow unsupported value: {:?}", e)
+            }
+            UnknownSubstatePolicy::Reject => RuntimeError::UnknownSubstate { address, value },
+            #[cfg(feature = "unknown_substates_as_raw")]
+            UnknownSubstatePolicy::LoadAsRaw => {
+                self.unknown_substates
+                    .insert(address.clone(), value.clone());
+                RuntimeError::UnknownSubstate { address, value }
+            }
+        }
+    }
+
+    /// Loads and decodes a substate, applying the unknown substate policy if decoding fails.
+    fn load_decoded_substate<A: Encode, T: Decode>(
+        &mut self,
+        address: &A,
+    ) -> Result<Option<(T, (Hash, u32))>, RuntimeError> {
+        let substate = match self.substate_store.get_substate(address) {
+            Some(substate) => substate,
+            None => return Ok(None),
+        };
+        match decode_substate_value(&substate.value) {
+            Ok(value) => Ok(Some((value, substate.phys_id))),
+            Err(e) => Err(self.unknown_substate(scrypto_encode(address), substate.value, e)),
+        }
+    }
+
+    /// Loads and decodes a child substate, applying the unknown substate policy if decoding fails.
+    fn load_decoded_child_substate<A: Encode, K: Encode, T: Decode>(
+        &mut self,
+        address: &A,
+        key: &K,
+    ) -> Result<Option<(T, (Hash, u32))>, RuntimeError> {
+        let child_key = scrypto_encode(key);
+        let substate = match self.substate_store.get_child_substate(address, &child_key) {
+            Some(substate) => substate,
+            None => return Ok(None),
+        };
+        match decode_substate_value(&substate.value) {
+            Ok(value) => Ok(Some((value, substate.phys_id))),
+            Err(e) => {
+                let mut substate_address = scrypto_encode(address);
+                substate_address.extend(child_key);
+                Err(self.unknown_substate(substate_address, substate.value, e))
+            }
+        }
+    }
+
+    /// Loads a lazy map entry, applying the unknown substate policy if its version is unknown.
+    fn load_lazy_map_entry(
+        &mut self,
+        component_address: &ComponentAddress,
+        lazy_map_id: &LazyMapId,
+        key: &[u8],
+    ) -> Result<Option<(Vec<u8>, (Hash, u32))>, RuntimeError> {
+        let mut child_key = scrypto_encode(lazy_map_id);
+        child_key.extend(key.to_vec());
+        let substate = match self
+            .substate_store
+            .get_child_substate(component_address, &child_key)
+        {
+            Some(substate) => substate,
+            None => return Ok(None),
+        };
+        match decode_substate_bytes(&substate.value) {
+            Ok(value) => Ok(Some((value.to_vec(), substate.phys_id))),
+            Err(e) => {
+                let mut substate_address = scrypto_encode(component_address);
+                substate_address.extend(child_key);
+                Err(self.unknown_substate(substate_address, substate.value, e))
+            }
+        }
+    }
+
+    /// Declares that the given addresses are only read in this transaction.
+    ///
+    /// Any later mutable borrow of these addresses fails with `ReadOnlyViolation`. Vaults and
+    /// lazy map entries are read-only when their component is, and non-fungibles when their
+    /// resource is.
+    pub fn declare_readonly<A: Encode>(&mut self, addresses: &[A]) {
+        for address in addresses {
+            self.readonly_addresses.insert(scrypto_encode(address));
+        }
+    }
+
+    fn check_writable(&self, address: Vec<u8>) -> Result<(), RuntimeError> {
+        if self.readonly_addresses.contains(&address) {
+            Err(RuntimeError::ReadOnlyViolation(address))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Sets the blueprint methods which may be re-entered while already on the call stack.
+    pub fn set_allowed_reentrant_methods(
+        &mut self,
+        allowed_reentrant_methods: Vec<ReentrantMethod>,
+    ) {
+        self.allowed_reentrant_methods = allowed_reentrant_methods;
+    }
+
+    /// Marks a component method as being on the call stack.
+    ///
+    /// Fails with `ReentrantComponentCall` if the same method is already on the stack, unless
+    /// its blueprint method is allowed to be re-entered.
+    pub fn enter_component_call(
+        &mut self,
+        component_address: ComponentAddress,
+        function: &str,
+    ) -> Result<(), RuntimeError> {
+        let key = (component_address, function.to_string());
+        if self.active_component_calls.contains_key(&key)
+            && !self.is_reentry_allowed(component_address, function)
+        {
+            return Err(RuntimeError::ReentrantComponentCall(
+                component_address,
+                function.to_string(),
+            ));
+        }
+        *self.active_component_calls.entry(key).or_insert(0) += 1;
+        Ok(())
+    }
+
+    /// Marks a component method as no longer being on the call stack.
+    pub fn exit_component_call(&mut self, component_address: ComponentAddress, function: &str) {
+        let key = (component_address, function.to_string());
+        if let Some(count) = self.active_component_calls.get_mut(&key) {
+            *count -= 1;
+            if *count == 0 {
+                self.active_component_calls.remove(&key);
+            }
+        }
+    }
+
+    fn is_reentry_allowed(&self, component_address: ComponentAddress, function: &str) -> bool {
+        // A method on the call stack has borrowed its component, so the blueprint is known
+        match self.component_blueprints.get(&component_address) {
+            Some((package_address, blueprint_name)) => self
+                .allowed_reentrant_methods
+                .iter()
+                .any(|m| m.allows(*package_address, blueprint_name, function)),
+            None => false,
+        }
+    }
+
+    /// Adds a log message, emitted by the given component.
+    ///
+    /// Once the log limits are reached, further messages are dropped and a single
+    /// truncation marker is recorded instead.
+    pub fn add_log(&mut self, level: Level, component_address: ComponentAddress, message: String) {
+        if self.logs_truncated {
+            return;
+        }
+
+        if self.logs.len() >= self.max_log_count
+            || self.logs_size + message.len() > self.max_log_bytes
+        {
+            self.logs.push((
+                Level::Warn,
+                NO_LOG_COMPONENT,
+                LOGS_TRUNCATED_MESSAGE.to_string(),
+            ));
+            self.logs_truncated = true;
+            return;
+        }
+
+        self.logs_size += message.len();
+        self.logs.push((level, component_address, message));
+    }
+
+    /// Records a resource movement in the audit log.
+    ///
+    /// Unlike logs, these are never truncated, as they are emitted by the engine only.
+    pub fn record_resource_movement(&mut self, movement: ResourceMovement) {
+        self.resource_movements.push(movement);
     }
 
     /// Returns an immutable reference to a package, if exists.
-    pub fn get_package(&mut self, package_address: &PackageAddress) -> Option<&Package> {
+    pub fn get_package(
+        &mut self,
+        package_address: &PackageAddress,
+    ) -> Result<Option<&Package>, RuntimeError> {
         if self.packages.contains_key(package_address) {
-            return self.packages.get(package_address).map(|p| &p.value);
+            return Ok(self.packages.get(package_address).map(|p| &p.value));
         }
 
-        if let Some((package, phys_id)) = self.substate_store.get_decoded_substate(package_address)
-        {
+        if let Some((package, phys_id)) = self.load_decoded_substate(package_address)? {
             self.packages.insert(
                 package_address.clone(),
                 SubstateUpdate {
@@ -195,15 +632,15 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                     value: package,
                 },
             );
-            self.packages.get(package_address).map(|p| &p.value)
+            Ok(self.packages.get(package_address).map(|p| &p.value))
         } else {
-            None
+            Ok(None)
         }
     }
 
     /// Inserts a new package.
-    pub fn create_package(&mut self, package: Package) -> PackageAddress {
-        let package_address = self.new_package_address();
+    pub fn create_package(&mut self, package: Package) -> Result<PackageAddress, RuntimeError> {
+        let package_address = self.new_package_address()?;
         self.packages.insert(
             package_address,
             SubstateUpdate {
@@ -211,28 +648,35 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                 value: package,
             },
         );
-        package_address
+        Ok(package_address)
     }
 
     pub fn borrow_global_mut_component(
         &mut self,
         component_address: ComponentAddress,
     ) -> Result<Component, RuntimeError> {
+        self.check_writable(scrypto_encode(&component_address))?;
         let maybe_component = self.components.remove(&component_address);
-        if let Some(SubstateUpdate { value, prev_id }) = maybe_component {
+        let component = if let Some(SubstateUpdate { value, prev_id }) = maybe_component {
             self.borrowed_components.insert(component_address, prev_id);
-            Ok(value)
+            value
         } else if self.borrowed_components.contains_key(&component_address) {
-            Err(RuntimeError::ComponentReentrancy(component_address))
-        } else if let Some((component, phys_id)) =
-            self.substate_store.get_decoded_substate(&component_address)
-        {
+            return Err(RuntimeError::ComponentReentrancy(component_address));
+        } else if let Some((component, phys_id)) = self.load_decoded_substate(&component_address)? {
             self.borrowed_components
                 .insert(component_address, Some(phys_id));
-            Ok(component)
+            component
         } else {
-            Err(RuntimeError::ComponentNotFound(component_address))
-        }
+            return Err(RuntimeError::ComponentNotFound(component_address));
+        };
+        self.component_blueprints.insert(
+            component_address,
+            (
+                component.package_address(),
+                component.blueprint_name().to_string(),
+            ),
+        );
+        Ok(component)
     }
 
     pub fn return_borrowed_global_component(
@@ -254,14 +698,15 @@ impl<'s, S: SubstateStore> Track<'s, S> {
     }
 
     /// Returns an immutable reference to a component, if exists.
-    pub fn get_component(&mut self, component_address: ComponentAddress) -> Option<&Component> {
+    pub fn get_component(
+        &mut self,
+        component_address: ComponentAddress,
+    ) -> Result<Option<&Component>, RuntimeError> {
         if self.components.contains_key(&component_address) {
-            return self.components.get(&component_address).map(|c| &c.value);
+            return Ok(self.components.get(&component_address).map(|c| &c.value));
         }
 
-        if let Some((component, phys_id)) =
-            self.substate_store.get_decoded_substate(&component_address)
-        {
+        if let Some((component, phys_id)) = self.load_decoded_substate(&component_address)? {
             self.components.insert(
                 component_address,
                 SubstateUpdate {
@@ -269,15 +714,18 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                     value: component,
                 },
             );
-            self.components.get(&component_address).map(|c| &c.value)
+            Ok(self.components.get(&component_address).map(|c| &c.value))
         } else {
-            None
+            Ok(None)
         }
     }
 
     /// Inserts a new component.
-    pub fn create_component(&mut self, component: Component) -> ComponentAddress {
-        let component_address = self.new_component_address();
+    pub fn create_component(
+        &mut self,
+        component: Component,
+    ) -> Result<ComponentAddress, RuntimeError> {
+        let component_address = self.new_component_address()?;
         self.components.insert(
             component_address,
             SubstateUpdate {
@@ -285,26 +733,51 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                 value: component,
             },
         );
-        component_address
+        Ok(component_address)
+    }
+
+    /// Inserts a new component at an address derived from its package address and name.
+    pub fn create_named_component(
+        &mut self,
+        component: Component,
+        name: &str,
+    ) -> Result<ComponentAddress, RuntimeError> {
+        self.count_created_value()?;
+        let component_address = self
+            .id_allocator
+            .new_named_component_address(component.package_address(), name);
+        if self.borrowed_components.contains_key(&component_address)
+            || self.get_component(component_address)?.is_some()
+        {
+            return Err(RuntimeError::ComponentAddressCollision(component_address));
+        }
+        self.components.insert(
+            component_address,
+            SubstateUpdate {
+                prev_id: None,
+                value: component,
+            },
+        );
+        Ok(component_address)
     }
 
     /// Returns an immutable reference to a non-fungible, if exists.
     pub fn get_non_fungible(
         &mut self,
         non_fungible_address: &NonFungibleAddress,
-    ) -> Option<&NonFungible> {
+    ) -> Result<Option<&NonFungible>, RuntimeError> {
         if self.non_fungibles.contains_key(non_fungible_address) {
-            return self
+            return Ok(self
                 .non_fungibles
                 .get(non_fungible_address)
                 .map(|s| s.value.as_ref())
-                .unwrap_or(Option::None);
+                .unwrap_or(Option::None));
         }
 
-        if let Some((non_fungible, phys_id)) = self.substate_store.get_decoded_child_substate(
+        if let Some((non_fungible, phys_id)) = self.load_decoded_child_substate(
             &non_fungible_address.resource_address(),
             &non_fungible_address.non_fungible_id(),
-        ) {
+        )? {
             self.non_fungibles.insert(
                 non_fungible_address.clone(),
                 SubstateUpdate {
@@ -312,12 +785,13 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                     value: non_fungible,
                 },
             );
-            self.non_fungibles
+            Ok(self
+                .non_fungibles
                 .get(non_fungible_address)
                 .map(|s| s.value.as_ref())
-                .unwrap()
+                .unwrap())
         } else {
-            None
+            Ok(None)
         }
     }
 
@@ -326,12 +800,13 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         &mut self,
         non_fungible_address: NonFungibleAddress,
         non_fungible: Option<NonFungible>,
-    ) {
-        let cur: Option<(Option<NonFungible>, (Hash, u32))> =
-            self.substate_store.get_decoded_child_substate(
-                &non_fungible_address.resource_address(),
-                &non_fungible_address.non_fungible_id(),
-            );
+    ) -> Result<(), RuntimeError> {
+        self.check_writable(scrypto_encode(&non_fungible_address.resource_address()))?;
+        self.check_writable(scrypto_encode(&non_fungible_address))?;
+        let cur: Option<(Option<NonFungible>, (Hash, u32))> = self.load_decoded_child_substate(
+            &non_fungible_address.resource_address(),
+            &non_fungible_address.non_fungible_id(),
+        )?;
         let prev_id = cur.map(|(_, cur_id)| cur_id);
 
         self.non_fungibles.insert(
@@ -341,6 +816,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                 value: non_fungible,
             },
         );
+        Ok(())
     }
 
     pub fn get_lazy_map_entry(
@@ -348,24 +824,19 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         component_address: ComponentAddress,
         lazy_map_id: &LazyMapId,
         key: &[u8],
-    ) -> Option<Vec<u8>> {
+    ) -> Result<Option<Vec<u8>>, RuntimeError> {
         let canonical_id = (component_address.clone(), lazy_map_id.clone(), key.to_vec());
 
         if self.lazy_map_entries.contains_key(&canonical_id) {
-            return Some(
+            return Ok(Some(
                 self.lazy_map_entries
                     .get(&canonical_id)
                     .map(|r| r.value.clone())
                     .unwrap(),
-            );
+            ));
         }
 
-        let grand_child_key = key.to_vec();
-        let value = self.substate_store.get_decoded_grand_child_substate(
-            &component_address,
-            lazy_map_id,
-            &grand_child_key,
-        );
+        let value = self.load_lazy_map_entry(&component_address, lazy_map_id, key)?;
         if let Some((ref entry_bytes, phys_id)) = value {
             self.lazy_map_entries.insert(
                 canonical_id,
@@ -375,7 +846,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                 },
             );
         }
-        value.map(|r| r.0)
+        Ok(value.map(|r| r.0))
     }
 
     pub fn put_lazy_map_entry(
@@ -384,15 +855,12 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         lazy_map_id: LazyMapId,
         key: Vec<u8>,
         value: Vec<u8>,
-    ) {
+    ) -> Result<(), RuntimeError> {
+        self.check_writable(scrypto_encode(&component_address))?;
         let canonical_id = (component_address.clone(), lazy_map_id.clone(), key.clone());
 
         if !self.lazy_map_entries.contains_key(&canonical_id) {
-            let entry = self.substate_store.get_decoded_grand_child_substate(
-                &component_address,
-                &lazy_map_id,
-                &key,
-            );
+            let entry = self.load_lazy_map_entry(&component_address, &lazy_map_id, &key)?;
             if let Some((_, phys_id)) = entry {
                 self.lazy_map_entries.insert(
                     canonical_id,
@@ -401,7 +869,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                         value,
                     },
                 );
-                return;
+                return Ok(());
             }
         }
 
@@ -417,23 +885,22 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                 },
             );
         }
+        Ok(())
     }
 
     /// Returns an immutable reference to a resource manager, if exists.
     pub fn get_resource_manager(
         &mut self,
         resource_address: &ResourceAddress,
-    ) -> Option<&ResourceManager> {
+    ) -> Result<Option<&ResourceManager>, RuntimeError> {
         if self.resource_managers.contains_key(resource_address) {
-            return self
+            return Ok(self
                 .resource_managers
                 .get(resource_address)
-                .map(|r| &r.value);
+                .map(|r| &r.value));
         }
 
-        if let Some((resource_manager, phys_id)) =
-            self.substate_store.get_decoded_substate(resource_address)
-        {
+        if let Some((resource_manager, phys_id)) = self.load_decoded_substate(resource_address)? {
             self.resource_managers.insert(
                 resource_address.clone(),
                 SubstateUpdate {
@@ -441,11 +908,12 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                     value: resource_manager,
                 },
             );
-            self.resource_managers
+            Ok(self
+                .resource_managers
                 .get(resource_address)
-                .map(|r| &r.value)
+                .map(|r| &r.value))
         } else {
-            None
+            Ok(None)
         }
     }
 
@@ -453,6 +921,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         &mut self,
         resource_address: ResourceAddress,
     ) -> Result<ResourceManager, RuntimeError> {
+        self.check_writable(scrypto_encode(&resource_address))?;
         let maybe_resource = self.resource_managers.remove(&resource_address);
         if self
             .borrowed_resource_managers
@@ -464,7 +933,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                 .insert(resource_address, prev_id);
             Ok(value)
         } else if let Some((resource_manager, phys_id)) =
-            self.substate_store.get_decoded_substate(&resource_address)
+            self.load_decoded_substate(&resource_address)?
         {
             self.borrowed_resource_managers
                 .insert(resource_address, Some(phys_id));
@@ -496,8 +965,8 @@ impl<'s, S: SubstateStore> Track<'s, S> {
     pub fn create_resource_manager(
         &mut self,
         resource_manager: ResourceManager,
-    ) -> ResourceAddress {
-        let resource_address = self.new_resource_address();
+    ) -> Result<ResourceAddress, RuntimeError> {
+        let resource_address = self.new_resource_address()?;
         self.resource_managers.insert(
             resource_address,
             SubstateUpdate {
@@ -505,24 +974,28 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                 value: resource_manager,
             },
         );
-        resource_address
+        Ok(resource_address)
     }
 
-    pub fn borrow_vault_mut(&mut self, component_address: &ComponentAddress, vid: &VaultId) -> Vault {
+    pub fn borrow_vault_mut(
+        &mut self,
+        component_address: &ComponentAddress,
+        vid: &VaultId,
+    ) -> Result<Vault, RuntimeError> {
         let canonical_id = (component_address.clone(), vid.clone());
         if self.borrowed_vaults.contains_key(&canonical_id) {
             panic!("Invalid vault reentrancy");
         }
+        self.check_writable(scrypto_encode(component_address))?;
 
         if let Some(SubstateUpdate { value, prev_id }) = self.vaults.remove(&canonical_id) {
             self.borrowed_vaults.insert(canonical_id, prev_id);
-            return value;
+            return Ok(value);
         }
 
-        if let Some((vault, phys_id)) = self.substate_store.get_decoded_child_substate(component_address, vid) {
-            self.borrowed_vaults
-                .insert(canonical_id, Some(phys_id));
-            return vault;
+        if let Some((vault, phys_id)) = self.load_decoded_child_substate(component_address, vid)? {
+            self.borrowed_vaults.insert(canonical_id, Some(phys_id));
+            return Ok(vault);
         }
 
         panic!("Should not get here");
@@ -565,61 +1038,153 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         );
     }
 
+    /// Returns the raw bytes of an opaque substate, if exists.
+    ///
+    /// Raw substates are addressed by opaque bytes and are never decoded, which allows
+    /// substate kinds unknown to this engine to pass through the track unchanged. They are
+    /// stored under a `RawSubstateAddress`, so they never collide with typed substates.
+    pub fn get_raw_substate(&mut self, address: &[u8]) -> Option<&[u8]> {
+        if self.raw_substates.contains_key(address) {
+            return self.raw_substates.get(address).map(|s| s.value.as_slice());
+        }
+
+        if let Some(substate) = self
+            .substate_store
+            .get_substate(&RawSubstateAddress(address.to_vec()))
+        {
+            self.raw_substates.insert(
+                address.to_vec(),
+                SubstateUpdate {
+                    prev_id: Some(substate.phys_id),
+                    value: substate.value,
+                },
+            );
+            self.raw_substates.get(address).map(|s| s.value.as_slice())
+        } else {
+            None
+        }
+    }
+
+    /// Inserts or overwrites an opaque substate.
+    pub fn put_raw_substate(
+        &mut self,
+        address: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), RuntimeError> {
+        if self.borrowed_raw_substates.contains_key(&address) {
+            panic!("Invalid raw substate reentrancy");
+        }
+        let raw_address = RawSubstateAddress(address.clone());
+        self.check_writable(scrypto_encode(&raw_address))?;
+
+        if let Some(substate) = self.raw_substates.get_mut(&address) {
+            substate.value = value;
+            return Ok(());
+        }
+
+        let prev_id = self
+            .substate_store
+            .get_substate(&raw_address)
+            .map(|substate| substate.phys_id);
+        self.raw_substates
+            .insert(address, SubstateUpdate { prev_id, value });
+        Ok(())
+    }
+
+    pub fn borrow_raw_substate_mut(&mut self, address: &[u8]) -> Result<Vec<u8>, RuntimeError> {
+        if self.borrowed_raw_substates.contains_key(address) {
+            return Err(RuntimeError::RawSubstateReentrancy(address.to_vec()));
+        }
+        let raw_address = RawSubstateAddress(address.to_vec());
+        self.check_writable(scrypto_encode(&raw_address))?;
+
+        if let Some(SubstateUpdate { value, prev_id }) = self.raw_substates.remove(address) {
+            self.borrowed_raw_substates
+                .insert(address.to_vec(), prev_id);
+            Ok(value)
+        } else if let Some(substate) = self.substate_store.get_substate(&raw_address) {
+            self.borrowed_raw_substates
+                .insert(address.to_vec(), Some(substate.phys_id));
+            Ok(substate.value)
+        } else {
+            Err(RuntimeError::RawSubstateNotFound(address.to_vec()))
+        }
+    }
+
+    pub fn return_borrowed_raw_substate(&mut self, address: &[u8], value: Vec<u8>) {
+        if let Some(prev_id) = self.borrowed_raw_substates.remove(address) {
+            self.raw_substates
+                .insert(address.to_vec(), SubstateUpdate { prev_id, value });
+        } else {
+            panic!("Raw substate was never borrowed");
+        }
+    }
+
     /// Creates a new package ID.
-    fn new_package_address(&mut self) -> PackageAddress {
+    fn new_package_address(&mut self) -> Result<PackageAddress, RuntimeError> {
         // Security Alert: ensure ID allocating will practically never fail
-        let package_address = self
-            .id_allocator
-            .new_package_address(self.transaction_hash())
-            .unwrap();
-        package_address
+        self.id_allocator
+            .new_package_address(self.block_context.transaction_hash)
+            .map_err(RuntimeError::IdAllocatorError)
     }
 
     /// Creates a new component address.
-    fn new_component_address(&mut self) -> ComponentAddress {
-        let component_address = self
-            .id_allocator
-            .new_component_address(self.transaction_hash())
-            .unwrap();
-        component_address
+    fn new_component_address(&mut self) -> Result<ComponentAddress, RuntimeError> {
+        self.count_created_value()?;
+        self.id_allocator
+            .new_component_address(self.block_context.transaction_hash)
+            .map_err(RuntimeError::IdAllocatorError)
     }
 
     /// Creates a new resource address.
-    fn new_resource_address(&mut self) -> ResourceAddress {
-        let resource_address = self
-            .id_allocator
-            .new_resource_address(self.transaction_hash())
-            .unwrap();
-        resource_address
+    fn new_resource_address(&mut self) -> Result<ResourceAddress, RuntimeError> {
+        self.id_allocator
+            .new_resource_address(self.block_context.transaction_hash)
+            .map_err(RuntimeError::IdAllocatorError)
     }
 
     /// Creates a new UUID.
-    pub fn new_uuid(&mut self) -> u128 {
-        self.id_allocator.new_uuid(self.transaction_hash()).unwrap()
+    pub fn new_uuid(&mut self) -> Result<u128, RuntimeError> {
+        self.id_allocator
+            .new_uuid(self.block_context.transaction_hash)
+            .map_err(RuntimeError::IdAllocatorError)
     }
 
     /// Creates a new bucket ID.
-    pub fn new_bucket_id(&mut self) -> BucketId {
-        self.id_allocator.new_bucket_id().unwrap()
+    pub fn new_bucket_id(&mut self) -> Result<BucketId, RuntimeError> {
+        self.count_created_value()?;
+        self.id_allocator
+            .new_bucket_id()
+            .map_err(RuntimeError::IdAllocatorError)
     }
 
     /// Creates a new vault ID.
-    pub fn new_vault_id(&mut self) -> VaultId {
+    pub fn new_vault_id(&mut self) -> Result<VaultId, RuntimeError> {
+        self.count_created_value()?;
         self.id_allocator
-            .new_vault_id(self.transaction_hash())
-            .unwrap()
+            .new_vault_id(self.block_context.transaction_hash)
+            .map_err(RuntimeError::IdAllocatorError)
     }
 
     /// Creates a new reference id.
-    pub fn new_proof_id(&mut self) -> ProofId {
-        self.id_allocator.new_proof_id().unwrap()
+    pub fn new_proof_id(&mut self) -> Result<ProofId, RuntimeError> {
+        self.count_created_value()?;
+        self.id_allocator
+            .new_proof_id()
+            .map_err(RuntimeError::IdAllocatorError)
     }
 
     /// Creates a new map id.
-    pub fn new_lazy_map_id(&mut self) -> LazyMapId {
+    pub fn new_lazy_map_id(&mut self) -> Result<LazyMapId, RuntimeError> {
+        self.count_created_value()?;
         self.id_allocator
-            .new_lazy_map_id(self.transaction_hash())
-            .unwrap()
+            .new_lazy_map_id(self.block_context.transaction_hash)
+            .map_err(RuntimeError::IdAllocatorError)
+    }
+
+    /// Replaces the allocator used for application IDs.
+    pub fn set_id_allocator(&mut self, id_allocator: IdAllocator) {
+        self.id_allocator = id_allocator;
     }
 
     /// Commits changes to the underlying ledger.
@@ -635,9 +1200,12 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         if !self.borrowed_vaults.is_empty() {
             panic!("Borrowed vaults should be empty by end of transaction.");
         }
+        if !self.borrowed_raw_substates.is_empty() {
+            panic!("Borrowed raw substates should be empty by end of transaction.");
+        }
 
         let mut receipt = CommitReceipt::new();
-        let mut id_gen = SubstateIdGenerator::new(self.transaction_hash());
+        let mut id_gen = SubstateIdGenerator::new(self.block_context.transaction_hash);
 
         let package_addresses: Vec<PackageAddress> = self.packages.keys().cloned().collect();
         for package_address in package_addresses {
@@ -741,6 +1309,24 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             );
         }
 
+        let raw_addresses: Vec<Vec<u8>> = self.raw_substates.keys().cloned().collect();
+        for raw_address in raw_addresses {
+            let raw_substate = self.raw_substates.remove(&raw_address).unwrap();
+            if let Some(prev_id) = raw_substate.prev_id {
+                receipt.down(prev_id);
+            }
+            let phys_id = id_gen.next();
+            receipt.up(phys_id);
+
+            self.substate_store.put_substate(
+                &RawSubstateAddress(raw_address),
+                Substate {
+                    value: raw_substate.value,
+                    phys_id,
+                },
+            );
+        }
+
         receipt
     }
 }
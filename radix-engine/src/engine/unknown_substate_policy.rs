@@ -0,0 +1,18 @@
+/// Decides what happens when a stored substate can't be decoded, e.g. because it was written
+/// in a newer schema version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownSubstatePolicy {
+    /// Panics, as the ledger is assumed to only hold substates this engine knows.
+    Panic,
+    /// Fails the read or borrow with `RuntimeError::UnknownSubstate`, which carries the raw
+    /// bytes, without crashing the node. This is the default.
+    ///
+    /// Unknown substates can't be used, but the rest of the ledger keeps working.
+    Reject,
+    /// Loads the substate as raw bytes, readable through `Track::get_unknown_substate`, and
+    /// fails typed access with `RuntimeError::UnknownSubstate`.
+    ///
+    /// Only available with the `unknown_substates_as_raw` feature.
+    #[cfg(feature = "unknown_substates_as_raw")]
+    LoadAsRaw,
+}
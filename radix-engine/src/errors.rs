@@ -64,6 +64,8 @@ pub enum WasmValidationError {
     NoPackageInitExport(WasmiError),
     /// package_init function is not the correct interface
     InvalidPackageInit,
+    /// The wasm code exceeds the maximum package size.
+    CodeTooLarge { size: usize, max: usize },
 }
 
 /// Represents an error when validating a transaction.
@@ -74,6 +76,9 @@ pub enum TransactionValidationError {
     VaultNotAllowed(VaultId),
     LazyMapNotAllowed(LazyMapId),
     InvalidSignature,
+    InstructionNotAllowedInBranch(Instruction),
+    DecodeError(DecodeError),
+    UnsupportedInstruction(String),
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -81,7 +86,7 @@ pub enum ResourceFailure {
     Resource(ResourceAddress),
     Resources(Vec<ResourceAddress>),
     UnclaimedLazyMap,
-    Unknown
+    Unknown,
 }
 
 /// Represents an error when executing a transaction.
@@ -134,14 +139,35 @@ pub enum RuntimeError {
     /// Blueprint does not exist.
     BlueprintNotFound(PackageAddress, String),
 
+    /// The state returned by `migrate_state` is not valid.
+    InvalidMigratedState(DecodeError),
+
     /// System call not allowed in given context.
     IllegalSystemCall,
 
     ComponentReentrancy(ComponentAddress),
 
+    /// A method was called on a frozen component.
+    ComponentFrozen(ComponentAddress),
+
+    /// A component tried to freeze or unfreeze a component other than itself.
+    ComponentNotActor(ComponentAddress),
+
+    /// A component method was called again while already on the call stack.
+    ReentrantComponentCall(ComponentAddress, String),
+
+    /// The transaction created more values than allowed.
+    TooManyValuesCreated,
+
+    /// The transaction would write more new substates than allowed.
+    TooManySubstatesCreated,
+
     /// Component does not exist.
     ComponentNotFound(ComponentAddress),
 
+    /// A named component already exists at the derived address.
+    ComponentAddressCollision(ComponentAddress),
+
     /// Component is already loaded
     ComponentAlreadyLoaded(ComponentAddress),
 
@@ -166,6 +192,30 @@ pub enum RuntimeError {
     /// Cyclic LazyMap added
     CyclicLazyMap(LazyMapId),
 
+    /// A stored LazyMap entry could not be decoded, identified by map id and key.
+    InvalidLazyMapEntry(LazyMapId, Vec<u8>),
+
+    /// Raw substate does not exist.
+    RawSubstateNotFound(Vec<u8>),
+
+    /// Raw substate is already borrowed.
+    RawSubstateReentrancy(Vec<u8>),
+
+    /// Substate could not be decoded and was rejected; carries its raw bytes.
+    UnknownSubstate {
+        address: Vec<u8>,
+        value: Vec<u8>,
+    },
+
+    /// A stored substate could not be decoded.
+    InvalidSubstate(DecodeError),
+
+    /// The transaction was committed, but the substate store failed to make it durable.
+    FlushFailed(String),
+
+    /// Substate was declared read-only but borrowed mutably.
+    ReadOnlyViolation(Vec<u8>),
+
     /// Vault does not exist.
     VaultNotFound(VaultId),
 
@@ -175,6 +225,15 @@ pub enum RuntimeError {
     /// Duplicate Vault added
     DuplicateVault(VaultId),
 
+    /// Value is owned but no longer reachable from its root.
+    OrphanedValue(ValueId),
+
+    /// Value is owned twice, borrowed across a call or both owned and referenced.
+    EngineInvariantViolated(ValueId),
+
+    /// Value is referenced but not visible to the current process.
+    InvalidDataAccess(ValueId),
+
     /// Bucket does not exist.
     BucketNotFound(BucketId),
 
@@ -228,6 +287,8 @@ pub enum RuntimeError {
         function: String,
         authorization: MethodAuthorization,
         error: MethodAuthorizationError,
+        /// The path to the sub-rule which is not satisfied.
+        failed_rule: Option<String>,
     },
 
     /// Index out of bounds.
@@ -242,6 +303,8 @@ pub enum RuntimeError {
     /// Can't move restricted proof.
     CantMoveRestrictedProof(ProofId),
 
+    /// The manifest aborted the transaction, with the given reason.
+    ManifestAborted(String),
 }
 
 impl fmt::Display for RuntimeError {
@@ -1,16 +1,22 @@
-use sbor::Encode;
-use scrypto::buffer::scrypto_encode;
+use sbor::{DecodeError, Encode};
+use scrypto::buffer::{scrypto_decode, scrypto_encode};
+use scrypto::engine::types::*;
 use scrypto::rust::collections::HashMap;
 use scrypto::rust::vec::Vec;
 
 use crate::ledger::traits::Substate;
 use crate::ledger::*;
+use crate::model::Component;
 
 /// An in-memory ledger stores all substates in host memory.
 #[derive(Debug, Clone)]
 pub struct InMemorySubstateStore {
     substates: HashMap<Vec<u8>, Substate>,
     child_substates: HashMap<Vec<u8>, Substate>,
+    substates_by_phys_id: HashMap<(Hash, u32), Substate>,
+    addresses_by_phys_id: HashMap<(Hash, u32), Vec<u8>>,
+    /// The most recently replaced version of each address, the only one kept by phys id
+    downed_phys_ids: HashMap<Vec<u8>, (Hash, u32)>,
     current_epoch: u64,
     nonce: u64,
 }
@@ -20,6 +26,9 @@ impl InMemorySubstateStore {
         Self {
             substates: HashMap::new(),
             child_substates: HashMap::new(),
+            substates_by_phys_id: HashMap::new(),
+            addresses_by_phys_id: HashMap::new(),
+            downed_phys_ids: HashMap::new(),
             current_epoch: 0,
             nonce: 0,
         }
@@ -30,6 +39,33 @@ impl InMemorySubstateStore {
         ledger.bootstrap();
         ledger
     }
+
+    pub fn with_bootstrap_config(config: BootstrapConfig) -> Self {
+        let mut ledger = Self::new();
+        ledger.bootstrap_with_config(config);
+        ledger
+    }
+
+    /// Indexes a written substate by phys id, together with the version it replaced.
+    fn index_substate(
+        &mut self,
+        address: Vec<u8>,
+        substate: &Substate,
+        previous: Option<Substate>,
+    ) {
+        self.substates_by_phys_id
+            .insert(substate.phys_id, substate.clone());
+        self.addresses_by_phys_id
+            .insert(substate.phys_id, address.clone());
+
+        // Only the previous version stays readable, so the index doesn't grow with every write
+        if let Some(previous) = previous.filter(|p| p.phys_id != substate.phys_id) {
+            if let Some(older) = self.downed_phys_ids.insert(address, previous.phys_id) {
+                self.substates_by_phys_id.remove(&older);
+                self.addresses_by_phys_id.remove(&older);
+            }
+        }
+    }
 }
 
 impl Default for InMemorySubstateStore {
@@ -38,13 +74,60 @@ impl Default for InMemorySubstateStore {
     }
 }
 
+impl QueryableSubstateStore for InMemorySubstateStore {
+    fn get_lazy_map_entries(
+        &self,
+        component_address: ComponentAddress,
+        lazy_map_id: &LazyMapId,
+    ) -> Result<HashMap<Vec<u8>, Vec<u8>>, DecodeError> {
+        let mut id = scrypto_encode(&component_address);
+        id.extend(scrypto_encode(lazy_map_id));
+
+        self.child_substates
+            .iter()
+            .filter(|(key, _)| key.starts_with(&id))
+            .map(|(key, substate)| {
+                Ok((
+                    key.split_at(id.len()).1.to_vec(),
+                    decode_substate_bytes(&substate.value)?.to_vec(),
+                ))
+            })
+            .collect()
+    }
+
+    fn list_components_by_package(&self, package_address: PackageAddress) -> Vec<ComponentAddress> {
+        self.substates
+            .iter()
+            .filter_map(|(key, substate)| {
+                let component_address: ComponentAddress = scrypto_decode(key).ok()?;
+                let component: Component = decode_substate_value(&substate.value).ok()?;
+                if component.package_address() == package_address {
+                    Some(component_address)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn get_substate_by_phys_id(&self, phys_id: (Hash, u32)) -> Option<Substate> {
+        self.substates_by_phys_id.get(&phys_id).cloned()
+    }
+
+    fn get_address_by_phys_id(&self, phys_id: (Hash, u32)) -> Option<Vec<u8>> {
+        self.addresses_by_phys_id.get(&phys_id).cloned()
+    }
+}
+
 impl SubstateStore for InMemorySubstateStore {
     fn get_substate<T: Encode>(&self, address: &T) -> Option<Substate> {
         self.substates.get(&scrypto_encode(address)).cloned()
     }
 
     fn put_substate<T: Encode>(&mut self, address: &T, substate: Substate) {
-        self.substates.insert(scrypto_encode(address), substate);
+        let address = scrypto_encode(address);
+        let previous = self.substates.insert(address.clone(), substate.clone());
+        self.index_substate(address, &substate, previous);
     }
 
     fn get_child_substate<T: Encode>(&self, address: &T, key: &[u8]) -> Option<Substate> {
@@ -56,7 +139,8 @@ impl SubstateStore for InMemorySubstateStore {
     fn put_child_substate<T: Encode>(&mut self, address: &T, key: &[u8], substate: Substate) {
         let mut id = scrypto_encode(address);
         id.extend(key.to_vec());
-        self.child_substates.insert(id, substate);
+        let previous = self.child_substates.insert(id.clone(), substate.clone());
+        self.index_substate(id, &substate, previous);
     }
 
     fn get_epoch(&self) -> u64 {
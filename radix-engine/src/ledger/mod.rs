@@ -1,8 +1,19 @@
 mod memory;
+mod raw_substate_address;
+mod staging;
 mod traits;
 
 pub use memory::InMemorySubstateStore;
+pub use raw_substate_address::{RawSubstateAddress, TYPE_RAW_SUBSTATE_ADDRESS};
+pub use staging::StagingStore;
+pub use traits::decode_substate_bytes;
+pub use traits::decode_substate_value;
+pub use traits::encode_substate_bytes;
+pub use traits::encode_substate_value;
+pub use traits::BootstrapConfig;
+pub use traits::GenesisReceipt;
 pub use traits::QueryableSubstateStore;
 pub use traits::Substate;
 pub use traits::SubstateIdGenerator;
 pub use traits::SubstateStore;
+pub use traits::{SUBSTATE_SCHEMA_V1, SUBSTATE_SCHEMA_V2, SUBSTATE_SCHEMA_VERSION};
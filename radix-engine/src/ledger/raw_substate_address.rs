@@ -0,0 +1,33 @@
+use sbor::*;
+use scrypto::rust::vec::Vec;
+
+/// The type id which prefixes every encoded raw substate address.
+///
+/// Outside the range of Scrypto custom types, so a raw address never encodes to the same bytes
+/// as a typed one.
+pub const TYPE_RAW_SUBSTATE_ADDRESS: u8 = 0xf0;
+
+/// The address of an opaque substate, kept apart from the addresses of typed substates.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RawSubstateAddress(pub Vec<u8>);
+
+impl TypeId for RawSubstateAddress {
+    fn type_id() -> u8 {
+        TYPE_RAW_SUBSTATE_ADDRESS
+    }
+}
+
+impl Encode for RawSubstateAddress {
+    fn encode_value(&self, encoder: &mut Encoder) {
+        encoder.write_len(self.0.len());
+        encoder.write_slice(&self.0);
+    }
+}
+
+impl Decode for RawSubstateAddress {
+    fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        let len = decoder.read_len()?;
+        let slice = decoder.read_bytes(len)?;
+        Ok(Self(slice.to_vec()))
+    }
+}
@@ -0,0 +1,125 @@
+use sbor::*;
+use scrypto::buffer::scrypto_encode;
+use scrypto::rust::collections::HashMap;
+use scrypto::rust::string::String;
+use scrypto::rust::vec::Vec;
+
+use crate::ledger::traits::Substate;
+use crate::ledger::*;
+
+/// An address which has already been encoded, written verbatim when encoded again.
+struct EncodedAddress<'a>(&'a [u8]);
+
+impl<'a> TypeId for EncodedAddress<'a> {
+    fn type_id() -> u8 {
+        // Never written, as the encoded bytes already carry the type id
+        0
+    }
+}
+
+impl<'a> Encode for EncodedAddress<'a> {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.encode_value(encoder);
+    }
+
+    fn encode_value(&self, encoder: &mut Encoder) {
+        encoder.write_slice(self.0);
+    }
+}
+
+/// A substate store which stages writes on top of a base store.
+///
+/// Reads see the staged writes of every transaction executed so far, while the base store is
+/// left untouched until `commit` is called. Dropping the staging store discards staged writes.
+pub struct StagingStore<'s, S: SubstateStore> {
+    base: &'s mut S,
+    substates: HashMap<Vec<u8>, Substate>,
+    child_substates: HashMap<(Vec<u8>, Vec<u8>), Substate>,
+    epoch: Option<u64>,
+    nonce: u64,
+}
+
+impl<'s, S: SubstateStore> StagingStore<'s, S> {
+    pub fn new(base: &'s mut S) -> Self {
+        let nonce = base.get_nonce();
+        Self {
+            base,
+            substates: HashMap::new(),
+            child_substates: HashMap::new(),
+            epoch: None,
+            nonce,
+        }
+    }
+
+    /// Returns the base store, without any staged writes.
+    pub fn base(&self) -> &S {
+        self.base
+    }
+
+    /// Returns whether there is any staged write.
+    pub fn is_empty(&self) -> bool {
+        self.substates.is_empty()
+            && self.child_substates.is_empty()
+            && self.epoch.is_none()
+            && self.nonce == self.base.get_nonce()
+    }
+
+    /// Writes all staged writes into the base store and flushes it.
+    pub fn commit(self) -> Result<(), String> {
+        for (address, substate) in self.substates {
+            self.base.put_substate(&EncodedAddress(&address), substate);
+        }
+        for ((address, key), substate) in self.child_substates {
+            self.base
+                .put_child_substate(&EncodedAddress(&address), &key, substate);
+        }
+        if let Some(epoch) = self.epoch {
+            self.base.set_epoch(epoch);
+        }
+        while self.base.get_nonce() < self.nonce {
+            self.base.increase_nonce();
+        }
+        self.base.flush()
+    }
+}
+
+impl<'s, S: SubstateStore> SubstateStore for StagingStore<'s, S> {
+    fn get_substate<T: Encode>(&self, address: &T) -> Option<Substate> {
+        self.substates
+            .get(&scrypto_encode(address))
+            .cloned()
+            .or_else(|| self.base.get_substate(address))
+    }
+
+    fn put_substate<T: Encode>(&mut self, address: &T, substate: Substate) {
+        self.substates.insert(scrypto_encode(address), substate);
+    }
+
+    fn get_child_substate<T: Encode>(&self, address: &T, key: &[u8]) -> Option<Substate> {
+        self.child_substates
+            .get(&(scrypto_encode(address), key.to_vec()))
+            .cloned()
+            .or_else(|| self.base.get_child_substate(address, key))
+    }
+
+    fn put_child_substate<T: Encode>(&mut self, address: &T, key: &[u8], substate: Substate) {
+        self.child_substates
+            .insert((scrypto_encode(address), key.to_vec()), substate);
+    }
+
+    fn get_epoch(&self) -> u64 {
+        self.epoch.unwrap_or_else(|| self.base.get_epoch())
+    }
+
+    fn set_epoch(&mut self, epoch: u64) {
+        self.epoch = Some(epoch);
+    }
+
+    fn get_nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    fn increase_nonce(&mut self) {
+        self.nonce += 1;
+    }
+}
@@ -1,13 +1,14 @@
 use sbor::*;
-use scrypto::rule;
 use scrypto::buffer::*;
 use scrypto::constants::*;
 use scrypto::crypto::*;
 use scrypto::engine::types::*;
 use scrypto::prelude::LOCKED;
 use scrypto::resource::ResourceMethod::Withdraw;
+use scrypto::rule;
 use scrypto::rust::borrow::ToOwned;
 use scrypto::rust::collections::*;
+use scrypto::rust::string::String;
 use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
 
@@ -33,7 +34,96 @@ pub trait QueryableSubstateStore {
         &self,
         component_address: ComponentAddress,
         lazy_map_id: &LazyMapId,
-    ) -> HashMap<Vec<u8>, Vec<u8>>;
+    ) -> Result<HashMap<Vec<u8>, Vec<u8>>, DecodeError>;
+
+    /// Returns the addresses of all components instantiated from the given package.
+    fn list_components_by_package(&self, package_address: PackageAddress) -> Vec<ComponentAddress>;
+
+    /// Returns the substate written with the given physical id, even if it has since been downed.
+    fn get_substate_by_phys_id(&self, phys_id: (Hash, u32)) -> Option<Substate>;
+
+    /// Returns the raw address the substate with the given physical id was written under.
+    fn get_address_by_phys_id(&self, phys_id: (Hash, u32)) -> Option<Vec<u8>>;
+}
+
+/// Parameters of the native XRD token minted during bootstrap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootstrapConfig {
+    /// The divisibility of XRD.
+    pub xrd_divisibility: u8,
+    /// The amount of XRD minted into the system component.
+    pub initial_supply: Decimal,
+}
+
+/// The well-known addresses set up by bootstrap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenesisReceipt {
+    /// The system package.
+    pub system_package: PackageAddress,
+    /// The account package.
+    pub account_package: PackageAddress,
+    /// The native XRD token.
+    pub xrd_resource: ResourceAddress,
+    /// The virtual resource of ECDSA signatures.
+    pub ecdsa_resource: ResourceAddress,
+    /// The system component, which gives out free XRD.
+    pub faucet_component: ComponentAddress,
+}
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        Self {
+            xrd_divisibility: 18,
+            initial_supply: XRD_MAX_SUPPLY.into(),
+        }
+    }
+}
+
+/// The first schema version of encoded substate values.
+///
+/// Version bytes are taken from `0xf0..=0xff`, which SBOR never uses as a type id, so legacy
+/// values written without a version prefix can still be told apart.
+pub const SUBSTATE_SCHEMA_V1: u8 = 0xf1;
+
+/// The schema version in which packages, components, resource managers and non-fungibles
+/// gained fields.
+///
+/// Unprefixed and v1 values of these kinds may have been stored with fewer fields. Their
+/// `Decode` implementations tell the layouts apart by field count, and fill in defaults.
+pub const SUBSTATE_SCHEMA_V2: u8 = 0xf2;
+
+/// The schema version prepended to newly encoded substate values.
+pub const SUBSTATE_SCHEMA_VERSION: u8 = SUBSTATE_SCHEMA_V2;
+
+/// Prefixes an already encoded substate value, e.g. a lazy map entry, with the current schema
+/// version.
+pub fn encode_substate_bytes(value: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![SUBSTATE_SCHEMA_VERSION];
+    bytes.extend(value);
+    bytes
+}
+
+/// Strips the schema version off an encoded substate value, treating unprefixed legacy data
+/// as v1.
+pub fn decode_substate_bytes(bytes: &[u8]) -> Result<&[u8], DecodeError> {
+    match bytes.first() {
+        Some(&SUBSTATE_SCHEMA_V1) | Some(&SUBSTATE_SCHEMA_V2) => Ok(&bytes[1..]),
+        Some(&version) if version >= 0xf0 => Err(DecodeError::InvalidType {
+            expected: Some(SUBSTATE_SCHEMA_VERSION),
+            actual: version,
+        }),
+        _ => Ok(bytes),
+    }
+}
+
+/// Encodes a substate value, prefixed with the current schema version.
+pub fn encode_substate_value<V: Encode>(value: &V) -> Vec<u8> {
+    encode_substate_bytes(&scrypto_encode(value))
+}
+
+/// Decodes a substate value, treating unprefixed legacy data as v1.
+pub fn decode_substate_value<T: Decode>(bytes: &[u8]) -> Result<T, DecodeError> {
+    scrypto_decode(decode_substate_bytes(bytes)?)
 }
 
 #[derive(Clone, Debug, Encode, Decode, TypeId)]
@@ -69,9 +159,13 @@ pub trait SubstateStore {
     fn put_child_substate<T: Encode>(&mut self, address: &T, key: &[u8], substate: Substate);
 
     // Temporary Encoded/Decoded interface
-    fn get_decoded_substate<A: Encode, T: Decode>(&self, address: &A) -> Option<(T, (Hash, u32))> {
+    fn get_decoded_substate<A: Encode, T: Decode>(
+        &self,
+        address: &A,
+    ) -> Result<Option<(T, (Hash, u32))>, DecodeError> {
         self.get_substate(address)
-            .map(|s| (scrypto_decode(&s.value).unwrap(), s.phys_id))
+            .map(|s| Ok((decode_substate_value(&s.value)?, s.phys_id)))
+            .transpose()
     }
     fn put_encoded_substate<A: Encode, V: Encode>(
         &mut self,
@@ -82,7 +176,7 @@ pub trait SubstateStore {
         self.put_substate(
             address,
             Substate {
-                value: scrypto_encode(value),
+                value: encode_substate_value(value),
                 phys_id,
             },
         );
@@ -91,10 +185,11 @@ pub trait SubstateStore {
         &self,
         address: &A,
         key: &K,
-    ) -> Option<(T, (Hash, u32))> {
+    ) -> Result<Option<(T, (Hash, u32))>, DecodeError> {
         let child_key = &scrypto_encode(key);
         self.get_child_substate(address, child_key)
-            .map(|s| (scrypto_decode(&s.value).unwrap(), s.phys_id))
+            .map(|s| Ok((decode_substate_value(&s.value)?, s.phys_id)))
+            .transpose()
     }
     fn put_encoded_child_substate<A: Encode, K: Encode, V: Encode>(
         &mut self,
@@ -108,7 +203,7 @@ pub trait SubstateStore {
             address,
             child_key,
             Substate {
-                value: scrypto_encode(value),
+                value: encode_substate_value(value),
                 phys_id,
             },
         );
@@ -118,11 +213,12 @@ pub trait SubstateStore {
         address: &A,
         child_key: &C,
         grand_child_key: &[u8],
-    ) -> Option<(Vec<u8>, (Hash, u32))> {
+    ) -> Result<Option<(Vec<u8>, (Hash, u32))>, DecodeError> {
         let mut key = scrypto_encode(child_key);
         key.extend(grand_child_key.to_vec());
         self.get_child_substate(address, &key)
-            .map(|s| (s.value, s.phys_id))
+            .map(|s| Ok((decode_substate_bytes(&s.value)?.to_vec(), s.phys_id)))
+            .transpose()
     }
     fn put_encoded_grand_child_substate<A: Encode, C: Encode>(
         &mut self,
@@ -138,17 +234,24 @@ pub trait SubstateStore {
             address,
             &key,
             Substate {
-                value: value.to_vec(),
+                value: encode_substate_bytes(value),
                 phys_id,
             },
         );
     }
 
-    fn bootstrap(&mut self) {
-        let package: Option<Package> = self
-            .get_decoded_substate(&SYSTEM_PACKAGE)
-            .map(|(package, _)| package);
-        if package.is_none() {
+    /// Makes all previous writes durable. Called after every committed transaction.
+    fn flush(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Bootstraps the store, returning what was set up or `None` if already bootstrapped.
+    fn bootstrap(&mut self) -> Option<GenesisReceipt> {
+        self.bootstrap_with_config(BootstrapConfig::default())
+    }
+
+    fn bootstrap_with_config(&mut self, config: BootstrapConfig) -> Option<GenesisReceipt> {
+        if self.get_substate(&SYSTEM_PACKAGE).is_none() {
             let tx_hash = hash(self.get_and_increase_nonce().to_le_bytes());
             let mut id_gen = SubstateIdGenerator::new(tx_hash);
 
@@ -173,14 +276,16 @@ pub trait SubstateStore {
             resource_auth.insert(Withdraw, (rule!(allow_all), LOCKED));
 
             let mut xrd = ResourceManager::new(
-                ResourceType::Fungible { divisibility: 18 },
+                ResourceType::Fungible {
+                    divisibility: config.xrd_divisibility,
+                },
                 metadata,
                 resource_auth,
             )
             .unwrap();
             self.put_encoded_substate(&RADIX_TOKEN, &xrd, id_gen.next());
             let minted_xrd = xrd
-                .mint_fungible(XRD_MAX_SUPPLY.into(), RADIX_TOKEN.clone())
+                .mint_fungible(config.initial_supply, RADIX_TOKEN.clone())
                 .unwrap();
 
             let mut ecdsa_resource_auth = HashMap::new();
@@ -209,6 +314,16 @@ pub trait SubstateStore {
                 scrypto_encode(&SystemComponentState { xrd: XRD_VAULT }),
             );
             self.put_encoded_substate(&SYSTEM_COMPONENT, &system_component, id_gen.next());
+
+            Some(GenesisReceipt {
+                system_package: SYSTEM_PACKAGE,
+                account_package: ACCOUNT_PACKAGE,
+                xrd_resource: RADIX_TOKEN,
+                ecdsa_resource: ECDSA_TOKEN,
+                faucet_component: SYSTEM_COMPONENT,
+            })
+        } else {
+            None
         }
     }
 
@@ -19,6 +19,7 @@ pub enum AuthZoneError {
     InvalidRequestData(DecodeError),
     CouldNotGetProof,
     CouldNotGetResource,
+    NotEmpty,
 }
 
 /// A transient resource container.
@@ -52,6 +53,14 @@ impl AuthZone {
         self.proofs.push(proof);
     }
 
+    pub fn len(&self) -> usize {
+        self.proofs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.proofs.is_empty()
+    }
+
     fn clear(&mut self) {
         loop {
             if let Some(proof) = self.proofs.pop() {
@@ -89,6 +98,14 @@ impl AuthZone {
                 self.clear();
                 Ok(ScryptoValue::from_value(&()))
             }
+            "len" => Ok(ScryptoValue::from_value(&self.len())),
+            "assert_empty" => {
+                if self.is_empty() {
+                    Ok(ScryptoValue::from_value(&()))
+                } else {
+                    Err(AuthZoneError::NotEmpty)
+                }
+            }
             "pop" => {
                 let proof = self.pop()?;
                 let proof_id = system_api.create_proof(proof).map_err(|_| AuthZoneError::CouldNotCreateProof)?;
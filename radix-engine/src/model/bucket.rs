@@ -13,6 +13,7 @@ use scrypto::values::ScryptoValue;
 
 use crate::model::{
     Proof, ProofError, ResourceContainer, ResourceContainerError, ResourceContainerId,
+    ResourceMovement,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -25,6 +26,8 @@ pub enum BucketError {
     ResourceContainerError(ResourceContainerError),
     ProofError(ProofError),
     CouldNotCreateProof,
+    ResourceDoesNotExist(ResourceAddress),
+    NonFungibleReadOnly(NonFungibleAddress),
 }
 
 /// A transient resource container.
@@ -125,7 +128,7 @@ impl Bucket {
         self.borrow_container().resource_type()
     }
 
-    fn total_amount(&self) -> Decimal {
+    pub fn total_amount(&self) -> Decimal {
         self.borrow_container().total_amount()
     }
 
@@ -141,6 +144,16 @@ impl Bucket {
         self.borrow_container().is_empty()
     }
 
+    /// Returns whether this holds exactly the given amount and id set, where `None` leaves
+    /// that part unchecked.
+    pub fn contents_eq(
+        &self,
+        amount: Option<Decimal>,
+        ids: Option<BTreeSet<NonFungibleId>>,
+    ) -> bool {
+        self.borrow_container().contents_eq(amount, ids)
+    }
+
     pub fn into_container(self) -> Result<ResourceContainer, ResourceContainerError> {
         Rc::try_unwrap(self.container)
             .map_err(|_| ResourceContainerError::ContainerLocked)
@@ -227,15 +240,27 @@ impl Bucket {
         let resource_address = self.resource_address();
         let mut resource_manager = system_api
             .borrow_global_mut_resource_manager(resource_address)
-            .unwrap();
+            .map_err(|_| BucketError::ResourceDoesNotExist(resource_address))?;
         resource_manager.burn(self.total_amount());
-        if matches!(resource_manager.resource_type(), ResourceType::NonFungible) {
-            for id in self.total_ids().unwrap() {
-                let non_fungible_address = NonFungibleAddress::new(resource_address, id);
-                system_api.set_non_fungible(non_fungible_address, Option::None);
-            }
-        }
+        let result = if matches!(resource_manager.resource_type(), ResourceType::NonFungible) {
+            self.total_ids()
+                .unwrap()
+                .into_iter()
+                .map(|id| NonFungibleAddress::new(resource_address, id))
+                .try_for_each(|non_fungible_address| {
+                    system_api
+                        .set_non_fungible(non_fungible_address.clone(), Option::None)
+                        .map_err(|_| BucketError::NonFungibleReadOnly(non_fungible_address))
+                })
+        } else {
+            Ok(())
+        };
         system_api.return_borrowed_global_resource_manager(resource_address, resource_manager);
+        result?;
+        system_api.record_resource_movement(ResourceMovement::Burn {
+            resource_address,
+            amount: self.total_amount(),
+        });
 
         Ok(ScryptoValue::from_value(&()))
     }
@@ -1,6 +1,7 @@
 use sbor::*;
+use scrypto::abi;
 use scrypto::engine::types::*;
-use scrypto::resource::AccessRules;
+use scrypto::resource::{AccessRule, AccessRules};
 use scrypto::rust::string::String;
 use scrypto::rust::vec::Vec;
 use scrypto::values::*;
@@ -8,12 +9,36 @@ use scrypto::values::*;
 use crate::model::{convert, MethodAuthorization};
 
 /// A component is an instance of blueprint.
-#[derive(Debug, TypeId, Encode, Decode)]
+#[derive(Debug, TypeId, Encode)]
 pub struct Component {
     package_address: PackageAddress,
     blueprint_name: String,
     auths: Vec<AccessRules>,
     state: Vec<u8>,
+    package_version: u32,
+    frozen: bool,
+}
+
+// Components stored before `package_version` and `frozen` were added are decoded as
+// instantiated from package version 0 and not frozen.
+impl Decode for Component {
+    fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        let len = decoder.read_len()?;
+        if len != 4 && len != 6 {
+            return Err(DecodeError::InvalidLength {
+                expected: 6,
+                actual: len,
+            });
+        }
+        Ok(Self {
+            package_address: PackageAddress::decode(decoder)?,
+            blueprint_name: String::decode(decoder)?,
+            auths: Vec::<AccessRules>::decode(decoder)?,
+            state: Vec::<u8>::decode(decoder)?,
+            package_version: if len == 6 { u32::decode(decoder)? } else { 0 },
+            frozen: if len == 6 { bool::decode(decoder)? } else { false },
+        })
+    }
 }
 
 impl Component {
@@ -28,6 +53,8 @@ impl Component {
             blueprint_name,
             auths: method_auth,
             state,
+            package_version: 0,
+            frozen: false,
         }
     }
 
@@ -41,13 +68,26 @@ impl Component {
         let mut authorizations = Vec::new();
         for auth in &self.auths {
             let method_auth = auth.get(method_name);
-            let authorization = convert(schema, &data.dom, method_auth);
+            let authorization = match method_auth {
+                AccessRule::AllowAll if auth.contains(method_name) => MethodAuthorization::Public,
+                _ => convert(schema, &data.dom, method_auth),
+            };
             authorizations.push(authorization);
         }
 
         (data, authorizations)
     }
 
+    /// Returns the blueprint methods which no access rules of this component mention explicitly.
+    pub fn unconfigured_methods(&self, blueprint_abi: &abi::Blueprint) -> Vec<String> {
+        blueprint_abi
+            .methods
+            .iter()
+            .map(|method| method.name.clone())
+            .filter(|name| !self.auths.iter().any(|auth| auth.contains(name)))
+            .collect()
+    }
+
     pub fn authorization(&self) -> &[AccessRules] {
         &self.auths
     }
@@ -67,4 +107,22 @@ impl Component {
     pub fn set_state(&mut self, new_state: Vec<u8>) {
         self.state = new_state;
     }
+
+    /// Returns the version of the package which the state layout conforms to.
+    pub fn package_version(&self) -> u32 {
+        self.package_version
+    }
+
+    pub fn set_package_version(&mut self, package_version: u32) {
+        self.package_version = package_version;
+    }
+
+    /// Returns whether method calls to this component are blocked, except for unfreezing.
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen;
+    }
 }
@@ -1,5 +1,8 @@
 use sbor::*;
 use scrypto::engine::types::*;
+use scrypto::rust::format;
+use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
 use scrypto::rust::vec::Vec;
 
 use crate::model::method_authorization::MethodAuthorizationError::NotAuthorized;
@@ -154,6 +157,34 @@ impl HardProofRule {
             _ => Err(NotAuthorized),
         }
     }
+
+    /// Describes the part of this rule which is not satisfied, if any.
+    pub fn failed_rule(&self, auth_zones: &[&AuthZone]) -> Option<String> {
+        if self.check(auth_zones).is_ok() {
+            return None;
+        }
+
+        let description = match self {
+            HardProofRule::This(resource) => format!("require({:?})", resource),
+            HardProofRule::SomeOfResource(amount, resource) => {
+                format!("require_amount({:?}, {:?})", amount, resource)
+            }
+            HardProofRule::AllOf(HardProofRuleResourceList::List(resources)) => {
+                let (index, resource) = resources
+                    .iter()
+                    .enumerate()
+                    .find(|(_, resource)| !resource.check(auth_zones))
+                    .unwrap();
+                format!("AllOf[{}]: require({:?})", index, resource)
+            }
+            HardProofRule::AnyOf(resources) => format!("require_any_of({:?})", resources),
+            HardProofRule::CountOf(count, resources) => {
+                format!("require_n_of({:?}, {:?})", count, resources)
+            }
+            rule => format!("{:?}", rule),
+        };
+        Some(description)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, TypeId, Encode, Decode)]
@@ -181,12 +212,33 @@ impl HardAuthRule {
             }
         }
     }
+
+    fn failed_rule(&self, auth_zones: &[&AuthZone]) -> Option<String> {
+        match self {
+            HardAuthRule::ProofRule(rule) => rule.failed_rule(auth_zones),
+            HardAuthRule::AnyOf(rules) => {
+                if rules.iter().any(|r| r.check(auth_zones).is_ok()) {
+                    return None;
+                }
+                Some("AnyOf: no rule satisfied".to_string())
+            }
+            HardAuthRule::AllOf(rules) => rules
+                .iter()
+                .enumerate()
+                .find_map(|(index, r)| {
+                    r.failed_rule(auth_zones)
+                        .map(|failed| format!("AllOf[{}]: {}", index, failed))
+                }),
+        }
+    }
 }
 
 /// Snode which verifies authorization of a method call
 #[derive(Debug, Clone, PartialEq, Eq, Hash, TypeId, Encode, Decode)]
 pub enum MethodAuthorization {
     Protected(HardAuthRule),
+    /// The method was explicitly configured to allow all callers.
+    Public,
     AllowAll,
     DenyAll,
     Unsupported,
@@ -196,9 +248,19 @@ impl MethodAuthorization {
     pub fn check(&self, auth_zones: &[&AuthZone]) -> Result<(), MethodAuthorizationError> {
         match self {
             MethodAuthorization::Protected(rule) => rule.check(auth_zones),
-            MethodAuthorization::AllowAll => Ok(()),
+            MethodAuthorization::Public | MethodAuthorization::AllowAll => Ok(()),
             MethodAuthorization::DenyAll => Err(MethodAuthorizationError::NotAuthorized),
             MethodAuthorization::Unsupported => Err(MethodAuthorizationError::UnsupportedMethod),
         }
     }
+
+    /// Returns the path to the sub-rule which fails the check, e.g. `AllOf[1]: require(..)`.
+    pub fn failed_rule(&self, auth_zones: &[&AuthZone]) -> Option<String> {
+        match self {
+            MethodAuthorization::Protected(rule) => rule.failed_rule(auth_zones),
+            MethodAuthorization::Public | MethodAuthorization::AllowAll => None,
+            MethodAuthorization::DenyAll => Some("DenyAll".to_string()),
+            MethodAuthorization::Unsupported => Some("Unsupported".to_string()),
+        }
+    }
 }
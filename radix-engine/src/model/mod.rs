@@ -20,18 +20,19 @@ pub use auth_converter::convert;
 pub use bucket::{Bucket, BucketError};
 pub use component::Component;
 pub use method_authorization::{
-    HardProofRule, HardResourceOrNonFungible, MethodAuthorization, MethodAuthorizationError,
+    HardAuthRule, HardProofRule, HardResourceOrNonFungible, MethodAuthorization,
+    MethodAuthorizationError,
 };
 pub use non_fungible::NonFungible;
-pub use package::{Package, PackageError};
+pub use package::{Package, PackageError, MAX_PACKAGE_CODE_SIZE};
 pub use proof::*;
-pub use receipt::Receipt;
+pub use receipt::{BalanceChanges, Receipt, ReceiptDiff, ReceiptField, ResourceMovement};
 pub use resource::*;
 pub use resource_manager::{ResourceManager, ResourceManagerError};
 pub use transaction_process::{TransactionProcess};
 pub use transaction::{
-    Instruction, SignedTransaction, Transaction,
+    Condition, Instruction, SignedTransaction, Transaction,
 };
 pub use validated_transaction::{ValidatedTransaction, ValidatedInstruction};
 pub use vault::{Vault, VaultError};
-pub use worktop::{Worktop, WorktopError};
+pub use worktop::{Worktop, WorktopError, DEFAULT_MAX_WORKTOP_RESOURCES};
@@ -2,10 +2,29 @@ use sbor::*;
 use scrypto::rust::vec::Vec;
 
 /// A non-fungible is a piece of data that is uniquely identified within a resource.
-#[derive(Debug, Clone, TypeId, Encode, Decode)]
+#[derive(Debug, Clone, TypeId, Encode)]
 pub struct NonFungible {
     immutable_data: Vec<u8>,
     mutable_data: Vec<u8>,
+    version: u64,
+}
+
+// Non-fungibles stored before `version` was added are decoded as version 0.
+impl Decode for NonFungible {
+    fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        let len = decoder.read_len()?;
+        if len != 2 && len != 3 {
+            return Err(DecodeError::InvalidLength {
+                expected: 3,
+                actual: len,
+            });
+        }
+        Ok(Self {
+            immutable_data: Vec::<u8>::decode(decoder)?,
+            mutable_data: Vec::<u8>::decode(decoder)?,
+            version: if len == 3 { u64::decode(decoder)? } else { 0 },
+        })
+    }
 }
 
 impl NonFungible {
@@ -13,6 +32,7 @@ impl NonFungible {
         Self {
             immutable_data,
             mutable_data,
+            version: 0,
         }
     }
 
@@ -24,7 +44,13 @@ impl NonFungible {
         self.mutable_data.clone()
     }
 
+    /// Returns the number of times the mutable data has been updated.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
     pub fn set_mutable_data(&mut self, new_mutable_data: Vec<u8>) {
         self.mutable_data = new_mutable_data;
+        self.version += 1;
     }
 }
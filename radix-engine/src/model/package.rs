@@ -1,6 +1,7 @@
 use sbor::*;
 use scrypto::abi::{Function, Method};
 use scrypto::buffer::scrypto_decode;
+use scrypto::crypto::{hash, Hash};
 use scrypto::rust::collections::HashMap;
 use scrypto::rust::string::String;
 use scrypto::rust::string::ToString;
@@ -15,11 +16,44 @@ use wasmi::{
 use crate::engine::{EnvModuleResolver, SystemApi};
 use crate::errors::WasmValidationError;
 
+/// The maximum size of package code, in bytes.
+pub const MAX_PACKAGE_CODE_SIZE: usize = 1024 * 1024;
+
 /// A collection of blueprints, compiled and published as a single unit.
-#[derive(Debug, Clone, TypeId, Encode, Decode)]
+#[derive(Debug, Clone, TypeId, Encode)]
 pub struct Package {
     code: Vec<u8>,
+    code_hash: Hash,
     blueprints: HashMap<String, Type>,
+    version: u32,
+}
+
+// Packages stored before `version` and `code_hash` were added are decoded with version 0 and
+// the hash of their code.
+impl Decode for Package {
+    fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        let len = decoder.read_len()?;
+        if len != 2 && len != 4 {
+            return Err(DecodeError::InvalidLength {
+                expected: 4,
+                actual: len,
+            });
+        }
+        let code = Vec::<u8>::decode(decoder)?;
+        let code_hash = if len == 4 {
+            Hash::decode(decoder)?
+        } else {
+            hash(&code)
+        };
+        let blueprints = HashMap::<String, Type>::decode(decoder)?;
+        let version = if len == 4 { u32::decode(decoder)? } else { 0 };
+        Ok(Self {
+            code,
+            code_hash,
+            blueprints,
+            version,
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -28,13 +62,54 @@ pub enum PackageError {
     BlueprintNotFound,
     WasmValidationError(WasmValidationError),
     MethodNotFound(String),
+    CouldNotCreatePackage,
 }
 
 impl Package {
     /// Validates and creates a package
     pub fn new(code: Vec<u8>) -> Result<Self, WasmValidationError> {
+        Self::new_with_version(code, 0)
+    }
+
+    /// Validates and creates a package with the given upgrade version.
+    pub fn new_with_version(code: Vec<u8>, version: u32) -> Result<Self, WasmValidationError> {
+        let imports = ImportsBuilder::new().with_resolver("env", &EnvModuleResolver);
+        let blueprints = Self::validate(&code, &imports)?;
+
+        Ok(Self {
+            blueprints,
+            code_hash: hash(&code),
+            code,
+            version,
+        })
+    }
+
+    /// Validates many package codes, sharing the import resolver between them.
+    ///
+    /// Returns one result per code, in the same order.
+    pub fn validate_many(codes: &[&[u8]]) -> Vec<Result<(), WasmValidationError>> {
+        let imports = ImportsBuilder::new().with_resolver("env", &EnvModuleResolver);
+        codes
+            .iter()
+            .map(|code| Self::validate(code, &imports).map(|_| ()))
+            .collect()
+    }
+
+    /// Validates package code and returns the schemas of its blueprints.
+    fn validate(
+        code: &[u8],
+        imports: &ImportsBuilder,
+    ) -> Result<HashMap<String, Type>, WasmValidationError> {
+        // Check size
+        if code.len() > MAX_PACKAGE_CODE_SIZE {
+            return Err(WasmValidationError::CodeTooLarge {
+                size: code.len(),
+                max: MAX_PACKAGE_CODE_SIZE,
+            });
+        }
+
         // Parse
-        let parsed = Self::parse_module(&code)?;
+        let parsed = Self::parse_module(code)?;
 
         // check floating point
         parsed
@@ -42,11 +117,8 @@ impl Package {
             .map_err(|_| WasmValidationError::FloatingPointNotAllowed)?;
 
         // Instantiate
-        let instance = ModuleInstance::new(
-            &parsed,
-            &ImportsBuilder::new().with_resolver("env", &EnvModuleResolver),
-        )
-        .map_err(|_| WasmValidationError::InvalidModule)?;
+        let instance = ModuleInstance::new(&parsed, imports)
+            .map_err(|_| WasmValidationError::InvalidModule)?;
 
         // Check start function
         if instance.has_start() {
@@ -105,13 +177,23 @@ impl Package {
             }
         }
 
-        Ok(Self { blueprints, code })
+        Ok(blueprints)
     }
 
     pub fn code(&self) -> &[u8] {
         &self.code
     }
 
+    /// Returns the hash of the package code, computed at validation time.
+    pub fn code_hash(&self) -> Hash {
+        self.code_hash
+    }
+
+    /// Returns the number of times this package has been upgraded.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
     pub fn contains_blueprint(&self, blueprint_name: &str) -> bool {
         self.blueprints.contains_key(blueprint_name)
     }
@@ -123,8 +205,8 @@ impl Package {
     }
 
     pub fn load_module(&self) -> Result<(ModuleRef, MemoryRef), PackageError> {
-        let module = Self::parse_module(&self.code).unwrap();
-        let inst = Self::instantiate_module(&module).unwrap();
+        let module = Self::parse_module(&self.code).map_err(PackageError::WasmValidationError)?;
+        let inst = Self::instantiate_module(&module).map_err(PackageError::WasmValidationError)?;
         Ok(inst)
     }
 
@@ -159,7 +241,9 @@ impl Package {
                 let bytes =
                     scrypto_decode(&args[0].raw).map_err(PackageError::InvalidRequestData)?;
                 let package = Package::new(bytes).map_err(PackageError::WasmValidationError)?;
-                let package_address = system_api.create_package(package);
+                let package_address = system_api
+                    .create_package(package)
+                    .map_err(|_| PackageError::CouldNotCreatePackage)?;
                 Ok(ScryptoValue::from_value(&package_address))
             }
             _ => Err(PackageError::MethodNotFound(function.to_string())),
@@ -264,6 +264,9 @@ impl Proof {
     ///
     /// Note that cloning a proof will update the ref count of the locked
     /// resources in the source containers.
+    ///
+    /// The clone is restricted if this proof is; restricting either proof later doesn't
+    /// affect the other.
     pub fn clone(&self) -> Self {
         for (_, (container, locked_amount_or_ids)) in &self.evidence {
             match locked_amount_or_ids {
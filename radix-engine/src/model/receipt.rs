@@ -1,6 +1,7 @@
 use colored::*;
 use scrypto::engine::types::*;
 use scrypto::rust::borrow::ToOwned;
+use scrypto::rust::collections::HashMap;
 use scrypto::rust::fmt;
 use scrypto::rust::format;
 use scrypto::rust::string::String;
@@ -8,7 +9,7 @@ use scrypto::rust::string::ToString;
 use scrypto::rust::vec::Vec;
 use scrypto::values::*;
 
-use crate::engine::CommitReceipt;
+use crate::engine::{CommitReceipt, NO_LOG_COMPONENT};
 use crate::errors::*;
 use crate::model::*;
 
@@ -18,13 +19,157 @@ pub struct Receipt {
     pub validated_transaction: ValidatedTransaction,
     pub result: Result<(), RuntimeError>,
     pub outputs: Vec<ScryptoValue>,
-    pub logs: Vec<(Level, String)>,
+    pub logs: Vec<(Level, ComponentAddress, String)>,
     pub new_package_addresses: Vec<PackageAddress>,
     pub new_component_addresses: Vec<ComponentAddress>,
     pub new_resource_addresses: Vec<ResourceAddress>,
+    pub balance_changes: BalanceChanges,
+    pub supply_changes: HashMap<ResourceAddress, Decimal>,
+    pub resource_movements: Vec<ResourceMovement>,
     pub execution_time: Option<u128>,
 }
 
+/// The net change of vault balances per component, caused by a transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BalanceChanges {
+    pub per_account: HashMap<ComponentAddress, HashMap<ResourceAddress, Decimal>>,
+}
+
+impl BalanceChanges {
+    /// Adds a balance change, dropping entries which net out to zero.
+    pub fn add(
+        &mut self,
+        account: ComponentAddress,
+        resource_address: ResourceAddress,
+        amount: Decimal,
+    ) {
+        let resources = self.per_account.entry(account).or_default();
+        let total = resources.entry(resource_address).or_default();
+        *total += amount;
+        if total.is_zero() {
+            resources.remove(&resource_address);
+        }
+        if resources.is_empty() {
+            self.per_account.remove(&account);
+        }
+    }
+
+    /// Returns the net change of the given resource in the given account.
+    pub fn get(&self, account: ComponentAddress, resource_address: ResourceAddress) -> Decimal {
+        self.per_account
+            .get(&account)
+            .and_then(|resources| resources.get(&resource_address))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// A movement of resources, recorded by the engine regardless of what blueprints log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceMovement {
+    /// Resources taken from a vault into a bucket.
+    Withdraw {
+        vault_id: VaultId,
+        resource_address: ResourceAddress,
+        amount: Decimal,
+    },
+    /// Resources put from a bucket into a vault.
+    Deposit {
+        vault_id: VaultId,
+        resource_address: ResourceAddress,
+        amount: Decimal,
+    },
+    /// Resources newly minted.
+    Mint {
+        resource_address: ResourceAddress,
+        amount: Decimal,
+    },
+    /// Resources burnt.
+    Burn {
+        resource_address: ResourceAddress,
+        amount: Decimal,
+    },
+}
+
+/// A field of a receipt which differs between two receipts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReceiptField {
+    Transaction,
+    Result,
+    Output(usize),
+    Log(usize),
+    NewPackageAddresses,
+    NewComponentAddresses,
+    NewResourceAddresses,
+    BalanceChanges,
+    SupplyChanges,
+    ResourceMovements,
+    SubstateOps,
+}
+
+/// Represents the differences between two receipts, ignoring execution time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReceiptDiff {
+    pub fields: Vec<ReceiptField>,
+}
+
+impl ReceiptDiff {
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+impl Receipt {
+    /// Compares this receipt with another one, field by field.
+    pub fn diff(&self, other: &Receipt) -> ReceiptDiff {
+        let mut fields = Vec::new();
+        if self.validated_transaction != other.validated_transaction {
+            fields.push(ReceiptField::Transaction);
+        }
+        if self.result != other.result {
+            fields.push(ReceiptField::Result);
+        }
+        for i in 0..self.outputs.len().max(other.outputs.len()) {
+            if self.outputs.get(i) != other.outputs.get(i) {
+                fields.push(ReceiptField::Output(i));
+            }
+        }
+        for i in 0..self.logs.len().max(other.logs.len()) {
+            if self.logs.get(i) != other.logs.get(i) {
+                fields.push(ReceiptField::Log(i));
+            }
+        }
+        if self.new_package_addresses != other.new_package_addresses {
+            fields.push(ReceiptField::NewPackageAddresses);
+        }
+        if self.new_component_addresses != other.new_component_addresses {
+            fields.push(ReceiptField::NewComponentAddresses);
+        }
+        if self.new_resource_addresses != other.new_resource_addresses {
+            fields.push(ReceiptField::NewResourceAddresses);
+        }
+        if self.balance_changes != other.balance_changes {
+            fields.push(ReceiptField::BalanceChanges);
+        }
+        if self.supply_changes != other.supply_changes {
+            fields.push(ReceiptField::SupplyChanges);
+        }
+        if self.resource_movements != other.resource_movements {
+            fields.push(ReceiptField::ResourceMovements);
+        }
+        if self.commit_receipt != other.commit_receipt {
+            fields.push(ReceiptField::SubstateOps);
+        }
+        ReceiptDiff { fields }
+    }
+}
+
+impl PartialEq for Receipt {
+    fn eq(&self, other: &Self) -> bool {
+        self.diff(other).is_empty()
+    }
+}
+
 macro_rules! prefix {
     ($i:expr, $list:expr) => {
         if $i == $list.len() - 1 {
@@ -72,19 +217,42 @@ impl fmt::Debug for Receipt {
 
         write!(f, "\n{}", "Instruction Outputs:".bold().green())?;
         for (i, result) in self.outputs.iter().enumerate() {
-            write!(f, "\n{} {:?}", prefix!(i, self.outputs), result)?;
+            write!(f, "\n{} {}", prefix!(i, self.outputs), result.pretty(2))?;
         }
 
         write!(f, "\n{} {}", "Logs:".bold().green(), self.logs.len())?;
-        for (i, (level, msg)) in self.logs.iter().enumerate() {
-            let (l, m) = match level {
-                Level::Error => ("ERROR".red(), msg.red()),
-                Level::Warn => ("WARN".yellow(), msg.yellow()),
-                Level::Info => ("INFO".green(), msg.green()),
-                Level::Debug => ("DEBUG".cyan(), msg.cyan()),
-                Level::Trace => ("TRACE".normal(), msg.normal()),
-            };
-            write!(f, "\n{} [{:5}] {}", prefix!(i, self.logs), l, m)?;
+        let mut components: Vec<ComponentAddress> = Vec::new();
+        for (_, component_address, _) in &self.logs {
+            if !components.contains(component_address) {
+                components.push(*component_address);
+            }
+        }
+        for (i, component_address) in components.iter().enumerate() {
+            if *component_address == NO_LOG_COMPONENT {
+                write!(f, "\n{} Transaction", prefix!(i, components))?;
+            } else {
+                write!(
+                    f,
+                    "\n{} Component: {}",
+                    prefix!(i, components),
+                    component_address
+                )?;
+            }
+            let logs: Vec<&(Level, ComponentAddress, String)> = self
+                .logs
+                .iter()
+                .filter(|(_, c, _)| c == component_address)
+                .collect();
+            for (j, (level, _, msg)) in logs.iter().enumerate() {
+                let (l, m) = match level {
+                    Level::Error => ("ERROR".red(), msg.red()),
+                    Level::Warn => ("WARN".yellow(), msg.yellow()),
+                    Level::Info => ("INFO".green(), msg.green()),
+                    Level::Debug => ("DEBUG".cyan(), msg.cyan()),
+                    Level::Trace => ("TRACE".normal(), msg.normal()),
+                };
+                write!(f, "\n   {} [{:5}] {}", prefix!(j, logs), l, m)?;
+            }
         }
 
         write!(
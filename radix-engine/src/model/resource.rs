@@ -138,7 +138,7 @@ impl ResourceContainer {
     pub fn take_by_amount(&mut self, amount: Decimal) -> Result<Self, ResourceContainerError> {
         // check amount granularity
         let divisibility = self.resource_type().divisibility();
-        Self::check_amount(amount, divisibility)?;
+        assert_valid_amount(amount, divisibility)?;
 
         // deduct from liquidity pool
         match self {
@@ -191,7 +191,7 @@ impl ResourceContainer {
     ) -> Result<LockedAmountOrIds, ResourceContainerError> {
         // check amount granularity
         let divisibility = self.resource_type().divisibility();
-        Self::check_amount(amount, divisibility)?;
+        assert_valid_amount(amount, divisibility)?;
 
         match self {
             Self::Fungible {
@@ -371,6 +371,18 @@ impl ResourceContainer {
         self.total_amount().is_zero()
     }
 
+    /// Returns whether the container holds exactly the given amount and id set, where `None`
+    /// leaves that part unchecked. An id set never matches a fungible container.
+    pub fn contents_eq(
+        &self,
+        amount: Option<Decimal>,
+        ids: Option<BTreeSet<NonFungibleId>>,
+    ) -> bool {
+        let amount_eq = amount.map_or(true, |amount| self.total_amount() == amount);
+        let ids_eq = ids.map_or(true, |ids| self.total_ids() == Ok(ids));
+        amount_eq && ids_eq
+    }
+
     pub fn resource_address(&self) -> ResourceAddress {
         match self {
             Self::Fungible {
@@ -390,12 +402,17 @@ impl ResourceContainer {
             Self::NonFungible { .. } => ResourceType::NonFungible,
         }
     }
+}
 
-    fn check_amount(amount: Decimal, divisibility: u8) -> Result<(), ResourceContainerError> {
-        if amount.is_negative() || amount.0 % 10i128.pow((18 - divisibility).into()) != 0.into() {
-            Err(ResourceContainerError::InvalidAmount(amount, divisibility))
-        } else {
-            Ok(())
-        }
+/// Checks that an amount is non-negative and has no more decimal places than the divisibility
+/// allows, e.g. `1.5` is rejected for a resource of divisibility `0`.
+pub fn assert_valid_amount(
+    amount: Decimal,
+    divisibility: u8,
+) -> Result<(), ResourceContainerError> {
+    if amount.is_negative() || amount.0 % 10i128.pow((18 - divisibility).into()) != 0.into() {
+        Err(ResourceContainerError::InvalidAmount(amount, divisibility))
+    } else {
+        Ok(())
     }
 }
@@ -1,5 +1,5 @@
 use crate::engine::SystemApi;
-use crate::model::NonFungible;
+use crate::model::{assert_valid_amount, NonFungible};
 use sbor::*;
 use scrypto::buffer::scrypto_decode;
 use scrypto::engine::types::*;
@@ -9,12 +9,15 @@ use scrypto::resource::Mutability::LOCKED;
 use scrypto::resource::ResourceMethod::{Burn, Mint, UpdateMetadata, UpdateNonFungibleData};
 use scrypto::resource::*;
 use scrypto::rust::collections::*;
+use scrypto::rust::format;
 use scrypto::rust::string::String;
 use scrypto::rust::string::ToString;
 use scrypto::rust::vec::*;
 use scrypto::values::ScryptoValue;
 
-use crate::model::{convert, MethodAuthorization, ResourceContainer};
+use crate::model::{
+    convert, MethodAuthorization, ResourceContainer, ResourceContainerError, ResourceMovement,
+};
 
 /// Converts soft authorization rule to a hard authorization rule.
 /// Currently required as all auth is defined by soft authorization rules.
@@ -33,12 +36,20 @@ pub enum ResourceManagerError {
     InvalidMintPermission,
     ResourceTypeDoesNotMatch,
     MaxMintAmountExceeded,
+    MintBatchTooLarge,
+    MaxSupplyExceeded,
     InvalidNonFungibleData,
     NonFungibleAlreadyExists(NonFungibleAddress),
     NonFungibleNotFound(NonFungibleAddress),
+    NonFungibleReadOnly(NonFungibleAddress),
+    UnknownNonFungible(NonFungibleAddress),
+    VersionConflict(NonFungibleAddress),
     InvalidRequestData(DecodeError),
     MethodNotFound(String),
+    ResourceContainerError(ResourceContainerError),
     CouldNotCreateBucket,
+    CouldNotTakeBucket,
+    CouldNotCreateResource,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
@@ -101,20 +112,56 @@ impl MethodEntry {
 }
 
 /// The definition of a resource.
-#[derive(Debug, Clone, TypeId, Encode, Decode)]
+#[derive(Debug, Clone, TypeId, Encode)]
 pub struct ResourceManager {
     resource_type: ResourceType,
     metadata: HashMap<String, String>,
     method_table: HashMap<String, Option<ResourceMethod>>,
     authorization: HashMap<ResourceMethod, MethodEntry>,
     total_supply: Decimal,
+    max_supply: Option<Decimal>,
+}
+
+// Resource managers stored before `max_supply` was added are decoded as uncapped.
+impl Decode for ResourceManager {
+    fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        let len = decoder.read_len()?;
+        if len != 5 && len != 6 {
+            return Err(DecodeError::InvalidLength {
+                expected: 6,
+                actual: len,
+            });
+        }
+        Ok(Self {
+            resource_type: ResourceType::decode(decoder)?,
+            metadata: HashMap::<String, String>::decode(decoder)?,
+            method_table: HashMap::<String, Option<ResourceMethod>>::decode(decoder)?,
+            authorization: HashMap::<ResourceMethod, MethodEntry>::decode(decoder)?,
+            total_supply: Decimal::decode(decoder)?,
+            max_supply: if len == 6 {
+                Option::<Decimal>::decode(decoder)?
+            } else {
+                None
+            },
+        })
+    }
 }
 
 impl ResourceManager {
     pub fn new(
+        resource_type: ResourceType,
+        metadata: HashMap<String, String>,
+        auth: HashMap<ResourceMethod, (AccessRule, Mutability)>,
+    ) -> Result<Self, ResourceManagerError> {
+        Self::new_with_max_supply(resource_type, metadata, auth, None)
+    }
+
+    /// Creates a resource whose total supply may never exceed `max_supply`, if set.
+    pub fn new_with_max_supply(
         resource_type: ResourceType,
         metadata: HashMap<String, String>,
         mut auth: HashMap<ResourceMethod, (AccessRule, Mutability)>,
+        max_supply: Option<Decimal>,
     ) -> Result<Self, ResourceManagerError> {
         let mut method_table: HashMap<String, Option<ResourceMethod>> = HashMap::new();
         method_table.insert("mint".to_string(), Some(Mint));
@@ -124,6 +171,7 @@ impl ResourceManager {
         method_table.insert("update_metadata".to_string(), Some(UpdateMetadata));
         if let ResourceType::NonFungible = resource_type {
             method_table.insert("take_non_fungibles_from_vault".to_string(), Some(Withdraw));
+            method_table.insert("burn_non_fungibles".to_string(), Some(Burn));
         }
 
         for pub_method in [
@@ -133,11 +181,14 @@ impl ResourceManager {
             "get_metadata",
             "get_resource_type",
             "get_total_supply",
+            "get_max_supply",
             "take_from_bucket",
             "put_into_bucket",
             "get_bucket_amount",
             "get_bucket_resource_address",
             "get_vault_amount",
+            "get_vault_locked_amount",
+            "get_vault_available_amount",
             "get_vault_resource_address",
             "create_vault_proof",
             "create_vault_proof_by_amount",
@@ -151,10 +202,15 @@ impl ResourceManager {
                 "update_non_fungible_mutable_data".to_string(),
                 Some(UpdateNonFungibleData),
             );
+            method_table.insert(
+                "update_non_fungible_mutable_data_if_version".to_string(),
+                Some(UpdateNonFungibleData),
+            );
             for pub_method in [
                 "take_non_fungibles_from_bucket",
                 "non_fungible_exists",
                 "get_non_fungible",
+                "get_non_fungible_version",
                 "get_non_fungible_ids_in_bucket",
                 "get_non_fungible_ids_in_vault",
             ] {
@@ -181,7 +237,11 @@ impl ResourceManager {
             method_table,
             authorization,
             total_supply: 0.into(),
+            max_supply,
         };
+        if let Some(max_supply) = max_supply {
+            resource_manager.check_amount(max_supply)?;
+        }
 
         Ok(resource_manager)
     }
@@ -221,18 +281,56 @@ impl ResourceManager {
         self.total_supply
     }
 
+    pub fn max_supply(&self) -> Option<Decimal> {
+        self.max_supply
+    }
+
+    /// Returns the authorization of each resource method, keyed by method name.
+    pub fn auth_config(&self) -> BTreeMap<String, MethodAuthorization> {
+        self.authorization
+            .iter()
+            .map(|(method, entry)| (format!("{:?}", method), entry.get_method_auth().clone()))
+            .collect()
+    }
+
+    /// Returns whether more of this resource may ever be minted.
+    pub fn mintable(&self) -> bool {
+        self.is_enabled(&Mint)
+    }
+
+    /// Returns whether this resource may ever be burnt.
+    pub fn burnable(&self) -> bool {
+        self.is_enabled(&Burn)
+    }
+
+    /// A method is disabled for good if both its rule and the rule for updating it deny all.
+    fn is_enabled(&self, method: &ResourceMethod) -> bool {
+        match self.authorization.get(method) {
+            Some(entry) => !matches!(
+                (&entry.auth, &entry.update_auth),
+                (MethodAuthorization::DenyAll, MethodAuthorization::DenyAll)
+            ),
+            None => false,
+        }
+    }
+
     fn mint<S: SystemApi>(
         &mut self,
         mint_params: MintParams,
         self_address: ResourceAddress,
         system_api: &mut S,
     ) -> Result<ResourceContainer, ResourceManagerError> {
-        match mint_params {
+        let container = match mint_params {
             MintParams::Fungible { amount } => self.mint_fungible(amount, self_address),
             MintParams::NonFungible { entries } => {
                 self.mint_non_fungibles(entries, self_address, system_api)
             }
-        }
+        }?;
+        system_api.record_resource_movement(ResourceMovement::Mint {
+            resource_address: self_address,
+            amount: container.total_amount(),
+        });
+        Ok(container)
     }
 
     pub fn mint_fungible(
@@ -249,6 +347,7 @@ impl ResourceManager {
             if amount > 100_000_000_000i128.into() {
                 return Err(ResourceManagerError::MaxMintAmountExceeded);
             }
+            self.check_max_supply(amount)?;
 
             self.total_supply += amount;
 
@@ -291,6 +390,10 @@ impl ResourceManager {
             return Err(ResourceManagerError::ResourceTypeDoesNotMatch);
         }
 
+        if entries.len() > system_api.max_mint_batch_size() {
+            return Err(ResourceManagerError::MintBatchTooLarge);
+        }
+
         // check amount
         let amount = entries.len().into();
         self.check_amount(amount)?;
@@ -300,6 +403,7 @@ impl ResourceManager {
         if amount > 100_000_000_000i128.into() {
             return Err(ResourceManagerError::MaxMintAmountExceeded);
         }
+        self.check_max_supply(amount)?;
 
         self.total_supply += amount;
 
@@ -307,7 +411,13 @@ impl ResourceManager {
         let mut ids = BTreeSet::new();
         for (id, data) in entries {
             let non_fungible_address = NonFungibleAddress::new(self_address, id.clone());
-            if system_api.get_non_fungible(&non_fungible_address).is_some() {
+            if system_api
+                .get_non_fungible(&non_fungible_address)
+                .map_err(|_| {
+                    ResourceManagerError::UnknownNonFungible(non_fungible_address.clone())
+                })?
+                .is_some()
+            {
                 return Err(ResourceManagerError::NonFungibleAlreadyExists(
                     non_fungible_address,
                 ));
@@ -317,7 +427,9 @@ impl ResourceManager {
             let mutable_data = Self::process_non_fungible_data(&data.1)?;
             let non_fungible = NonFungible::new(immutable_data.raw, mutable_data.raw);
 
-            system_api.set_non_fungible(non_fungible_address, Some(non_fungible));
+            system_api
+                .set_non_fungible(non_fungible_address.clone(), Some(non_fungible))
+                .map_err(|_| ResourceManagerError::NonFungibleReadOnly(non_fungible_address))?;
             ids.insert(id);
         }
 
@@ -339,11 +451,16 @@ impl ResourceManager {
 
     fn check_amount(&self, amount: Decimal) -> Result<(), ResourceManagerError> {
         let divisibility = self.resource_type.divisibility();
+        assert_valid_amount(amount, divisibility)
+            .map_err(|_| ResourceManagerError::InvalidAmount(amount, divisibility))
+    }
 
-        if amount.is_negative() || amount.0 % 10i128.pow((18 - divisibility).into()) != 0.into() {
-            Err(ResourceManagerError::InvalidAmount(amount, divisibility))
-        } else {
-            Ok(())
+    fn check_max_supply(&self, amount: Decimal) -> Result<(), ResourceManagerError> {
+        match self.max_supply {
+            Some(max_supply) if self.total_supply + amount > max_supply => {
+                Err(ResourceManagerError::MaxSupplyExceeded)
+            }
+            _ => Ok(()),
         }
     }
 
@@ -362,8 +479,20 @@ impl ResourceManager {
                     .map_err(ResourceManagerError::InvalidRequestData)?;
                 let mint_params_maybe: Option<MintParams> = scrypto_decode(&args[3].raw)
                     .map_err(ResourceManagerError::InvalidRequestData)?;
-                let resource_manager = ResourceManager::new(resource_type, metadata, auth)?;
-                let resource_address = system_api.create_resource(resource_manager);
+                let max_supply: Option<Decimal> = match args.get(4) {
+                    Some(arg) => scrypto_decode(&arg.raw)
+                        .map_err(ResourceManagerError::InvalidRequestData)?,
+                    None => None,
+                };
+                let resource_manager = ResourceManager::new_with_max_supply(
+                    resource_type,
+                    metadata,
+                    auth,
+                    max_supply,
+                )?;
+                let resource_address = system_api
+                    .create_resource(resource_manager)
+                    .map_err(|_| ResourceManagerError::CouldNotCreateResource)?;
 
                 let bucket_id = if let Some(mint_params) = mint_params_maybe {
                     let mut resource_manager = system_api
@@ -431,6 +560,7 @@ impl ResourceManager {
             "get_metadata" => Ok(ScryptoValue::from_value(&self.metadata)),
             "get_resource_type" => Ok(ScryptoValue::from_value(&self.resource_type)),
             "get_total_supply" => Ok(ScryptoValue::from_value(&self.total_supply)),
+            "get_max_supply" => Ok(ScryptoValue::from_value(&self.max_supply)),
             "update_metadata" => {
                 let new_metadata: HashMap<String, String> = scrypto_decode(&args[0].raw)
                     .map_err(|e| ResourceManagerError::InvalidRequestData(e))?;
@@ -448,12 +578,49 @@ impl ResourceManager {
                 let data = Self::process_non_fungible_data(&new_mutable_data)?;
                 let mut non_fungible = system_api
                     .get_non_fungible(&non_fungible_address)
+                    .map_err(|_| {
+                        ResourceManagerError::UnknownNonFungible(non_fungible_address.clone())
+                    })?
                     .cloned()
                     .ok_or(ResourceManagerError::NonFungibleNotFound(
                         non_fungible_address.clone(),
                     ))?;
                 non_fungible.set_mutable_data(data.raw);
-                system_api.set_non_fungible(non_fungible_address, Some(non_fungible));
+                system_api
+                    .set_non_fungible(non_fungible_address.clone(), Some(non_fungible))
+                    .map_err(|_| ResourceManagerError::NonFungibleReadOnly(non_fungible_address))?;
+
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "update_non_fungible_mutable_data_if_version" => {
+                let non_fungible_id: NonFungibleId = scrypto_decode(&args[0].raw)
+                    .map_err(|e| ResourceManagerError::InvalidRequestData(e))?;
+                let expected_version: u64 = scrypto_decode(&args[1].raw)
+                    .map_err(|e| ResourceManagerError::InvalidRequestData(e))?;
+                let new_mutable_data: Vec<u8> = scrypto_decode(&args[2].raw)
+                    .map_err(|e| ResourceManagerError::InvalidRequestData(e))?;
+
+                let non_fungible_address =
+                    NonFungibleAddress::new(resource_address.clone(), non_fungible_id);
+                let data = Self::process_non_fungible_data(&new_mutable_data)?;
+                let mut non_fungible = system_api
+                    .get_non_fungible(&non_fungible_address)
+                    .map_err(|_| {
+                        ResourceManagerError::UnknownNonFungible(non_fungible_address.clone())
+                    })?
+                    .cloned()
+                    .ok_or(ResourceManagerError::NonFungibleNotFound(
+                        non_fungible_address.clone(),
+                    ))?;
+                if non_fungible.version() != expected_version {
+                    return Err(ResourceManagerError::VersionConflict(
+                        non_fungible_address,
+                    ));
+                }
+                non_fungible.set_mutable_data(data.raw);
+                system_api
+                    .set_non_fungible(non_fungible_address.clone(), Some(non_fungible))
+                    .map_err(|_| ResourceManagerError::NonFungibleReadOnly(non_fungible_address))?;
 
                 Ok(ScryptoValue::from_value(&()))
             }
@@ -462,22 +629,89 @@ impl ResourceManager {
                     .map_err(|e| ResourceManagerError::InvalidRequestData(e))?;
                 let non_fungible_address =
                     NonFungibleAddress::new(resource_address.clone(), non_fungible_id);
-                let non_fungible = system_api.get_non_fungible(&non_fungible_address);
+                let non_fungible = system_api
+                    .get_non_fungible(&non_fungible_address)
+                    .map_err(|_| {
+                        ResourceManagerError::UnknownNonFungible(non_fungible_address.clone())
+                    })?;
                 Ok(ScryptoValue::from_value(&non_fungible.is_some()))
             }
+            "burn_non_fungibles" => {
+                let bucket: scrypto::resource::Bucket = scrypto_decode(&args[0].raw)
+                    .map_err(|e| ResourceManagerError::InvalidRequestData(e))?;
+                let ids: BTreeSet<NonFungibleId> = scrypto_decode(&args[1].raw)
+                    .map_err(|e| ResourceManagerError::InvalidRequestData(e))?;
+                let bucket = system_api
+                    .take_bucket(bucket.0)
+                    .map_err(|_| ResourceManagerError::CouldNotTakeBucket)?;
+                if bucket.resource_address() != resource_address {
+                    return Err(ResourceManagerError::ResourceTypeDoesNotMatch);
+                }
+
+                // Only ids held by the bucket are burnt, so no vault or bucket is left
+                // holding a non-fungible which no longer exists
+                let mut container = bucket
+                    .into_container()
+                    .map_err(ResourceManagerError::ResourceContainerError)?;
+                let burnt = container
+                    .take_by_ids(&ids)
+                    .map_err(ResourceManagerError::ResourceContainerError)?;
+
+                let amount = burnt.total_amount();
+                self.burn(amount);
+                for id in ids {
+                    let non_fungible_address = NonFungibleAddress::new(resource_address, id);
+                    system_api
+                        .set_non_fungible(non_fungible_address.clone(), None)
+                        .map_err(|_| {
+                            ResourceManagerError::NonFungibleReadOnly(non_fungible_address)
+                        })?;
+                }
+                system_api.record_resource_movement(ResourceMovement::Burn {
+                    resource_address,
+                    amount,
+                });
+
+                let bucket_id = system_api
+                    .create_bucket(container)
+                    .map_err(|_| ResourceManagerError::CouldNotCreateBucket)?;
+                Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(
+                    bucket_id,
+                )))
+            }
             "get_non_fungible" => {
                 let non_fungible_id: NonFungibleId = scrypto_decode(&args[0].raw)
                     .map_err(|e| ResourceManagerError::InvalidRequestData(e))?;
                 let non_fungible_address =
                     NonFungibleAddress::new(resource_address.clone(), non_fungible_id);
-                let non_fungible = system_api.get_non_fungible(&non_fungible_address).ok_or(
-                    ResourceManagerError::NonFungibleNotFound(non_fungible_address),
-                )?;
+                let non_fungible = system_api
+                    .get_non_fungible(&non_fungible_address)
+                    .map_err(|_| {
+                        ResourceManagerError::UnknownNonFungible(non_fungible_address.clone())
+                    })?
+                    .ok_or(ResourceManagerError::NonFungibleNotFound(
+                        non_fungible_address,
+                    ))?;
                 Ok(ScryptoValue::from_value(&[
                     non_fungible.immutable_data(),
                     non_fungible.mutable_data(),
                 ]))
             }
+            "get_non_fungible_version" => {
+                let non_fungible_id: NonFungibleId = scrypto_decode(&args[0].raw)
+                    .map_err(|e| ResourceManagerError::InvalidRequestData(e))?;
+                let non_fungible_address =
+                    NonFungibleAddress::new(resource_address.clone(), non_fungible_id);
+                let non_fungible = system_api
+                    .get_non_fungible(&non_fungible_address)
+                    .map_err(|_| {
+                        ResourceManagerError::UnknownNonFungible(non_fungible_address.clone())
+                    })?
+                    .ok_or(ResourceManagerError::NonFungibleNotFound(
+                        non_fungible_address,
+                    ))?;
+                Ok(ScryptoValue::from_value(&non_fungible.version()))
+            }
             _ => Err(ResourceManagerError::MethodNotFound(function.to_string())),
         }
     }
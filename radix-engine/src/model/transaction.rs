@@ -1,9 +1,10 @@
 use sbor::*;
-use scrypto::buffer::scrypto_encode;
+use scrypto::buffer::{scrypto_decode, scrypto_encode};
 use scrypto::crypto::*;
 use scrypto::engine::types::*;
 use scrypto::rust::collections::BTreeSet;
 use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
 use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
 use scrypto::values::*;
@@ -72,6 +73,9 @@ pub enum Instruction {
     /// Drops all proofs in the auth zone
     ClearAuthZone,
 
+    /// Asserts the auth zone contains no proofs.
+    AssertAuthZoneEmpty,
+
     // TODO: do we need `CreateProofFromWorktop`, to avoid taking resource out and then creating proof?
     /// Creates a proof from the auth zone
     CreateProofFromAuthZone { resource_address: ResourceAddress },
@@ -88,6 +92,12 @@ pub enum Instruction {
         resource_address: ResourceAddress,
     },
 
+    /// Creates a proof from the worktop, by the given amount.
+    CreateProofFromWorktopByAmount {
+        amount: Decimal,
+        resource_address: ResourceAddress,
+    },
+
     /// Creates a proof from a bucket.
     CreateProofFromBucket { bucket_id: BucketId },
 
@@ -125,13 +135,83 @@ pub enum Instruction {
     /// Publishes a package.
     PublishPackage { code: Vec<u8> },
 
+    /// Aborts the transaction with the given reason.
+    Abort { reason: String },
+
+    /// Executes one of two instruction lists, depending on whether the condition holds.
+    ///
+    /// Branches may not create, consume or pass buckets and proofs, nor nest other branches.
+    /// Records a single output: a tuple of whether the condition held and the outputs of the
+    /// executed instructions.
+    ConditionalBranch {
+        condition: Condition,
+        then_instructions: Vec<Instruction>,
+        else_instructions: Vec<Instruction>,
+    },
+
     /// Specifies transaction nonce
     Nonce {
         nonce: u64, // TODO: may be replaced with substate id for entropy
     },
 }
 
+/// A condition which a `ConditionalBranch` instruction evaluates.
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
+pub enum Condition {
+    /// The worktop contains some of the resource.
+    WorktopContains { resource_address: ResourceAddress },
+
+    /// The worktop contains at least the given amount of the resource.
+    WorktopContainsByAmount {
+        amount: Decimal,
+        resource_address: ResourceAddress,
+    },
+}
+
 impl Transaction {
+    /// Decodes a transaction, rejecting instructions which this engine doesn't support.
+    ///
+    /// Instructions are encoded by name, so one added by a later protocol version is reported
+    /// by name instead of being mistaken for another.
+    pub fn from_slice(slice: &[u8]) -> Result<Self, TransactionValidationError> {
+        scrypto_decode(slice).map_err(|e| match e {
+            DecodeError::InvalidEnum(name) if Self::has_unknown_instruction(slice, &name) => {
+                TransactionValidationError::UnsupportedInstruction(name)
+            }
+            e => TransactionValidationError::DecodeError(e),
+        })
+    }
+
+    /// Returns whether an encoded transaction has a top-level instruction with the given name,
+    /// which is not a variant of `Instruction`.
+    fn has_unknown_instruction(slice: &[u8], name: &str) -> bool {
+        let is_top_level = match decode_any(slice) {
+            Ok(Value::Struct { fields }) => match fields.first() {
+                Some(Value::Vec { elements, .. }) => elements
+                    .iter()
+                    .any(|e| matches!(e, Value::Enum { name: n, .. } if n == name)),
+                _ => false,
+            },
+            _ => false,
+        };
+
+        // Only the instruction name is decoded, so an invalid field can't be mistaken for it
+        let mut discriminator = Vec::new();
+        encode_any(
+            None,
+            &Value::Enum {
+                name: name.to_string(),
+                fields: Vec::new(),
+            },
+            &mut Encoder::with_type(&mut discriminator),
+        );
+        is_top_level
+            && matches!(
+                scrypto_decode::<Instruction>(&discriminator),
+                Err(DecodeError::InvalidEnum(_))
+            )
+    }
+
     pub fn to_vec(&self) -> Vec<u8> {
         scrypto_encode(self)
     }
@@ -158,25 +238,15 @@ impl Transaction {
             signatures: signatures,
         }
     }
-}
 
-impl SignedTransaction {
-    pub fn validate(&self) -> Result<ValidatedTransaction, TransactionValidationError> {
+    /// Validates the instructions of this transaction without executing them, checking that
+    /// every bucket and proof is created before it's used and that call arguments are well-formed.
+    pub fn validate_manifest(
+        &self,
+    ) -> Result<Vec<ValidatedInstruction>, TransactionValidationError> {
         let mut instructions = vec![];
-        let mut signers = vec![];
-
-        // verify signature (may defer to runtime)
-        let msg = self.transaction.to_vec();
-        for (pk, sig) in &self.signatures {
-            if !EcdsaVerifier::verify(&msg, pk, sig) {
-                return Err(TransactionValidationError::InvalidSignature);
-            }
-            signers.push(pk.clone());
-        }
-
-        // semantic analysis
         let mut id_validator = IdValidator::new();
-        for inst in &self.transaction.instructions {
+        for inst in &self.instructions {
             match inst.clone() {
                 Instruction::TakeFromWorktop { resource_address } => {
                     id_validator
@@ -251,6 +321,9 @@ impl SignedTransaction {
                 Instruction::ClearAuthZone => {
                     instructions.push(ValidatedInstruction::ClearAuthZone);
                 }
+                Instruction::AssertAuthZoneEmpty => {
+                    instructions.push(ValidatedInstruction::AssertAuthZoneEmpty);
+                }
                 Instruction::CreateProofFromAuthZone { resource_address } => {
                     id_validator
                         .new_proof(ProofKind::AuthZoneProof)
@@ -270,6 +343,18 @@ impl SignedTransaction {
                         resource_address,
                     });
                 }
+                Instruction::CreateProofFromWorktopByAmount {
+                    amount,
+                    resource_address,
+                } => {
+                    id_validator
+                        .new_proof(ProofKind::WorktopProof)
+                        .map_err(TransactionValidationError::IdValidatorError)?;
+                    instructions.push(ValidatedInstruction::CreateProofFromWorktopByAmount {
+                        amount,
+                        resource_address,
+                    });
+                }
                 Instruction::CreateProofFromAuthZoneByIds {
                     ids,
                     resource_address,
@@ -339,17 +424,123 @@ impl SignedTransaction {
                 Instruction::PublishPackage { code } => {
                     instructions.push(ValidatedInstruction::PublishPackage { code });
                 }
+                Instruction::Abort { reason } => {
+                    instructions.push(ValidatedInstruction::Abort { reason });
+                }
+                Instruction::ConditionalBranch {
+                    condition,
+                    then_instructions,
+                    else_instructions,
+                } => {
+                    instructions.push(ValidatedInstruction::ConditionalBranch {
+                        condition,
+                        then_instructions: Self::validate_branch(
+                            then_instructions,
+                            &mut id_validator,
+                        )?,
+                        else_instructions: Self::validate_branch(
+                            else_instructions,
+                            &mut id_validator,
+                        )?,
+                    });
+                }
                 Instruction::Nonce { .. } => {
                     // TODO: validate nonce
                 }
             }
         }
 
-        Ok(ValidatedTransaction {
-            raw_hash: self.transaction.raw_hash(),
-            instructions,
-            signers,
-        })
+        Ok(instructions)
+    }
+
+    fn validate_branch(
+        branch: Vec<Instruction>,
+        id_validator: &mut IdValidator,
+    ) -> Result<Vec<ValidatedInstruction>, TransactionValidationError> {
+        let mut instructions = vec![];
+        for inst in branch {
+            let validated = match inst.clone() {
+                Instruction::AssertWorktopContains { resource_address } => {
+                    ValidatedInstruction::AssertWorktopContains { resource_address }
+                }
+                Instruction::AssertWorktopContainsByAmount {
+                    amount,
+                    resource_address,
+                } => ValidatedInstruction::AssertWorktopContainsByAmount {
+                    amount,
+                    resource_address,
+                },
+                Instruction::AssertWorktopContainsByIds {
+                    ids,
+                    resource_address,
+                } => ValidatedInstruction::AssertWorktopContainsByIds {
+                    ids,
+                    resource_address,
+                },
+                Instruction::ClearAuthZone => ValidatedInstruction::ClearAuthZone,
+                Instruction::AssertAuthZoneEmpty => ValidatedInstruction::AssertAuthZoneEmpty,
+                Instruction::CallFunction {
+                    package_address,
+                    blueprint_name,
+                    function,
+                    args,
+                } => ValidatedInstruction::CallFunction {
+                    package_address,
+                    blueprint_name,
+                    function,
+                    args: Self::validate_branch_args(args, &inst)?,
+                },
+                Instruction::CallMethod {
+                    component_address,
+                    method,
+                    args,
+                } => ValidatedInstruction::CallMethod {
+                    component_address,
+                    method,
+                    args: Self::validate_branch_args(args, &inst)?,
+                },
+                Instruction::CallMethodWithAllResources {
+                    component_address,
+                    method,
+                } => {
+                    // Resources are moved on either path, so later instructions can't use them.
+                    id_validator
+                        .move_all_resources()
+                        .map_err(TransactionValidationError::IdValidatorError)?;
+                    ValidatedInstruction::CallMethodWithAllResources {
+                        component_address,
+                        method,
+                    }
+                }
+                Instruction::PublishPackage { code } => {
+                    ValidatedInstruction::PublishPackage { code }
+                }
+                Instruction::Abort { reason } => ValidatedInstruction::Abort { reason },
+                _ => {
+                    return Err(TransactionValidationError::InstructionNotAllowedInBranch(
+                        inst,
+                    ));
+                }
+            };
+            instructions.push(validated);
+        }
+        Ok(instructions)
+    }
+
+    fn validate_branch_args(
+        args: Vec<Vec<u8>>,
+        inst: &Instruction,
+    ) -> Result<Vec<ScryptoValue>, TransactionValidationError> {
+        for arg in &args {
+            let value = ScryptoValue::from_slice(arg)
+                .map_err(TransactionValidationError::ParseScryptoValueError)?;
+            if !value.bucket_ids.is_empty() || !value.proof_ids.is_empty() {
+                return Err(TransactionValidationError::InstructionNotAllowedInBranch(
+                    inst.clone(),
+                ));
+            }
+        }
+        Self::validate_args(args, &mut IdValidator::new())
     }
 
     fn validate_args(
@@ -379,6 +570,30 @@ impl SignedTransaction {
     }
 }
 
+impl SignedTransaction {
+    pub fn validate(&self) -> Result<ValidatedTransaction, TransactionValidationError> {
+        let mut signers = vec![];
+
+        // verify signature (may defer to runtime)
+        let msg = self.transaction.to_vec();
+        for (pk, sig) in &self.signatures {
+            if !EcdsaVerifier::verify(&msg, pk, sig) {
+                return Err(TransactionValidationError::InvalidSignature);
+            }
+            signers.push(pk.clone());
+        }
+
+        // semantic analysis
+        let instructions = self.transaction.validate_manifest()?;
+
+        Ok(ValidatedTransaction {
+            raw_hash: self.transaction.raw_hash(),
+            instructions,
+            signers,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
@@ -435,4 +650,110 @@ mod tests {
             ))),
         );
     }
+
+    #[test]
+    fn should_reject_manifest_using_undefined_bucket() {
+        assert_eq!(
+            Transaction {
+                instructions: vec![Instruction::ReturnToWorktop { bucket_id: 1024 }],
+            }
+            .validate_manifest(),
+            Err(TransactionValidationError::IdValidatorError(
+                IdValidatorError::BucketNotFound(1024)
+            )),
+        );
+    }
+
+    #[test]
+    fn should_reject_manifest_using_consumed_bucket() {
+        assert_eq!(
+            Transaction {
+                instructions: vec![
+                    Instruction::TakeFromWorktop {
+                        resource_address: ResourceAddress([1u8; 26]),
+                    },
+                    Instruction::ReturnToWorktop { bucket_id: 512 },
+                    Instruction::ReturnToWorktop { bucket_id: 512 },
+                ],
+            }
+            .validate_manifest(),
+            Err(TransactionValidationError::IdValidatorError(
+                IdValidatorError::BucketAlreadyConsumed(512)
+            )),
+        );
+    }
+
+    #[derive(TypeId, Encode)]
+    enum FutureInstruction {
+        ClearAuthZone,
+        Teleport {
+            destination: String,
+        },
+        ConditionalBranch {
+            condition: FutureCondition,
+            then_instructions: Vec<FutureInstruction>,
+            else_instructions: Vec<FutureInstruction>,
+        },
+    }
+
+    #[derive(TypeId, Encode)]
+    enum FutureCondition {
+        WorktopIsEmpty,
+    }
+
+    #[derive(TypeId, Encode)]
+    struct FutureTransaction {
+        instructions: Vec<FutureInstruction>,
+    }
+
+    #[test]
+    fn should_decode_supported_instructions() {
+        let encoded = scrypto_encode(&FutureTransaction {
+            instructions: vec![FutureInstruction::ClearAuthZone],
+        });
+
+        assert_eq!(
+            Transaction::from_slice(&encoded),
+            Ok(Transaction {
+                instructions: vec![Instruction::ClearAuthZone],
+            })
+        );
+    }
+
+    #[test]
+    fn should_reject_unsupported_instruction() {
+        let encoded = scrypto_encode(&FutureTransaction {
+            instructions: vec![
+                FutureInstruction::ClearAuthZone,
+                FutureInstruction::Teleport {
+                    destination: "moon".to_owned(),
+                },
+            ],
+        });
+
+        assert_eq!(
+            Transaction::from_slice(&encoded),
+            Err(TransactionValidationError::UnsupportedInstruction(
+                "Teleport".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn should_not_report_unknown_nested_enum_as_unsupported_instruction() {
+        let encoded = scrypto_encode(&FutureTransaction {
+            instructions: vec![FutureInstruction::ConditionalBranch {
+                condition: FutureCondition::WorktopIsEmpty,
+                then_instructions: vec![],
+                else_instructions: vec![],
+            }],
+        });
+
+        assert_eq!(
+            Transaction::from_slice(&encoded),
+            Err(TransactionValidationError::DecodeError(
+                DecodeError::InvalidEnum("WorktopIsEmpty".to_owned())
+            ))
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use sbor::{encode_any, Encoder, Value};
+use scrypto::buffer::scrypto_decode;
 use scrypto::core::SNodeRef;
 use scrypto::engine::types::*;
 use scrypto::prelude::ScryptoActor;
@@ -9,7 +11,7 @@ use scrypto::values::*;
 use crate::engine::{IdAllocator, IdSpace, SystemApi};
 use crate::errors::RuntimeError::{ProofNotFound};
 use crate::errors::RuntimeError;
-use crate::model::{ValidatedInstruction, ValidatedTransaction};
+use crate::model::{Condition, ValidatedInstruction, ValidatedTransaction};
 
 pub struct TransactionProcess {
     transaction: ValidatedTransaction,
@@ -49,358 +51,457 @@ impl TransactionProcess {
     }
 
     pub fn main<S: SystemApi>(&mut self, system_api: &mut S) -> Result<ScryptoValue, RuntimeError> {
-        for inst in &self.transaction.instructions.clone() {
-            let result = match inst {
-                ValidatedInstruction::TakeFromWorktop { resource_address } => {
-                    self.id_allocator.new_bucket_id()
-                        .map_err(RuntimeError::IdAllocatorError)
-                        .and_then(|new_id| {
-                            system_api.invoke_snode(
-                                SNodeRef::WorktopRef,
-                                "take_all".to_string(),
-                                vec![
-                                    ScryptoValue::from_value(resource_address),
-                                ]
-                            ).map(|rtn| {
-                                let bucket_id = *rtn.bucket_ids.iter().next().unwrap().0;
-                                self.bucket_id_mapping.insert(new_id, bucket_id);
-                                ScryptoValue::from_value(&scrypto::resource::Bucket(new_id))
-                            })
+        for (index, inst) in self.transaction.instructions.clone().iter().enumerate() {
+            system_api.set_current_instruction_index(Some(index));
+            let result = self.execute_instruction(inst, system_api)?;
+            self.outputs.push(result);
+        }
+        system_api.set_current_instruction_index(None);
+
+        Ok(ScryptoValue::from_value(&()))
+    }
+
+    fn evaluate_condition<S: SystemApi>(
+        &mut self,
+        condition: &Condition,
+        system_api: &mut S,
+    ) -> Result<bool, RuntimeError> {
+        let (amount, resource_address) = match condition {
+            Condition::WorktopContains { resource_address } => (None, resource_address),
+            Condition::WorktopContainsByAmount {
+                amount,
+                resource_address,
+            } => (Some(amount), resource_address),
+        };
+        let rtn = system_api.invoke_snode(
+            SNodeRef::WorktopRef,
+            "get_amount".to_string(),
+            vec![ScryptoValue::from_value(resource_address)],
+        )?;
+        let total: Decimal = scrypto_decode(&rtn.raw).map_err(|e| {
+            RuntimeError::ParseScryptoValueError(ParseScryptoValueError::DecodeError(e))
+        })?;
+        Ok(match amount {
+            Some(amount) => total >= *amount,
+            None => !total.is_zero(),
+        })
+    }
+
+    fn execute_instruction<S: SystemApi>(
+        &mut self,
+        inst: &ValidatedInstruction,
+        system_api: &mut S,
+    ) -> Result<ScryptoValue, RuntimeError> {
+        match inst {
+            ValidatedInstruction::TakeFromWorktop { resource_address } => {
+                self.id_allocator.new_bucket_id()
+                    .map_err(RuntimeError::IdAllocatorError)
+                    .and_then(|new_id| {
+                        system_api.invoke_snode(
+                            SNodeRef::WorktopRef,
+                            "take_all".to_string(),
+                            vec![
+                                ScryptoValue::from_value(resource_address),
+                            ]
+                        ).map(|rtn| {
+                            let bucket_id = *rtn.bucket_ids.iter().next().unwrap().0;
+                            self.bucket_id_mapping.insert(new_id, bucket_id);
+                            ScryptoValue::from_value(&scrypto::resource::Bucket(new_id))
                         })
-                },
-                ValidatedInstruction::TakeFromWorktopByAmount {
-                    amount,
-                    resource_address,
-                } =>
-                    self.id_allocator
-                        .new_bucket_id()
-                        .map_err(RuntimeError::IdAllocatorError)
-                        .and_then(|new_id| {
-                            system_api.invoke_snode(
-                                SNodeRef::WorktopRef,
-                                "take_amount".to_string(),
-                                vec![
-                                    ScryptoValue::from_value(amount),
-                                    ScryptoValue::from_value(resource_address),
-                                ]
-                            ).map(|rtn| {
-                                let bucket_id = *rtn.bucket_ids.iter().next().unwrap().0;
-                                self.bucket_id_mapping.insert(new_id, bucket_id);
-                                ScryptoValue::from_value(&scrypto::resource::Bucket(new_id))
-                            })
-                        }),
-                ValidatedInstruction::TakeFromWorktopByIds {
-                    ids,
-                    resource_address,
-                } =>
-                    self.id_allocator
-                        .new_bucket_id()
-                        .map_err(RuntimeError::IdAllocatorError)
-                        .and_then(|new_id| {
-                            system_api.invoke_snode(
-                                SNodeRef::WorktopRef,
-                                "take_non_fungibles".to_string(),
-                                vec![
-                                    ScryptoValue::from_value(ids),
-                                    ScryptoValue::from_value(resource_address),
-                                ]
-                            ).map(|rtn| {
-                                let bucket_id = *rtn.bucket_ids.iter().next().unwrap().0;
-                                self.bucket_id_mapping.insert(new_id, bucket_id);
-                                ScryptoValue::from_value(&scrypto::resource::Bucket(new_id))
-                            })
-                        }),
-                ValidatedInstruction::ReturnToWorktop { bucket_id } => {
-                    self.bucket_id_mapping.remove(bucket_id)
-                        .map(|real_id| {
-                            system_api.invoke_snode(
-                                SNodeRef::WorktopRef,
-                                "put".to_string(),
-                                vec![
-                                    ScryptoValue::from_value(&scrypto::resource::Bucket(real_id)),
-                                ]
-                            )
+                    })
+            },
+            ValidatedInstruction::TakeFromWorktopByAmount {
+                amount,
+                resource_address,
+            } =>
+                self.id_allocator
+                    .new_bucket_id()
+                    .map_err(RuntimeError::IdAllocatorError)
+                    .and_then(|new_id| {
+                        system_api.invoke_snode(
+                            SNodeRef::WorktopRef,
+                            "take_amount".to_string(),
+                            vec![
+                                ScryptoValue::from_value(amount),
+                                ScryptoValue::from_value(resource_address),
+                            ]
+                        ).map(|rtn| {
+                            let bucket_id = *rtn.bucket_ids.iter().next().unwrap().0;
+                            self.bucket_id_mapping.insert(new_id, bucket_id);
+                            ScryptoValue::from_value(&scrypto::resource::Bucket(new_id))
                         })
-                        .unwrap_or(Err(RuntimeError::BucketNotFound(*bucket_id)))
-                }
-                ValidatedInstruction::AssertWorktopContains { resource_address } => {
-                    system_api.invoke_snode(
-                        SNodeRef::WorktopRef,
-                        "assert_contains".to_string(),
-                        vec![
-                            ScryptoValue::from_value(resource_address),
-                        ]
-                    )
-                }
-                ValidatedInstruction::AssertWorktopContainsByAmount {
-                    amount,
-                    resource_address,
-                } => {
-                    system_api.invoke_snode(
-                        SNodeRef::WorktopRef,
-                        "assert_contains_amount".to_string(),
-                        vec![
-                            ScryptoValue::from_value(amount),
-                            ScryptoValue::from_value(resource_address),
-                        ]
-                    )
-                },
-                ValidatedInstruction::AssertWorktopContainsByIds {
-                    ids,
-                    resource_address,
-                } => {
-                    system_api.invoke_snode(
-                        SNodeRef::WorktopRef,
-                        "assert_contains_amount".to_string(),
-                        vec![
-                            ScryptoValue::from_value(ids),
-                            ScryptoValue::from_value(resource_address),
-                        ]
+                    }),
+            ValidatedInstruction::TakeFromWorktopByIds {
+                ids,
+                resource_address,
+            } =>
+                self.id_allocator
+                    .new_bucket_id()
+                    .map_err(RuntimeError::IdAllocatorError)
+                    .and_then(|new_id| {
+                        system_api.invoke_snode(
+                            SNodeRef::WorktopRef,
+                            "take_non_fungibles".to_string(),
+                            vec![
+                                ScryptoValue::from_value(ids),
+                                ScryptoValue::from_value(resource_address),
+                            ]
+                        ).map(|rtn| {
+                            let bucket_id = *rtn.bucket_ids.iter().next().unwrap().0;
+                            self.bucket_id_mapping.insert(new_id, bucket_id);
+                            ScryptoValue::from_value(&scrypto::resource::Bucket(new_id))
+                        })
+                    }),
+            ValidatedInstruction::ReturnToWorktop { bucket_id } => {
+                self.bucket_id_mapping.remove(bucket_id)
+                    .map(|real_id| {
+                        system_api.invoke_snode(
+                            SNodeRef::WorktopRef,
+                            "put".to_string(),
+                            vec![
+                                ScryptoValue::from_value(&scrypto::resource::Bucket(real_id)),
+                            ]
+                        )
+                    })
+                    .unwrap_or(Err(RuntimeError::BucketNotFound(*bucket_id)))
+            }
+            ValidatedInstruction::AssertWorktopContains { resource_address } => {
+                system_api.invoke_snode(
+                    SNodeRef::WorktopRef,
+                    "assert_contains".to_string(),
+                    vec![
+                        ScryptoValue::from_value(resource_address),
+                    ]
+                )
+            }
+            ValidatedInstruction::AssertWorktopContainsByAmount {
+                amount,
+                resource_address,
+            } => {
+                system_api.invoke_snode(
+                    SNodeRef::WorktopRef,
+                    "assert_contains_amount".to_string(),
+                    vec![
+                        ScryptoValue::from_value(amount),
+                        ScryptoValue::from_value(resource_address),
+                    ]
+                )
+            },
+            ValidatedInstruction::AssertWorktopContainsByIds {
+                ids,
+                resource_address,
+            } => {
+                system_api.invoke_snode(
+                    SNodeRef::WorktopRef,
+                    "assert_contains_amount".to_string(),
+                    vec![
+                        ScryptoValue::from_value(ids),
+                        ScryptoValue::from_value(resource_address),
+                    ]
+                )
+            },
+            ValidatedInstruction::PopFromAuthZone {} => {
+                self.id_allocator.new_proof_id()
+                    .map_err(RuntimeError::IdAllocatorError)
+                    .and_then(|new_id| {
+                        system_api.invoke_snode(
+                            SNodeRef::AuthZoneRef,
+                            "pop".to_string(),
+                            vec![]
+                        ).map(|rtn| {
+                            let proof_id = *rtn.proof_ids.iter().next().unwrap().0;
+                            self.proof_id_mapping.insert(new_id, proof_id);
+                            ScryptoValue::from_value(&scrypto::resource::Proof(new_id))
+                        })
+                    })
+            },
+            ValidatedInstruction::ClearAuthZone => {
+                self.proof_id_mapping.clear();
+                system_api.invoke_snode(SNodeRef::AuthZoneRef, "clear".to_string(), vec![])
+            },
+            ValidatedInstruction::AssertAuthZoneEmpty => {
+                system_api.invoke_snode(SNodeRef::AuthZoneRef, "assert_empty".to_string(), vec![])
+            },
+            ValidatedInstruction::PushToAuthZone { proof_id } => {
+                self.proof_id_mapping.remove(proof_id)
+                    .ok_or(RuntimeError::ProofNotFound(*proof_id))
+                    .and_then(|real_id|
+                        system_api.invoke_snode(
+                            SNodeRef::AuthZoneRef,
+                            "push".to_string(),
+                            vec![ScryptoValue::from_value(&scrypto::resource::Proof(real_id))]
+                        )
                     )
-                },
-                ValidatedInstruction::PopFromAuthZone {} => {
-                    self.id_allocator.new_proof_id()
-                        .map_err(RuntimeError::IdAllocatorError)
-                        .and_then(|new_id| {
-                            system_api.invoke_snode(
-                                SNodeRef::AuthZoneRef,
-                                "pop".to_string(),
-                                vec![]
-                            ).map(|rtn| {
-                                let proof_id = *rtn.proof_ids.iter().next().unwrap().0;
-                                self.proof_id_mapping.insert(new_id, proof_id);
-                                ScryptoValue::from_value(&scrypto::resource::Proof(new_id))
-                            })
+            },
+            ValidatedInstruction::CreateProofFromAuthZone { resource_address } =>
+                self.id_allocator.new_proof_id()
+                    .map_err(RuntimeError::IdAllocatorError)
+                    .and_then(|new_id| {
+                        system_api.invoke_snode(
+                            SNodeRef::AuthZoneRef,
+                            "create_proof".to_string(),
+                            vec![ScryptoValue::from_value(resource_address)]
+                        ).map(|rtn| {
+                            let proof_id = *rtn.proof_ids.iter().next().unwrap().0;
+                            self.proof_id_mapping.insert(new_id, proof_id);
+                            ScryptoValue::from_value(&scrypto::resource::Proof(new_id))
                         })
-                },
-                ValidatedInstruction::ClearAuthZone => {
-                    self.proof_id_mapping.clear();
-                    system_api.invoke_snode(SNodeRef::AuthZoneRef, "clear".to_string(), vec![])
-                },
-                ValidatedInstruction::PushToAuthZone { proof_id } => {
-                    self.proof_id_mapping.remove(proof_id)
-                        .ok_or(RuntimeError::ProofNotFound(*proof_id))
-                        .and_then(|real_id|
-                            system_api.invoke_snode(
-                                SNodeRef::AuthZoneRef,
-                                "push".to_string(),
-                                vec![ScryptoValue::from_value(&scrypto::resource::Proof(real_id))]
-                            )
+                    }),
+            ValidatedInstruction::CreateProofFromAuthZoneByAmount {
+                amount,
+                resource_address,
+            } =>
+                self.id_allocator.new_proof_id()
+                    .map_err(RuntimeError::IdAllocatorError)
+                    .and_then(|new_id| {
+                        system_api.invoke_snode(
+                            SNodeRef::AuthZoneRef,
+                            "create_proof_by_amount".to_string(),
+                            vec![
+                                ScryptoValue::from_value(amount),
+                                ScryptoValue::from_value(resource_address)
+                            ]
+                        ).map(|rtn| {
+                            let proof_id = *rtn.proof_ids.iter().next().unwrap().0;
+                            self.proof_id_mapping.insert(new_id, proof_id);
+                            ScryptoValue::from_value(&scrypto::resource::Proof(new_id))
+                        })
+                    }),
+            ValidatedInstruction::CreateProofFromWorktopByAmount {
+                amount,
+                resource_address,
+            } =>
+                self.id_allocator.new_proof_id()
+                    .map_err(RuntimeError::IdAllocatorError)
+                    .and_then(|new_id| {
+                        system_api.invoke_snode(
+                            SNodeRef::WorktopRef,
+                            "create_proof_by_amount".to_string(),
+                            vec![
+                                ScryptoValue::from_value(amount),
+                                ScryptoValue::from_value(resource_address)
+                            ]
+                        ).map(|rtn| {
+                            let proof_id = *rtn.proof_ids.iter().next().unwrap().0;
+                            self.proof_id_mapping.insert(new_id, proof_id);
+                            ScryptoValue::from_value(&scrypto::resource::Proof(new_id))
+                        })
+                    }),
+            ValidatedInstruction::CreateProofFromAuthZoneByIds {
+                ids,
+                resource_address,
+            } =>
+                self.id_allocator.new_proof_id()
+                    .map_err(RuntimeError::IdAllocatorError)
+                    .and_then(|new_id| {
+                        system_api.invoke_snode(
+                            SNodeRef::AuthZoneRef,
+                            "create_proof_by_ids".to_string(),
+                            vec![
+                                ScryptoValue::from_value(ids),
+                                ScryptoValue::from_value(resource_address)
+                            ]
+                        ).map(|rtn| {
+                            let proof_id = *rtn.proof_ids.iter().next().unwrap().0;
+                            self.proof_id_mapping.insert(new_id, proof_id);
+                            ScryptoValue::from_value(&scrypto::resource::Proof(new_id))
+                        })
+                    }),
+            ValidatedInstruction::CreateProofFromBucket { bucket_id } => {
+                self.id_allocator.new_proof_id()
+                    .map_err(RuntimeError::IdAllocatorError)
+                    .and_then(|new_id| {
+                        self.bucket_id_mapping.get(bucket_id).cloned()
+                            .map(|real_bucket_id| (new_id, real_bucket_id))
+                            .ok_or(RuntimeError::BucketNotFound(new_id))
+                    })
+                    .and_then(|(new_id, real_bucket_id)| {
+                        system_api.invoke_snode(
+                            SNodeRef::BucketRef(real_bucket_id),
+                            "create_bucket_proof".to_string(),
+                            vec![],
+                        ).map(|rtn| {
+                            let proof_id = *rtn.proof_ids.iter().next().unwrap().0;
+                            self.proof_id_mapping.insert(new_id, proof_id);
+                            ScryptoValue::from_value(&scrypto::resource::Proof(new_id))
+                        })
+                    })
+            },
+            ValidatedInstruction::CloneProof { proof_id } =>
+                self.id_allocator
+                    .new_proof_id()
+                    .map_err(RuntimeError::IdAllocatorError)
+                    .and_then(|new_id| {
+                        self.proof_id_mapping
+                            .get(proof_id)
+                            .cloned()
+                            .map(|real_id| {
+                                system_api.invoke_snode(SNodeRef::ProofRef(real_id),
+                                                        "clone".to_string(),
+                                                        vec![]
+                                ).map(|v| {
+                                    let cloned_proof_id = v.proof_ids.iter().next().unwrap().0;
+                                    self.proof_id_mapping.insert(new_id, *cloned_proof_id);
+                                    ScryptoValue::from_value(&scrypto::resource::Proof(new_id))
+                                })
+                            })
+                            .unwrap_or(Err(RuntimeError::ProofNotFound(*proof_id)))
+                    }),
+            ValidatedInstruction::DropProof { proof_id } => {
+                self.proof_id_mapping.remove(proof_id)
+                    .map(|real_id| {
+                        system_api.invoke_snode(
+                            SNodeRef::Proof(real_id),
+                            "drop".to_string(),
+                            vec![]
                         )
-                },
-                ValidatedInstruction::CreateProofFromAuthZone { resource_address } =>
-                    self.id_allocator.new_proof_id()
-                        .map_err(RuntimeError::IdAllocatorError)
-                        .and_then(|new_id| {
+                    })
+                    .unwrap_or(Err(ProofNotFound(*proof_id)))
+            },
+            ValidatedInstruction::CallFunction {
+                package_address,
+                blueprint_name,
+                function,
+                args,
+            } => {
+                self.replace_ids(args.clone())
+                    .and_then(|args|
+                        system_api.invoke_snode(
+                            SNodeRef::Scrypto(ScryptoActor::Blueprint(*package_address, blueprint_name.to_string())),
+                            function.to_string(),
+                            args
+                        )
+                    )
+                    .and_then(|result| {
+                        // Auto move into auth_zone
+                        for (proof_id, _) in &result.proof_ids {
                             system_api.invoke_snode(
                                 SNodeRef::AuthZoneRef,
-                                "create_proof".to_string(),
-                                vec![ScryptoValue::from_value(resource_address)]
-                            ).map(|rtn| {
-                                let proof_id = *rtn.proof_ids.iter().next().unwrap().0;
-                                self.proof_id_mapping.insert(new_id, proof_id);
-                                ScryptoValue::from_value(&scrypto::resource::Proof(new_id))
-                            })
-                        }),
-                ValidatedInstruction::CreateProofFromAuthZoneByAmount {
-                    amount,
-                    resource_address,
-                } =>
-                    self.id_allocator.new_proof_id()
-                        .map_err(RuntimeError::IdAllocatorError)
-                        .and_then(|new_id| {
+                                "push".to_string(),
+                                vec![ScryptoValue::from_value(&scrypto::resource::Proof(*proof_id))]
+                            )?;
+                        }
+                        // Auto move into worktop
+                        if !result.bucket_ids.is_empty() {
+                            let buckets: Vec<scrypto::resource::Bucket> = result
+                                .bucket_ids
+                                .keys()
+                                .map(|bucket_id| scrypto::resource::Bucket(*bucket_id))
+                                .collect();
                             system_api.invoke_snode(
-                                SNodeRef::AuthZoneRef,
-                                "create_proof_by_amount".to_string(),
-                                vec![
-                                    ScryptoValue::from_value(amount),
-                                    ScryptoValue::from_value(resource_address)
-                                ]
-                            ).map(|rtn| {
-                                let proof_id = *rtn.proof_ids.iter().next().unwrap().0;
-                                self.proof_id_mapping.insert(new_id, proof_id);
-                                ScryptoValue::from_value(&scrypto::resource::Proof(new_id))
-                            })
-                        }),
-                ValidatedInstruction::CreateProofFromAuthZoneByIds {
-                    ids,
-                    resource_address,
-                } =>
-                    self.id_allocator.new_proof_id()
-                        .map_err(RuntimeError::IdAllocatorError)
-                        .and_then(|new_id| {
+                                SNodeRef::WorktopRef,
+                                "put_batch".to_string(),
+                                vec![ScryptoValue::from_value(&buckets)]
+                            )?;
+                        }
+                        Ok(result)
+                    })
+            },
+            ValidatedInstruction::CallMethod {
+                component_address,
+                method,
+                args,
+            } => {
+                self.replace_ids(args.clone())
+                    .and_then(|args|
+                        system_api.invoke_snode(
+                            SNodeRef::Scrypto(ScryptoActor::Component(*component_address)),
+                            method.to_string(),
+                            args
+                        )
+                    )
+                    .and_then(|result| {
+                        // Auto move into auth_zone
+                        for (proof_id, _) in &result.proof_ids {
                             system_api.invoke_snode(
                                 SNodeRef::AuthZoneRef,
-                                "create_proof_by_ids".to_string(),
-                                vec![
-                                    ScryptoValue::from_value(ids),
-                                    ScryptoValue::from_value(resource_address)
-                                ]
-                            ).map(|rtn| {
-                                let proof_id = *rtn.proof_ids.iter().next().unwrap().0;
-                                self.proof_id_mapping.insert(new_id, proof_id);
-                                ScryptoValue::from_value(&scrypto::resource::Proof(new_id))
-                            })
-                        }),
-                ValidatedInstruction::CreateProofFromBucket { bucket_id } => {
-                    self.id_allocator.new_proof_id()
-                        .map_err(RuntimeError::IdAllocatorError)
-                        .and_then(|new_id| {
-                            self.bucket_id_mapping.get(bucket_id).cloned()
-                                .map(|real_bucket_id| (new_id, real_bucket_id))
-                                .ok_or(RuntimeError::BucketNotFound(new_id))
-                        })
-                        .and_then(|(new_id, real_bucket_id)| {
+                                "push".to_string(),
+                                vec![ScryptoValue::from_value(&scrypto::resource::Proof(*proof_id))]
+                            )?;
+                        }
+                        // Auto move into worktop
+                        if !result.bucket_ids.is_empty() {
+                            let buckets: Vec<scrypto::resource::Bucket> = result
+                                .bucket_ids
+                                .keys()
+                                .map(|bucket_id| scrypto::resource::Bucket(*bucket_id))
+                                .collect();
                             system_api.invoke_snode(
-                                SNodeRef::BucketRef(real_bucket_id),
-                                "create_bucket_proof".to_string(),
-                                vec![],
-                            ).map(|rtn| {
-                                let proof_id = *rtn.proof_ids.iter().next().unwrap().0;
-                                self.proof_id_mapping.insert(new_id, proof_id);
-                                ScryptoValue::from_value(&scrypto::resource::Proof(new_id))
-                            })
-                        })
-                },
-                ValidatedInstruction::CloneProof { proof_id } =>
-                    self.id_allocator
-                        .new_proof_id()
-                        .map_err(RuntimeError::IdAllocatorError)
-                        .and_then(|new_id| {
-                            self.proof_id_mapping
-                                .get(proof_id)
-                                .cloned()
-                                .map(|real_id| {
-                                    system_api.invoke_snode(SNodeRef::ProofRef(real_id),
-                                                            "clone".to_string(),
-                                                            vec![]
-                                    ).map(|v| {
-                                        let cloned_proof_id = v.proof_ids.iter().next().unwrap().0;
-                                        self.proof_id_mapping.insert(new_id, *cloned_proof_id);
-                                        ScryptoValue::from_value(&scrypto::resource::Proof(new_id))
-                                    })
-                                })
-                                .unwrap_or(Err(RuntimeError::ProofNotFound(*proof_id)))
-                        }),
-                ValidatedInstruction::DropProof { proof_id } => {
-                    self.proof_id_mapping.remove(proof_id)
-                        .map(|real_id| {
+                                SNodeRef::WorktopRef,
+                                "put_batch".to_string(),
+                                vec![ScryptoValue::from_value(&buckets)]
+                            )?;
+                        }
+                        Ok(result)
+                    })
+            },
+            ValidatedInstruction::CallMethodWithAllResources {
+                component_address,
+                method,
+            } => {
+                system_api.invoke_snode(SNodeRef::AuthZoneRef, "clear".to_string(), vec![])
+                    .and_then(|_| {
+                        for (_, real_id) in self.proof_id_mapping.drain() {
                             system_api.invoke_snode(
                                 SNodeRef::Proof(real_id),
                                 "drop".to_string(),
                                 vec![]
-                            )
-                        })
-                        .unwrap_or(Err(ProofNotFound(*proof_id)))
-                },
-                ValidatedInstruction::CallFunction {
-                    package_address,
-                    blueprint_name,
-                    function,
-                    args,
-                } => {
-                    self.replace_ids(args.clone())
-                        .and_then(|args|
-                            system_api.invoke_snode(
-                                SNodeRef::Scrypto(ScryptoActor::Blueprint(*package_address, blueprint_name.to_string())),
-                                function.to_string(),
-                                args
-                            )
+                            ).unwrap();
+                        }
+                        system_api.invoke_snode(SNodeRef::WorktopRef, "drain".to_string(), vec![])
+                    })
+                    .and_then(|result| {
+                        let mut buckets = Vec::new();
+                        for (bucket_id, _) in result.bucket_ids {
+                            buckets.push(scrypto::resource::Bucket(bucket_id));
+                        }
+                        for (_, real_id) in self.bucket_id_mapping.drain() {
+                            buckets.push(scrypto::resource::Bucket(real_id));
+                        }
+                        system_api.invoke_snode(
+                            SNodeRef::Scrypto(ScryptoActor::Component(*component_address)),
+                            method.to_string(),
+                            vec![ScryptoValue::from_value(&buckets)],
                         )
-                        .and_then(|result| {
-                            // Auto move into auth_zone
-                            for (proof_id, _) in &result.proof_ids {
-                                system_api.invoke_snode(
-                                    SNodeRef::AuthZoneRef,
-                                    "push".to_string(),
-                                    vec![ScryptoValue::from_value(&scrypto::resource::Proof(*proof_id))]
-                                ).unwrap(); // TODO: Remove unwrap
-                            }
-                            // Auto move into worktop
-                            for (bucket_id, _) in &result.bucket_ids {
-                                system_api.invoke_snode(
-                                    SNodeRef::WorktopRef,
-                                    "put".to_string(),
-                                    vec![ScryptoValue::from_value(&scrypto::resource::Bucket(*bucket_id))]
-                                ).unwrap(); // TODO: Remove unwrap
-                            }
-                            Ok(result)
-                        })
-                },
-                ValidatedInstruction::CallMethod {
-                    component_address,
-                    method,
-                    args,
-                } => {
-                    self.replace_ids(args.clone())
-                        .and_then(|args|
-                            system_api.invoke_snode(
-                                SNodeRef::Scrypto(ScryptoActor::Component(*component_address)),
-                                method.to_string(),
-                                args
-                            )
-                        )
-                        .and_then(|result| {
-                            // Auto move into auth_zone
-                            for (proof_id, _) in &result.proof_ids {
-                                system_api.invoke_snode(
-                                    SNodeRef::AuthZoneRef,
-                                    "push".to_string(),
-                                    vec![ScryptoValue::from_value(&scrypto::resource::Proof(*proof_id))]
-                                ).unwrap();
-                            }
-                            // Auto move into worktop
-                            for (bucket_id, _) in &result.bucket_ids {
-                                system_api.invoke_snode(
-                                    SNodeRef::WorktopRef,
-                                    "put".to_string(),
-                                    vec![ScryptoValue::from_value(&scrypto::resource::Bucket(*bucket_id))]
-                                ).unwrap(); // TODO: Remove unwrap
-                            }
-                            Ok(result)
-                        })
-                },
-                ValidatedInstruction::CallMethodWithAllResources {
-                    component_address,
-                    method,
-                } => {
-                    system_api.invoke_snode(SNodeRef::AuthZoneRef, "clear".to_string(), vec![])
-                        .and_then(|_| {
-                            for (_, real_id) in self.proof_id_mapping.drain() {
-                                system_api.invoke_snode(
-                                    SNodeRef::Proof(real_id),
-                                    "drop".to_string(),
-                                    vec![]
-                                ).unwrap();
-                            }
-                            system_api.invoke_snode(SNodeRef::WorktopRef, "drain".to_string(), vec![])
-                        })
-                        .and_then(|result| {
-                            let mut buckets = Vec::new();
-                            for (bucket_id, _) in result.bucket_ids {
-                                buckets.push(scrypto::resource::Bucket(bucket_id));
-                            }
-                            for (_, real_id) in self.bucket_id_mapping.drain() {
-                                buckets.push(scrypto::resource::Bucket(real_id));
-                            }
-                            system_api.invoke_snode(
-                                SNodeRef::Scrypto(ScryptoActor::Component(*component_address)),
-                                method.to_string(),
-                                vec![ScryptoValue::from_value(&buckets)],
-                            )
-                        })
-                },
-                ValidatedInstruction::PublishPackage { code } => {
-                    system_api.invoke_snode(
-                        SNodeRef::PackageStatic,
-                        "publish".to_string(),
-                        vec![ScryptoValue::from_value(code)],
-                    )
-                },
-            }?;
-            self.outputs.push(result);
+                    })
+            },
+            ValidatedInstruction::PublishPackage { code } => {
+                system_api.invoke_snode(
+                    SNodeRef::PackageStatic,
+                    "publish".to_string(),
+                    vec![ScryptoValue::from_value(code)],
+                )
+            },
+            ValidatedInstruction::Abort { reason } => {
+                Err(RuntimeError::ManifestAborted(reason.clone()))
+            },
+            ValidatedInstruction::ConditionalBranch {
+                condition,
+                then_instructions,
+                else_instructions,
+            } => {
+                self.evaluate_condition(condition, system_api)
+                    .and_then(|holds| {
+                        let branch = if holds { then_instructions } else { else_instructions };
+                        let mut outputs = Vec::new();
+                        for inst in branch {
+                            outputs.push(self.execute_instruction(inst, system_api)?.dom);
+                        }
+                        let mut bytes = Vec::new();
+                        let mut enc = Encoder::with_type(&mut bytes);
+                        encode_any(
+                            None,
+                            &Value::Tuple {
+                                elements: vec![
+                                    Value::Bool { value: holds },
+                                    Value::Tuple { elements: outputs },
+                                ],
+                            },
+                            &mut enc,
+                        );
+                        ScryptoValue::from_slice(&bytes).map_err(RuntimeError::ParseScryptoValueError)
+                    })
+            },
         }
-
-        Ok(ScryptoValue::from_value(&()))
     }
-}
\ No newline at end of file
+}
@@ -5,6 +5,8 @@ use scrypto::rust::string::String;
 use scrypto::rust::vec::Vec;
 use scrypto::values::*;
 
+use crate::model::Condition;
+
 /// Represents a validated transaction
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ValidatedTransaction {
@@ -45,6 +47,7 @@ pub enum ValidatedInstruction {
         proof_id: ProofId,
     },
     ClearAuthZone,
+    AssertAuthZoneEmpty,
     CreateProofFromAuthZone {
         resource_address: ResourceAddress,
     },
@@ -56,6 +59,10 @@ pub enum ValidatedInstruction {
         ids: BTreeSet<NonFungibleId>,
         resource_address: ResourceAddress,
     },
+    CreateProofFromWorktopByAmount {
+        amount: Decimal,
+        resource_address: ResourceAddress,
+    },
     CreateProofFromBucket {
         bucket_id: BucketId,
     },
@@ -83,4 +90,12 @@ pub enum ValidatedInstruction {
     PublishPackage {
         code: Vec<u8>,
     },
+    Abort {
+        reason: String,
+    },
+    ConditionalBranch {
+        condition: Condition,
+        then_instructions: Vec<ValidatedInstruction>,
+        else_instructions: Vec<ValidatedInstruction>,
+    },
 }
\ No newline at end of file
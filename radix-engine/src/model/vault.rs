@@ -1,6 +1,8 @@
+use sbor::describe::Type;
 use sbor::*;
 use scrypto::buffer::scrypto_decode;
 use scrypto::engine::types::*;
+use scrypto::resource::{NonFungiblePredicate, SchemaPath};
 use scrypto::rust::cell::{Ref, RefCell, RefMut};
 use scrypto::rust::collections::BTreeSet;
 use scrypto::rust::collections::HashMap;
@@ -13,6 +15,7 @@ use crate::engine::SystemApi;
 
 use crate::model::{
     Bucket, Proof, ProofError, ResourceContainer, ResourceContainerError, ResourceContainerId,
+    ResourceMovement,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -24,6 +27,9 @@ pub enum VaultError {
     CouldNotTakeBucket,
     ProofError(ProofError),
     CouldNotCreateProof,
+    InvalidSchemaPath(SchemaPath),
+    NonFungibleNotFound(NonFungibleAddress),
+    UnknownNonFungible(NonFungibleAddress),
 }
 
 /// A persistent resource container.
@@ -136,6 +142,14 @@ impl Vault {
         self.borrow_container().total_ids()
     }
 
+    pub fn locked_amount(&self) -> Decimal {
+        self.borrow_container().max_locked_amount()
+    }
+
+    pub fn available_amount(&self) -> Decimal {
+        self.borrow_container().liquid_amount()
+    }
+
     pub fn is_locked(&self) -> bool {
         self.borrow_container().is_locked()
     }
@@ -144,6 +158,16 @@ impl Vault {
         self.borrow_container().is_empty()
     }
 
+    /// Returns whether this holds exactly the given amount and id set, where `None` leaves
+    /// that part unchecked.
+    pub fn contents_eq(
+        &self,
+        amount: Option<Decimal>,
+        ids: Option<BTreeSet<NonFungibleId>>,
+    ) -> bool {
+        self.borrow_container().contents_eq(amount, ids)
+    }
+
     fn borrow_container(&self) -> Ref<ResourceContainer> {
         self.container.borrow()
     }
@@ -164,13 +188,24 @@ impl Vault {
                 let bucket: scrypto::resource::Bucket =
                     scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
                 let bucket = system_api.take_bucket(bucket.0).map_err(|_| VaultError::CouldNotTakeBucket)?;
+                let movement = ResourceMovement::Deposit {
+                    vault_id,
+                    resource_address: bucket.resource_address(),
+                    amount: bucket.total_amount(),
+                };
                 self.put(bucket).map_err(VaultError::ResourceContainerError)?;
+                system_api.record_resource_movement(movement);
                 Ok(ScryptoValue::from_value(&()))
             }
             "take_from_vault" => {
                 let amount: Decimal =
                     scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
                 let container = self.take(amount)?;
+                system_api.record_resource_movement(ResourceMovement::Withdraw {
+                    vault_id,
+                    resource_address: container.resource_address(),
+                    amount: container.total_amount(),
+                });
                 let bucket_id = system_api.create_bucket(container).map_err(|_| VaultError::CouldNotCreateBucket)?;
                 Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(bucket_id)))
             }
@@ -178,6 +213,53 @@ impl Vault {
                 let non_fungible_ids: BTreeSet<NonFungibleId> =
                     scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
                 let container = self.take_non_fungibles(&non_fungible_ids)?;
+                system_api.record_resource_movement(ResourceMovement::Withdraw {
+                    vault_id,
+                    resource_address: container.resource_address(),
+                    amount: container.total_amount(),
+                });
+                let bucket_id = system_api.create_bucket(container).map_err(|_| VaultError::CouldNotCreateBucket)?;
+                Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(bucket_id)))
+            }
+            "take_non_fungibles_from_vault_where" => {
+                let schema: Type =
+                    scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
+                let path: SchemaPath =
+                    scrypto_decode(&args[1].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
+                let predicate: NonFungiblePredicate =
+                    scrypto_decode(&args[2].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
+                let sbor_path = path
+                    .to_sbor_path(&schema)
+                    .ok_or(VaultError::InvalidSchemaPath(path))?;
+
+                let resource_address = self.resource_address();
+                let ids = self.total_ids().map_err(VaultError::ResourceContainerError)?;
+                let mut matching_ids = BTreeSet::new();
+                for id in ids {
+                    let address = NonFungibleAddress::new(resource_address, id.clone());
+                    let non_fungible = system_api
+                        .get_non_fungible(&address)
+                        .map_err(|_| VaultError::UnknownNonFungible(address.clone()))?
+                        .ok_or(VaultError::NonFungibleNotFound(address))?;
+                    let matches = ScryptoValue::from_slice(&non_fungible.mutable_data())
+                        .ok()
+                        .and_then(|data| {
+                            sbor_path
+                                .get_from_value(&data.dom)
+                                .map(|value| predicate.matches(value))
+                        })
+                        .unwrap_or(false);
+                    if matches {
+                        matching_ids.insert(id);
+                    }
+                }
+
+                let container = self.take_non_fungibles(&matching_ids)?;
+                system_api.record_resource_movement(ResourceMovement::Withdraw {
+                    vault_id,
+                    resource_address: container.resource_address(),
+                    amount: container.total_amount(),
+                });
                 let bucket_id = system_api.create_bucket(container).map_err(|_| VaultError::CouldNotCreateBucket)?;
                 Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(bucket_id)))
             }
@@ -185,6 +267,14 @@ impl Vault {
                 let amount = self.total_amount();
                 Ok(ScryptoValue::from_value(&amount))
             }
+            "get_vault_locked_amount" => {
+                let amount = self.locked_amount();
+                Ok(ScryptoValue::from_value(&amount))
+            }
+            "get_vault_available_amount" => {
+                let amount = self.available_amount();
+                Ok(ScryptoValue::from_value(&amount))
+            }
             "get_vault_resource_address" => {
                 let resource_address = self.resource_address();
                 Ok(ScryptoValue::from_value(&resource_address))
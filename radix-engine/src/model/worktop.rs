@@ -11,12 +11,19 @@ use scrypto::rust::string::ToString;
 use scrypto::values::ScryptoValue;
 use crate::engine::SystemApi;
 
-use crate::model::{Bucket, ResourceContainer, ResourceContainerError, ResourceManager};
+use crate::model::{
+    assert_valid_amount, Bucket, Proof, ProofError, ResourceContainer, ResourceContainerError,
+    ResourceContainerId, ResourceManager,
+};
+
+/// The default maximum number of distinct resources on a worktop.
+pub const DEFAULT_MAX_WORKTOP_RESOURCES: usize = 1024;
 
 /// Worktop collects resources from function or method returns.
 #[derive(Debug)]
 pub struct Worktop {
     containers: HashMap<ResourceAddress, Rc<RefCell<ResourceContainer>>>,
+    max_resources: usize,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -28,25 +35,73 @@ pub enum WorktopError {
     CouldNotCreateBucket,
     CouldNotTakeBucket,
     AssertionFailed,
+    TooManyResources(usize),
+    ProofError(ProofError),
+    CouldNotCreateProof,
 }
 
 impl Worktop {
     pub fn new() -> Self {
+        Self::with_max_resources(DEFAULT_MAX_WORKTOP_RESOURCES)
+    }
+
+    /// Creates a worktop which holds at most `max_resources` distinct resources.
+    pub fn with_max_resources(max_resources: usize) -> Self {
         Self {
             containers: HashMap::new(),
+            max_resources,
         }
     }
 
-    pub fn put(&mut self, other: Bucket) -> Result<(), ResourceContainerError> {
+    pub fn put(&mut self, other: Bucket) -> Result<(), WorktopError> {
         let resource_address = other.resource_address();
-        let other_container = other.into_container()?;
+        let other_container = other
+            .into_container()
+            .map_err(WorktopError::ResourceContainerError)?;
         if let Some(mut container) = self.borrow_container_mut(resource_address) {
-            return container.put(other_container);
+            return container
+                .put(other_container)
+                .map_err(WorktopError::ResourceContainerError);
+        }
+        if self.containers.len() >= self.max_resources {
+            return Err(WorktopError::TooManyResources(self.max_resources));
         }
         self.put_container(resource_address, other_container);
         Ok(())
     }
 
+    /// Puts many buckets at once, merging buckets of the same resource before touching the worktop.
+    pub fn put_batch(&mut self, buckets: Vec<Bucket>) -> Result<(), WorktopError> {
+        let mut merged: HashMap<ResourceAddress, ResourceContainer> = HashMap::new();
+        for bucket in buckets {
+            let resource_address = bucket.resource_address();
+            let container = bucket
+                .into_container()
+                .map_err(WorktopError::ResourceContainerError)?;
+            if let Some(existing) = merged.get_mut(&resource_address) {
+                existing
+                    .put(container)
+                    .map_err(WorktopError::ResourceContainerError)?;
+            } else {
+                merged.insert(resource_address, container);
+            }
+        }
+
+        for (resource_address, other_container) in merged {
+            if let Some(mut container) = self.borrow_container_mut(resource_address) {
+                container
+                    .put(other_container)
+                    .map_err(WorktopError::ResourceContainerError)?;
+                continue;
+            }
+            if self.containers.len() >= self.max_resources {
+                return Err(WorktopError::TooManyResources(self.max_resources));
+            }
+            self.put_container(resource_address, other_container);
+        }
+        Ok(())
+    }
+
     fn take(
         &mut self,
         amount: Decimal,
@@ -56,8 +111,6 @@ impl Worktop {
             container
                 .take_by_amount(amount)
                 .map(Option::Some)
-        } else if !amount.is_zero() {
-            Err(ResourceContainerError::InsufficientBalance)
         } else {
             Ok(None)
         }
@@ -135,6 +188,41 @@ impl Worktop {
         true
     }
 
+    /// Creates a proof of the given amount, locked from the combined balance of all buckets of
+    /// the resource which were put on the worktop.
+    pub fn create_proof_by_amount(
+        &mut self,
+        amount: Decimal,
+        resource_address: ResourceAddress,
+    ) -> Result<Proof, WorktopError> {
+        let container = self
+            .containers
+            .get(&resource_address)
+            .ok_or(WorktopError::ResourceContainerError(
+                ResourceContainerError::InsufficientBalance,
+            ))?
+            .clone();
+        let locked_amount_or_ids = container
+            .borrow_mut()
+            .lock_by_amount(amount)
+            .map_err(WorktopError::ResourceContainerError)?;
+        let resource_type = container.borrow().resource_type();
+
+        // There is a single worktop per transaction
+        let mut evidence = HashMap::new();
+        evidence.insert(
+            ResourceContainerId::Worktop(0, resource_address),
+            (container, locked_amount_or_ids.clone()),
+        );
+        Proof::new(
+            resource_address,
+            resource_type,
+            locked_amount_or_ids,
+            evidence,
+        )
+        .map_err(WorktopError::ProofError)
+    }
+
     pub fn create_reference_for_proof(
         &self,
         resource_address: ResourceAddress,
@@ -177,8 +265,20 @@ impl Worktop {
                 let bucket = system_api
                     .take_bucket(bucket_id.0)
                     .map_err(|_| WorktopError::CouldNotTakeBucket)?;
-                self.put(bucket)
-                    .map_err(WorktopError::ResourceContainerError)?;
+                self.put(bucket)?;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "put_batch" => {
+                let bucket_ids: Vec<scrypto::resource::Bucket> =
+                    scrypto_decode(&args[0].raw).map_err(|e| WorktopError::InvalidRequestData(e))?;
+                let mut buckets = Vec::new();
+                for bucket_id in bucket_ids {
+                    let bucket = system_api
+                        .take_bucket(bucket_id.0)
+                        .map_err(|_| WorktopError::CouldNotTakeBucket)?;
+                    buckets.push(bucket);
+                }
+                self.put_batch(buckets)?;
                 Ok(ScryptoValue::from_value(&()))
             }
             "take_amount" => {
@@ -196,6 +296,15 @@ impl Worktop {
                         .map_err(|_| WorktopError::ResourceDoesNotExist(resource_address))?;
                     let resource_type = resource_manager.resource_type();
                     system_api.return_borrowed_global_resource_manager(resource_address, resource_manager);
+
+                    // Reject invalid amounts even when the resource is not on the worktop
+                    assert_valid_amount(amount, resource_type.divisibility())
+                        .map_err(WorktopError::ResourceContainerError)?;
+                    if !amount.is_zero() {
+                        return Err(WorktopError::ResourceContainerError(
+                            ResourceContainerError::InsufficientBalance,
+                        ));
+                    }
                     ResourceContainer::new_empty(resource_address, resource_type)
                 };
 
@@ -248,6 +357,11 @@ impl Worktop {
                     bucket_id,
                 )))
             }
+            "get_amount" => {
+                let resource_address: ResourceAddress =
+                    scrypto_decode(&args[0].raw).map_err(|e| WorktopError::InvalidRequestData(e))?;
+                Ok(ScryptoValue::from_value(&self.total_amount(resource_address)))
+            }
             "assert_contains" => {
                 let resource_address: ResourceAddress =
                     scrypto_decode(&args[0].raw).map_err(|e| WorktopError::InvalidRequestData(e))?;
@@ -285,6 +399,16 @@ impl Worktop {
                     Ok(ScryptoValue::from_value(&()))
                 }
             }
+            "create_proof_by_amount" => {
+                let amount: Decimal =
+                    scrypto_decode(&args[0].raw).map_err(|e| WorktopError::InvalidRequestData(e))?;
+                let resource_address: ResourceAddress =
+                    scrypto_decode(&args[1].raw).map_err(|e| WorktopError::InvalidRequestData(e))?;
+
+                let proof = self.create_proof_by_amount(amount, resource_address)?;
+                let proof_id = system_api.create_proof(proof).map_err(|_| WorktopError::CouldNotCreateProof)?;
+                Ok(ScryptoValue::from_value(&scrypto::resource::Proof(proof_id)))
+            }
             "drain" => {
                 let mut buckets = Vec::new();
                 for (_, container) in self.containers.drain() {
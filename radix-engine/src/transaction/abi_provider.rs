@@ -31,14 +31,18 @@ pub trait AbiProvider {
 /// Provides ABIs for blueprints either installed during bootstrap or added manually.
 pub struct BasicAbiProvider {
     substate_store: InMemorySubstateStore,
-    trace: bool,
+    trace_level: Option<Level>,
 }
 
 impl BasicAbiProvider {
     pub fn new(trace: bool) -> Self {
+        Self::with_trace_level(if trace { Some(Level::Debug) } else { None })
+    }
+
+    pub fn with_trace_level(trace_level: Option<Level>) -> Self {
         Self {
             substate_store: InMemorySubstateStore::with_bootstrap(),
-            trace,
+            trace_level,
         }
     }
 
@@ -68,7 +72,7 @@ impl AbiProvider for BasicAbiProvider {
 
         // Start a process and run abi generator
         let mut track = Track::new(&mut ledger, transaction_hash, Vec::new());
-        let mut proc = track.start_process(self.trace);
+        let mut proc = track.start_process(self.trace_level);
         let output: (Type, Vec<abi::Function>, Vec<abi::Method>) = proc
             .call_abi(package_address, blueprint_name)
             .and_then(|rtn| scrypto_decode(&rtn.raw).map_err(RuntimeError::AbiValidationError))?;
@@ -89,6 +93,7 @@ impl AbiProvider for BasicAbiProvider {
         let component: Component = self
             .substate_store
             .get_decoded_substate(&component_address)
+            .map_err(RuntimeError::InvalidSubstate)?
             .map(|(component, _)| component)
             .ok_or(RuntimeError::ComponentNotFound(component_address))?;
         self.export_abi(component.package_address(), component.blueprint_name())
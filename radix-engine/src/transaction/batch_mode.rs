@@ -0,0 +1,8 @@
+/// Decides what happens to the rest of a batch when one of its transactions fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchMode {
+    /// Skips the transactions after the failed one.
+    StopOnError,
+    /// Executes the transactions after the failed one, against the state committed so far.
+    ContinueOnError,
+}
@@ -69,7 +69,7 @@ impl TransactionBuilder {
             Instruction::PushToAuthZone { proof_id } => {
                 self.id_validator.drop_proof(proof_id).unwrap();
             }
-            Instruction::ClearAuthZone => {}
+            Instruction::ClearAuthZone | Instruction::AssertAuthZoneEmpty => {}
             Instruction::CreateProofFromAuthZone { .. }
             | Instruction::CreateProofFromAuthZoneByAmount { .. }
             | Instruction::CreateProofFromAuthZoneByIds { .. } => {
@@ -79,6 +79,13 @@ impl TransactionBuilder {
                         .unwrap(),
                 );
             }
+            Instruction::CreateProofFromWorktopByAmount { .. } => {
+                new_proof_id = Some(
+                    self.id_validator
+                        .new_proof(ProofKind::WorktopProof)
+                        .unwrap(),
+                );
+            }
             Instruction::CreateProofFromBucket { bucket_id } => {
                 new_proof_id = Some(
                     self.id_validator
@@ -101,7 +108,22 @@ impl TransactionBuilder {
             Instruction::CallMethodWithAllResources { .. } => {
                 self.id_validator.move_all_resources().unwrap();
             }
-            Instruction::PublishPackage { .. } | Instruction::Nonce { .. } => {}
+            Instruction::ConditionalBranch {
+                then_instructions,
+                else_instructions,
+                ..
+            } => {
+                if then_instructions
+                    .iter()
+                    .chain(else_instructions.iter())
+                    .any(|i| matches!(i, Instruction::CallMethodWithAllResources { .. }))
+                {
+                    self.id_validator.move_all_resources().unwrap();
+                }
+            }
+            Instruction::PublishPackage { .. }
+            | Instruction::Abort { .. }
+            | Instruction::Nonce { .. } => {}
         }
 
         self.instructions.push(inst);
@@ -211,6 +233,11 @@ impl TransactionBuilder {
         self.add_instruction(Instruction::ClearAuthZone).0
     }
 
+    /// Asserts that the auth zone is empty.
+    pub fn assert_auth_zone_empty(&mut self) -> &mut Self {
+        self.add_instruction(Instruction::AssertAuthZoneEmpty).0
+    }
+
     /// Creates proof from the auth zone.
     pub fn create_proof_from_auth_zone<F>(
         &mut self,
@@ -243,6 +270,24 @@ impl TransactionBuilder {
         then(builder, proof_id.unwrap())
     }
 
+    /// Creates proof from the worktop by amount.
+    pub fn create_proof_from_worktop_by_amount<F>(
+        &mut self,
+        amount: Decimal,
+        resource_address: ResourceAddress,
+        then: F,
+    ) -> &mut Self
+    where
+        F: FnOnce(&mut Self, ProofId) -> &mut Self,
+    {
+        let (builder, _, proof_id) =
+            self.add_instruction(Instruction::CreateProofFromWorktopByAmount {
+                amount,
+                resource_address,
+            });
+        then(builder, proof_id.unwrap())
+    }
+
     /// Creates proof from the auth zone by non-fungible ids.
     pub fn create_proof_from_auth_zone_by_ids<F>(
         &mut self,
@@ -413,6 +458,31 @@ impl TransactionBuilder {
         .0
     }
 
+    /// Aborts the transaction with the given reason.
+    pub fn abort(&mut self, reason: &str) -> &mut Self {
+        self.add_instruction(Instruction::Abort {
+            reason: reason.to_owned(),
+        })
+        .0
+    }
+
+    /// Executes `then_instructions` if the condition holds, `else_instructions` otherwise.
+    ///
+    /// Branch instructions may not create, consume or pass buckets and proofs.
+    pub fn conditional_branch(
+        &mut self,
+        condition: Condition,
+        then_instructions: Vec<Instruction>,
+        else_instructions: Vec<Instruction>,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::ConditionalBranch {
+            condition,
+            then_instructions,
+            else_instructions,
+        })
+        .0
+    }
+
     /// Builds a transaction with the given nonce.
     pub fn build(&self, nonce: u64) -> Transaction {
         let mut instructions = self.instructions.clone();
@@ -553,6 +623,28 @@ impl TransactionBuilder {
         self
     }
 
+    /// Mints a single non-fungible with the given data, leaving it on the worktop.
+    pub fn mint_non_fungible(
+        &mut self,
+        resource_address: ResourceAddress,
+        id: NonFungibleId,
+        immutable_data: Vec<u8>,
+        mutable_data: Vec<u8>,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::CallFunction {
+            package_address: SYSTEM_PACKAGE,
+            blueprint_name: "System".to_owned(),
+            function: "mint_non_fungible".to_owned(),
+            args: vec![
+                scrypto_encode(&resource_address),
+                scrypto_encode(&id),
+                scrypto_encode(&immutable_data),
+                scrypto_encode(&mutable_data),
+            ],
+        });
+        self
+    }
+
     /// Burns a resource.
     pub fn burn(&mut self, amount: Decimal, resource_address: ResourceAddress) -> &mut Self {
         self.take_from_worktop_by_amount(amount, resource_address, |builder, bucket_id| {
@@ -586,6 +678,12 @@ impl TransactionBuilder {
         )
     }
 
+    /// Deposits the given amount of XRD from the faucet into an account.
+    pub fn faucet_free_xrd(&mut self, account: ComponentAddress, amount: Decimal) -> &mut Self {
+        self.call_method(SYSTEM_COMPONENT, "free_xrd_amount", args![amount])
+            .call_method_with_all_resources(account, "deposit_batch")
+    }
+
     /// Creates an account.
     pub fn new_account(&mut self, withdraw_auth: &AccessRuleNode) -> &mut Self {
         self.add_instruction(Instruction::CallFunction {
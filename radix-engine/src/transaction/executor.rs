@@ -1,10 +1,11 @@
 use scrypto::crypto::hash;
 use scrypto::engine::types::*;
 use scrypto::resource::*;
+use scrypto::rust::collections::HashMap;
+use scrypto::rust::string::ToString;
 use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
-use scrypto::rust::string::ToString;
-use scrypto::{abi, rule, access_rule_node};
+use scrypto::{abi, access_rule_node, rule};
 
 use crate::engine::*;
 use crate::errors::*;
@@ -15,7 +16,15 @@ use crate::transaction::*;
 /// An executor that runs transactions.
 pub struct TransactionExecutor<'l, L: SubstateStore> {
     substate_store: &'l mut L,
-    trace: bool,
+    trace_level: Option<Level>,
+    block_timestamp: u64,
+    dangling_policy: DanglingPolicy,
+    unknown_substate_policy: UnknownSubstatePolicy,
+    max_created_values: usize,
+    max_mint_batch_size: usize,
+    max_created_substates: usize,
+    max_worktop_resources: usize,
+    allowed_reentrant_methods: Vec<ReentrantMethod>,
 }
 
 impl<'l, L: SubstateStore> NonceProvider for TransactionExecutor<'l, L> {
@@ -33,10 +42,11 @@ impl<'l, L: SubstateStore> AbiProvider for TransactionExecutor<'l, L> {
         let package: Package = self
             .substate_store
             .get_decoded_substate(&package_address)
+            .map_err(RuntimeError::InvalidSubstate)?
             .map(|(package, _)| package)
             .ok_or(RuntimeError::PackageNotFound(package_address))?;
 
-        BasicAbiProvider::new(self.trace)
+        BasicAbiProvider::with_trace_level(self.trace_level)
             .with_package(&package_address, package)
             .export_abi(package_address, blueprint_name)
     }
@@ -48,27 +58,93 @@ impl<'l, L: SubstateStore> AbiProvider for TransactionExecutor<'l, L> {
         let component: Component = self
             .substate_store
             .get_decoded_substate(&component_address)
+            .map_err(RuntimeError::InvalidSubstate)?
             .map(|(component, _)| component)
             .ok_or(RuntimeError::ComponentNotFound(component_address))?;
         let package: Package = self
             .substate_store
             .get_decoded_substate(&component.package_address())
+            .map_err(RuntimeError::InvalidSubstate)?
             .map(|(package, _)| package)
-            .unwrap();
-        BasicAbiProvider::new(self.trace)
+            .ok_or(RuntimeError::PackageNotFound(component.package_address()))?;
+        BasicAbiProvider::with_trace_level(self.trace_level)
             .with_package(&component.package_address(), package)
             .export_abi(component.package_address(), component.blueprint_name())
     }
 }
 
 impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
+    /// Creates an executor, showing trace messages up to `Level::Debug` if `trace` is set.
     pub fn new(substate_store: &'l mut L, trace: bool) -> Self {
+        Self::with_trace_level(
+            substate_store,
+            if trace { Some(Level::Debug) } else { None },
+        )
+    }
+
+    /// Creates an executor which shows trace messages up to the given level, if any.
+    pub fn with_trace_level(substate_store: &'l mut L, trace_level: Option<Level>) -> Self {
         Self {
             substate_store,
-            trace,
+            trace_level,
+            block_timestamp: 0,
+            dangling_policy: DanglingPolicy::Fail,
+            unknown_substate_policy: UnknownSubstatePolicy::Reject,
+            max_created_values: DEFAULT_MAX_CREATED_VALUES,
+            max_mint_batch_size: DEFAULT_MAX_MINT_BATCH_SIZE,
+            max_created_substates: DEFAULT_MAX_CREATED_SUBSTATES,
+            max_worktop_resources: DEFAULT_MAX_WORKTOP_RESOURCES,
+            allowed_reentrant_methods: Vec::new(),
         }
     }
 
+    /// Sets the block timestamp, in milliseconds since the Unix epoch, seen by transactions.
+    pub fn set_block_timestamp(&mut self, block_timestamp: u64) {
+        self.block_timestamp = block_timestamp;
+    }
+
+    /// Sets what happens to resources left dangling at the end of a transaction.
+    ///
+    /// `DanglingPolicy::SweepTo` needs the `sweep_dangling` feature and is never used for consensus.
+    pub fn set_dangling_policy(&mut self, dangling_policy: DanglingPolicy) {
+        self.dangling_policy = dangling_policy;
+    }
+
+    /// Sets what happens when a transaction loads a substate this engine can't decode.
+    ///
+    /// `UnknownSubstatePolicy::LoadAsRaw` needs the `unknown_substates_as_raw` feature.
+    pub fn set_unknown_substate_policy(&mut self, unknown_substate_policy: UnknownSubstatePolicy) {
+        self.unknown_substate_policy = unknown_substate_policy;
+    }
+
+    /// Sets the maximum number of values a transaction may create.
+    pub fn set_max_created_values(&mut self, max_created_values: usize) {
+        self.max_created_values = max_created_values;
+    }
+
+    /// Sets the maximum number of non-fungibles which may be minted in one call.
+    pub fn set_max_mint_batch_size(&mut self, max_mint_batch_size: usize) {
+        self.max_mint_batch_size = max_mint_batch_size;
+    }
+
+    /// Sets the maximum number of new substates a transaction may write to the ledger.
+    pub fn set_max_created_substates(&mut self, max_created_substates: usize) {
+        self.max_created_substates = max_created_substates;
+    }
+
+    /// Sets the maximum number of distinct resources the worktop may hold.
+    pub fn set_max_worktop_resources(&mut self, max_worktop_resources: usize) {
+        self.max_worktop_resources = max_worktop_resources;
+    }
+
+    /// Sets the blueprint methods which may be re-entered while already on the call stack.
+    pub fn set_allowed_reentrant_methods(
+        &mut self,
+        allowed_reentrant_methods: Vec<ReentrantMethod>,
+    ) {
+        self.allowed_reentrant_methods = allowed_reentrant_methods;
+    }
+
     /// Returns an immutable reference to the ledger.
     pub fn substate_store(&self) -> &L {
         self.substate_store
@@ -139,15 +215,25 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
     }
 
     /// Overwrites a package.
+    ///
+    /// Components instantiated from the previous version are migrated on their next call,
+    /// if the blueprint defines a `migrate_state` function.
     pub fn overwrite_package(
         &mut self,
         package_address: PackageAddress,
         code: Vec<u8>,
-    ) -> Result<(), WasmValidationError> {
+    ) -> Result<(), RuntimeError> {
+        let version = self
+            .substate_store
+            .get_decoded_substate(&package_address)
+            .map_err(RuntimeError::InvalidSubstate)?
+            .map(|(package, _): (Package, _)| package.version() + 1)
+            .unwrap_or(0);
+        let package = Package::new_with_version(code, version)
+            .map_err(|e| RuntimeError::PackageError(PackageError::WasmValidationError(e)))?;
+
         let tx_hash = hash(self.substate_store.get_and_increase_nonce().to_le_bytes());
         let mut id_gen = SubstateIdGenerator::new(tx_hash);
-
-        let package = Package::new(code)?;
         self.substate_store
             .put_encoded_substate(&package_address, &package, id_gen.next());
         Ok(())
@@ -162,20 +248,56 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         Ok(receipt)
     }
 
+    /// Executes transactions in order, each against the state committed by the ones before it.
+    ///
+    /// A failed transaction is never committed. With `BatchMode::StopOnError`, the transactions
+    /// after it are skipped and have no receipt.
+    pub fn execute_batch(
+        &mut self,
+        transactions: Vec<ValidatedTransaction>,
+        mode: BatchMode,
+    ) -> Vec<Receipt> {
+        let mut receipts = Vec::new();
+        for validated in transactions {
+            let receipt = self.execute(validated);
+            let failed = receipt.result.is_err();
+            receipts.push(receipt);
+            if failed && mode == BatchMode::StopOnError {
+                break;
+            }
+        }
+        receipts
+    }
+
     pub fn execute(&mut self, validated: ValidatedTransaction) -> Receipt {
         #[cfg(not(feature = "alloc"))]
         let now = std::time::Instant::now();
 
-        let mut track = Track::new(
+        let block_context = BlockContext {
+            epoch: self.substate_store.get_epoch(),
+            timestamp: self.block_timestamp,
+            transaction_hash: validated.raw_hash.clone(),
+        };
+        let mut track = Track::with_block_context(
             self.substate_store,
-            validated.raw_hash.clone(),
+            block_context,
             validated.signers.clone(),
         );
-        let mut proc = track.start_process(self.trace);
+        track.set_dangling_policy(self.dangling_policy);
+        track.set_unknown_substate_policy(self.unknown_substate_policy);
+        track.set_max_created_values(self.max_created_values);
+        track.set_max_mint_batch_size(self.max_mint_batch_size);
+        track.set_max_created_substates(self.max_created_substates);
+        track.set_max_worktop_resources(self.max_worktop_resources);
+        track.set_allowed_reentrant_methods(self.allowed_reentrant_methods.clone());
+        let mut proc = track.start_process(self.trace_level);
 
         let txn_process = TransactionProcess::new(validated.clone());
         let mut txn_snode = SNodeState::Transaction(txn_process);
-        let error = match proc.run(&mut txn_snode, "execute".to_string(), vec![]) {
+        let mut error = match proc
+            .run(&mut txn_snode, "execute".to_string(), vec![])
+            .and_then(|_| track.check_created_substates())
+        {
             Ok(_) => None,
             Err(e) => Some(e),
         };
@@ -190,18 +312,22 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         let new_component_addresses = track.new_component_addresses();
         let new_resource_addresses = track.new_resource_addresses();
         let logs = track.logs().clone();
+        let resource_movements = track.resource_movements().clone();
 
         // commit state updates
-        let commit_receipt = if error.is_none() {
+        let (commit_receipt, balance_changes, supply_changes) = if error.is_none() {
+            let balance_changes = track.balance_changes();
+            let supply_changes = track.supply_changes();
             let receipt = track.commit();
             self.substate_store.increase_nonce();
-            Some(receipt)
+            if let Err(e) = self.substate_store.flush() {
+                error = Some(RuntimeError::FlushFailed(e));
+            }
+            (Some(receipt), balance_changes, supply_changes)
         } else {
-            None
+            (None, BalanceChanges::default(), HashMap::new())
         };
 
-
-
         #[cfg(feature = "alloc")]
         let execution_time = None;
         #[cfg(not(feature = "alloc"))]
@@ -219,6 +345,9 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
             new_package_addresses,
             new_component_addresses,
             new_resource_addresses,
+            balance_changes,
+            supply_changes,
+            resource_movements,
             execution_time,
         }
     }
@@ -1,10 +1,12 @@
 mod abi_provider;
+mod batch_mode;
 mod builder;
 mod error;
 mod executor;
 mod nonce_provider;
 
 pub use abi_provider::{AbiProvider, BasicAbiProvider};
+pub use batch_mode::BatchMode;
 pub use builder::TransactionBuilder;
 pub use error::{BuildArgsError, CallWithAbiError};
 pub use executor::TransactionExecutor;
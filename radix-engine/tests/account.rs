@@ -122,3 +122,51 @@ fn test_account_balance() {
         ScryptoValue::from_value(&Decimal::from(1000000))
     );
 }
+
+#[test]
+fn transfer_should_report_balance_changes() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_amount(dec!("5"), RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+
+    // Act
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    let balance_changes = receipt.balance_changes;
+    assert_eq!(balance_changes.get(account, RADIX_TOKEN), dec!("-5"));
+    assert_eq!(balance_changes.get(other_account, RADIX_TOKEN), dec!("5"));
+    assert_eq!(balance_changes.per_account.len(), 2);
+}
+
+#[test]
+fn faucet_should_top_up_account_by_amount() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .faucet_free_xrd(account, dec!("250"))
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+
+    // Act
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    assert_eq!(
+        receipt.balance_changes.get(account, RADIX_TOKEN),
+        dec!("250")
+    );
+}
@@ -4,6 +4,7 @@ pub mod test_runner;
 use crate::test_runner::TestRunner;
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::MethodAuthorization;
 use scrypto::prelude::*;
 
 #[test]
@@ -129,3 +130,49 @@ fn can_make_cross_component_call_with_authorization() {
     // Assert
     receipt.result.expect("Should be okay");
 }
+
+#[test]
+fn public_method_runs_without_auth_and_unconfigured_methods_are_flagged() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let authorization = AccessRules::new().method("get_component_state", rule!(allow_all));
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "CrossComponent",
+            "create_component_with_auth",
+            vec![scrypto_encode(&authorization)],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "get_component_state", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let component = test_runner.component(component_address);
+    let schema = test_runner
+        .package(package_address)
+        .load_blueprint_schema("CrossComponent")
+        .unwrap()
+        .clone();
+    let (_, method_auths) = component.method_authorization(&schema, "get_component_state");
+    let unconfigured =
+        component.unconfigured_methods(&test_runner.export_abi_by_component(component_address));
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    assert_eq!(method_auths, vec![MethodAuthorization::Public]);
+    assert!(!unconfigured.contains(&"get_component_state".to_owned()));
+    assert!(unconfigured.contains(&"cross_component_call".to_owned()));
+    assert!(unconfigured.contains(&"put_auth".to_owned()));
+}
@@ -0,0 +1,38 @@
+use radix_engine::ledger::*;
+use radix_engine::model::{ResourceManager, ResourceManagerError};
+use scrypto::prelude::*;
+
+#[test]
+fn bootstrap_with_custom_xrd_divisibility() {
+    // Arrange
+    let ledger = InMemorySubstateStore::with_bootstrap_config(BootstrapConfig {
+        xrd_divisibility: 6,
+        initial_supply: 1_000_000.into(),
+    });
+
+    // Act
+    let (mut xrd, _): (ResourceManager, _) =
+        ledger.get_decoded_substate(&RADIX_TOKEN).unwrap().unwrap();
+
+    // Assert
+    assert_eq!(xrd.resource_type(), ResourceType::Fungible { divisibility: 6 });
+    assert!(xrd.mint_fungible(dec!("0.000001"), RADIX_TOKEN).is_ok());
+    assert_eq!(
+        xrd.mint_fungible(dec!("0.0000001"), RADIX_TOKEN).err(),
+        Some(ResourceManagerError::InvalidAmount(dec!("0.0000001"), 6))
+    );
+}
+
+#[test]
+fn bootstrap_should_return_well_known_addresses() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::new();
+
+    // Act
+    let genesis = ledger.bootstrap().expect("Should be bootstrapped");
+
+    // Assert
+    assert_eq!(genesis.xrd_resource, RADIX_TOKEN);
+    assert_eq!(genesis.faucet_component, SYSTEM_COMPONENT);
+    assert!(ledger.bootstrap().is_none());
+}
@@ -4,7 +4,9 @@ pub mod test_runner;
 use crate::test_runner::TestRunner;
 use radix_engine::errors::*;
 use radix_engine::ledger::*;
-use radix_engine::model::{BucketError, ResourceContainerError};
+use radix_engine::model::{
+    Bucket, BucketError, ResourceContainer, ResourceContainerError, WorktopError,
+};
 use radix_engine::transaction::*;
 use scrypto::prelude::*;
 
@@ -161,3 +163,121 @@ fn create_empty_bucket() {
     // Assert
     assert!(receipt.result.is_ok());
 }
+
+#[test]
+fn creating_too_many_buckets_should_fail() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("bucket");
+    test_runner.set_max_created_values(100);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "BucketTest",
+            "create_many_empty_buckets",
+            args![1000u32],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(receipt.result, Err(RuntimeError::TooManyValuesCreated));
+}
+
+#[test]
+fn all_returned_buckets_should_arrive_in_caller() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package = test_runner.publish_package("bucket");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "BucketTest", "receive_three", args![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    let (amounts, buckets): (Vec<Decimal>, Vec<scrypto::resource::Bucket>) =
+        scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    assert_eq!(amounts, vec![dec!("1"), dec!("2"), dec!("3")]);
+    let bucket_ids: HashSet<scrypto::engine::types::BucketId> =
+        buckets.iter().map(|bucket| bucket.0).collect();
+    assert_eq!(bucket_ids.len(), 3);
+}
+
+#[test]
+fn returning_a_vault_with_buckets_should_fail() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package = test_runner.publish_package("bucket");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "BucketTest", "split_with_vault", args![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(receipt.result, Err(RuntimeError::VaultNotAllowed));
+}
+
+#[test]
+fn bucket_contents_eq_compares_non_fungible_ids() {
+    // Arrange
+    let resource_address = ResourceAddress([1u8; 26]);
+    let mut ids = BTreeSet::new();
+    ids.insert(NonFungibleId::from_u32(1));
+    ids.insert(NonFungibleId::from_u32(2));
+    let mut other_ids = BTreeSet::new();
+    other_ids.insert(NonFungibleId::from_u32(1));
+
+    // Act
+    let bucket = Bucket::new(ResourceContainer::new_non_fungible(
+        resource_address,
+        ids.clone(),
+    ));
+
+    // Assert
+    assert!(bucket.contents_eq(Some(2.into()), Some(ids)));
+    assert!(!bucket.contents_eq(None, Some(other_ids)));
+    assert!(!bucket.contents_eq(Some(1.into()), None));
+}
+
+#[test]
+fn returning_more_resources_than_the_worktop_allows_should_fail() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    test_runner.set_max_worktop_resources(1);
+    let package = test_runner.publish_package("bucket");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "BucketTest", "test_restricted_transfer", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        receipt.result,
+        Err(RuntimeError::WorktopError(WorktopError::TooManyResources(1)))
+    );
+}
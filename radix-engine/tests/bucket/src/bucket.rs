@@ -91,6 +91,32 @@ blueprint! {
             (bucket, x)
         }
 
+        pub fn split_in_three() -> (Bucket, Bucket, Bucket) {
+            let mut bucket1 = Self::create_test_token(6);
+            let bucket2 = bucket1.take(2);
+            let bucket3 = bucket1.take(3);
+            (bucket1, bucket2, bucket3)
+        }
+
+        pub fn receive_three() -> (Vec<Decimal>, Vec<Bucket>) {
+            let rtn = Runtime::call_function(
+                Runtime::package_address(),
+                "BucketTest",
+                "split_in_three",
+                args![],
+            );
+            let (bucket1, bucket2, bucket3): (Bucket, Bucket, Bucket) =
+                scrypto_decode(&rtn).unwrap();
+            let buckets = vec![bucket1, bucket2, bucket3];
+            (buckets.iter().map(|b| b.amount()).collect(), buckets)
+        }
+
+        pub fn split_with_vault() -> (Bucket, Vault) {
+            let mut bucket = Self::create_test_token(2);
+            let vault = Vault::with_bucket(bucket.take(1));
+            (bucket, vault)
+        }
+
         pub fn create_empty_bucket_fungible() -> Bucket {
             Bucket::new(RADIX_TOKEN)
         }
@@ -99,5 +125,9 @@ blueprint! {
             let resource_address = ResourceBuilder::new_non_fungible().no_initial_supply();
             Bucket::new(resource_address)
         }
+
+        pub fn create_many_empty_buckets(count: u32) -> Vec<Bucket> {
+            (0..count).map(|_| Bucket::new(RADIX_TOKEN)).collect()
+        }
     }
 }
@@ -2,8 +2,10 @@
 pub mod test_runner;
 
 use crate::test_runner::TestRunner;
+use radix_engine::engine::ReentrantMethod;
 use radix_engine::errors::RuntimeError;
-use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::ledger::{InMemorySubstateStore, QueryableSubstateStore};
+use radix_engine::model::Component;
 use scrypto::prelude::*;
 
 #[test]
@@ -114,6 +116,160 @@ fn reentrancy_should_not_be_possible() {
     assert_eq!(error, RuntimeError::ComponentReentrancy(component_address))
 }
 
+#[test]
+fn recursive_self_call_should_cause_reentrant_call_error() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package_address, "ReentrantComponent", "new", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "call_self_recursively", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_eq!(
+        error,
+        RuntimeError::ReentrantComponentCall(component_address, "call_self_recursively".to_owned())
+    );
+}
+
+#[test]
+fn allowed_reentrant_method_should_pass_reentrant_call_guard() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package_address, "ReentrantComponent", "new", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let component_address = receipt.new_component_addresses[0];
+    test_runner.set_allowed_reentrant_methods(vec![ReentrantMethod {
+        package_address,
+        blueprint_name: "ReentrantComponent".to_owned(),
+        method: Some("call_self_recursively".to_owned()),
+    }]);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "call_self_recursively", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_eq!(error, RuntimeError::ComponentReentrancy(component_address));
+}
+
+#[derive(TypeId, Decode)]
+struct ComponentTestState {
+    _test_vault: Vault,
+    secret: String,
+}
+
+#[test]
+fn substate_change_shows_component_state_before_and_after() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package_address, "ComponentTest", "create_component", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "put_component_state", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let (before, after) = test_runner.inspect_substate_change(&transaction, component_address);
+
+    // Assert
+    let before: ComponentTestState = scrypto_decode(&before.raw).unwrap();
+    let after: ComponentTestState = scrypto_decode(&after.raw).unwrap();
+    assert_eq!(before.secret, "Secret");
+    assert_eq!(after.secret, "New secret");
+}
+
+#[test]
+fn read_only_method_leaves_component_state_unchanged() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package_address, "ComponentTest", "create_component", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "get_component_state", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+
+    // Assert
+    test_runner.assert_state_unchanged(component_address, &transaction);
+}
+
+#[test]
+#[should_panic(expected = "was changed")]
+fn mutating_method_fails_state_unchanged_assertion() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package_address, "ComponentTest", "create_component", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "put_component_state", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+
+    // Assert
+    test_runner.assert_state_unchanged(component_address, &transaction);
+}
+
 #[test]
 fn missing_component_address_should_cause_error() {
     // Arrange
@@ -136,3 +292,268 @@ fn missing_component_address_should_cause_error() {
     let error = receipt.result.expect_err("Should be an error.");
     assert_eq!(error, RuntimeError::ComponentNotFound(component_address));
 }
+
+#[test]
+fn named_component_address_collision_should_cause_error() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "ComponentTest",
+            "create_named_component",
+            args!["dex".to_owned()],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "ComponentTest",
+            "create_named_component",
+            args!["dex".to_owned()],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_eq!(
+        error,
+        RuntimeError::ComponentAddressCollision(component_address)
+    );
+}
+
+#[test]
+fn component_with_missing_blueprint_should_cause_error() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "ComponentTest",
+            "create_component",
+            args![],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let component_address = receipt.new_component_addresses[0];
+    let component = test_runner.component(component_address);
+    test_runner.put_component(
+        component_address,
+        Component::new(
+            package_address,
+            "MissingBlueprint".to_owned(),
+            component.authorization().to_vec(),
+            component.state().to_vec(),
+        ),
+    );
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "get_component_state", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_eq!(
+        error,
+        RuntimeError::BlueprintNotFound(package_address, "MissingBlueprint".to_owned())
+    );
+}
+
+#[test]
+fn components_can_be_listed_by_package() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let other_package_address = test_runner.publish_package("component");
+    let mut component_addresses = Vec::new();
+    for package in [package_address, package_address, other_package_address] {
+        let transaction = test_runner
+            .new_transaction_builder()
+            .call_function(package, "ComponentTest", "create_component", args![])
+            .build(test_runner.get_nonce([]))
+            .sign([]);
+        let receipt = test_runner.validate_and_execute(&transaction);
+        component_addresses.push(receipt.new_component_addresses[0]);
+    }
+
+    // Act
+    let listed: HashSet<ComponentAddress> = substate_store
+        .list_components_by_package(package_address)
+        .into_iter()
+        .collect();
+
+    // Assert
+    assert_eq!(
+        listed,
+        HashSet::from([component_addresses[0], component_addresses[1]])
+    );
+}
+
+#[test]
+fn frozen_component_should_reject_calls_until_unfrozen() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "ComponentTest",
+            "create_component",
+            args![],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let component_address = receipt.new_component_addresses[0];
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "freeze", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "get_component_state", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let frozen_receipt = test_runner.validate_and_execute(&transaction);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "unfreeze", args![])
+        .call_method(component_address, "get_component_state", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let unfrozen_receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = frozen_receipt.result.expect_err("Should be an error.");
+    assert_eq!(error, RuntimeError::ComponentFrozen(component_address));
+    unfrozen_receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn component_cannot_freeze_another_component() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "ComponentTest",
+            "create_component",
+            args![],
+        )
+        .call_function(
+            package_address,
+            "ComponentTest",
+            "create_component",
+            args![],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let component_address = receipt.new_component_addresses[0];
+    let other_component_address = receipt.new_component_addresses[1];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(
+            component_address,
+            "freeze_other",
+            args![other_component_address],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_eq!(
+        error,
+        RuntimeError::ComponentNotActor(other_component_address)
+    );
+}
+
+#[test]
+fn router_can_check_blueprint_abi_before_calling_method() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "ComponentTest",
+            "create_component",
+            args![],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "Router",
+            "call_if_exists",
+            args![component_address, "get_component_state".to_owned()],
+        )
+        .call_function(
+            package_address,
+            "Router",
+            "call_if_exists",
+            args![component_address, "no_such_method".to_owned()],
+        )
+        .call_function(
+            package_address,
+            "Router",
+            "blueprint_exists",
+            args![package_address, "NoSuchBlueprint".to_owned()],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let existing: Option<String> = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    let missing: Option<String> = scrypto_decode(&receipt.outputs[1].raw).unwrap();
+    let blueprint_exists: bool = scrypto_decode(&receipt.outputs[2].raw).unwrap();
+    assert_eq!(existing, Some("Secret".to_owned()));
+    assert_eq!(missing, None);
+    assert!(!blueprint_exists);
+}
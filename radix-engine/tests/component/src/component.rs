@@ -23,6 +23,15 @@ blueprint! {
             .globalize()
         }
 
+        pub fn create_named_component(name: String) -> ComponentAddress {
+            Self {
+                test_vault: Vault::with_bucket(Self::create_test_token(1000)),
+                secret: "Secret".to_owned(),
+            }
+            .instantiate()
+            .globalize_with_name(&name)
+        }
+
         pub fn get_component_info(component_address: ComponentAddress) -> (PackageAddress, String) {
             (
                 borrow_component!(component_address).package_address(),
@@ -43,5 +52,17 @@ blueprint! {
 
             bucket
         }
+
+        pub fn freeze(&self) {
+            borrow_component!(Runtime::actor().component_address().unwrap()).freeze();
+        }
+
+        pub fn unfreeze(&self) {
+            borrow_component!(Runtime::actor().component_address().unwrap()).unfreeze();
+        }
+
+        pub fn freeze_other(&self, other: ComponentAddress) {
+            borrow_component!(other).freeze();
+        }
     }
 }
@@ -5,4 +5,5 @@ pub mod component;
 pub mod cross_component;
 pub mod package;
 pub mod reentrant_component;
+pub mod router;
 pub mod typed_cross_component_call;
@@ -16,5 +16,12 @@ blueprint! {
                 self_component.call("func", vec![])
             }
         }
+
+        pub fn call_self_recursively(&mut self) {
+            if let ScryptoActor::Component(addr) = Runtime::actor().actor() {
+                let self_component = borrow_component!(addr);
+                self_component.call("call_self_recursively", vec![])
+            }
+        }
     }
 }
@@ -0,0 +1,26 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Router {}
+
+    impl Router {
+        pub fn call_if_exists(component_address: ComponentAddress, method: String) -> Option<String> {
+            let component = borrow_component!(component_address);
+            let abi = borrow_package!(component.package_address())
+                .blueprint_abi(&component.blueprint_name())
+                .unwrap();
+
+            if abi.methods.iter().any(|m| m.name == method) {
+                Some(component.call(&method, vec![]))
+            } else {
+                None
+            }
+        }
+
+        pub fn blueprint_exists(package_address: PackageAddress, blueprint_name: String) -> bool {
+            borrow_package!(package_address)
+                .blueprint_abi(&blueprint_name)
+                .is_some()
+        }
+    }
+}
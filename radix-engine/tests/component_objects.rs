@@ -0,0 +1,160 @@
+pub mod fixtures;
+
+use crate::fixtures::new_empty_vault;
+use radix_engine::engine::*;
+use radix_engine::errors::RuntimeError;
+use radix_engine::model::*;
+use scrypto::prelude::*;
+
+#[test]
+fn referenced_descendents_are_reachable() {
+    // Arrange
+    let root: LazyMapId = (hash([0u8]), 1024);
+    let vault_id: VaultId = (hash([0u8]), 1025);
+    let mut unclaimed = UnclaimedLazyMap::new();
+    unclaimed.lazy_map.insert(
+        scrypto_encode(&1u32),
+        scrypto_encode(&scrypto::resource::Vault(vault_id)),
+    );
+    unclaimed
+        .descendent_vaults
+        .insert(vault_id, new_empty_vault());
+    let mut objects = ComponentObjects::new();
+    objects.lazy_maps.insert(root, unclaimed);
+
+    // Act
+    let result = objects.check_reachable(&root);
+
+    // Assert
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn unreferenced_descendent_is_reported_as_orphan() {
+    // Arrange
+    let root: LazyMapId = (hash([0u8]), 1024);
+    let vault_id: VaultId = (hash([0u8]), 1025);
+    let mut unclaimed = UnclaimedLazyMap::new();
+    unclaimed
+        .lazy_map
+        .insert(scrypto_encode(&1u32), scrypto_encode(&()));
+    unclaimed
+        .descendent_vaults
+        .insert(vault_id, new_empty_vault());
+    let mut objects = ComponentObjects::new();
+    objects.lazy_maps.insert(root, unclaimed);
+
+    // Act
+    let result = objects.check_reachable(&root);
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(RuntimeError::OrphanedValue(ValueId::Vault(vault_id)))
+    );
+}
+
+#[test]
+fn only_the_given_root_is_checked_for_orphans() {
+    // Arrange
+    let root: LazyMapId = (hash([0u8]), 1024);
+    let other_root: LazyMapId = (hash([0u8]), 1026);
+    let mut other = UnclaimedLazyMap::new();
+    other
+        .descendent_vaults
+        .insert((hash([0u8]), 1025), new_empty_vault());
+    let mut objects = ComponentObjects::new();
+    objects.lazy_maps.insert(root, UnclaimedLazyMap::new());
+    objects.lazy_maps.insert(other_root, other);
+
+    // Act
+    let result = objects.check_reachable(&root);
+
+    // Assert
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn invalid_lazy_map_entry_fails_reachability_check() {
+    // Arrange
+    let root: LazyMapId = (hash([0u8]), 1024);
+    let mut unclaimed = UnclaimedLazyMap::new();
+    unclaimed
+        .lazy_map
+        .insert(scrypto_encode(&1u32), vec![0xffu8]);
+    let mut objects = ComponentObjects::new();
+    objects.lazy_maps.insert(root, unclaimed);
+
+    // Act
+    let result = objects.check_reachable(&root);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(RuntimeError::ParseScryptoValueError(_))
+    ));
+}
+
+#[test]
+fn consistent_objects_pass_invariant_check() {
+    // Arrange
+    let mut objects = ComponentObjects::new();
+    objects
+        .vaults
+        .insert((hash([0u8]), 1025), new_empty_vault());
+    let mut refs = ComponentObjectRefs::new();
+    refs.vault_ids.insert((hash([0u8]), 1026));
+
+    // Act
+    let result = objects.check_invariants(&refs);
+
+    // Assert
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn vault_both_owned_and_referenced_violates_invariant() {
+    // Arrange
+    let vault_id: VaultId = (hash([0u8]), 1025);
+    let mut objects = ComponentObjects::new();
+    objects.vaults.insert(vault_id, new_empty_vault());
+    let mut refs = ComponentObjectRefs::new();
+    refs.vault_ids.insert(vault_id);
+
+    // Act
+    let result = objects.check_invariants(&refs);
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(RuntimeError::EngineInvariantViolated(ValueId::Vault(
+            vault_id
+        )))
+    );
+}
+
+#[test]
+fn lazy_map_owned_twice_violates_invariant() {
+    // Arrange
+    let lazy_map_id: LazyMapId = (hash([0u8]), 1025);
+    let mut unclaimed = UnclaimedLazyMap::new();
+    unclaimed
+        .descendent_lazy_maps
+        .insert(lazy_map_id, HashMap::new());
+    let mut objects = ComponentObjects::new();
+    objects.lazy_maps.insert((hash([0u8]), 1024), unclaimed);
+    objects
+        .lazy_maps
+        .insert(lazy_map_id, UnclaimedLazyMap::new());
+
+    // Act
+    let result = objects.check_invariants(&ComponentObjectRefs::new());
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(RuntimeError::EngineInvariantViolated(ValueId::LazyMap(
+            lazy_map_id
+        )))
+    );
+}
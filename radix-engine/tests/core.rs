@@ -1,3 +1,4 @@
+use radix_engine::engine::{DEFAULT_MAX_LOG_COUNT, LOGS_TRUNCATED_MESSAGE, NO_LOG_COMPONENT};
 use radix_engine::ledger::*;
 use radix_engine::transaction::*;
 use scrypto::prelude::*;
@@ -36,3 +37,190 @@ fn test_call() {
     let receipt = executor.validate_and_execute(&transaction).unwrap();
     receipt.result.expect("Should be okay.");
 }
+
+#[test]
+fn blueprint_reads_consistent_block_context() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    executor.substate_store_mut().set_epoch(7);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "core")))
+        .unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "CoreTest", "block_context", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    let (epoch, transaction_hash, nested_epoch, nested_hash): (u64, Hash, u64, Hash) =
+        scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    assert_eq!(epoch, 7);
+    assert_eq!(nested_epoch, 7);
+    assert_eq!(transaction_hash, receipt.validated_transaction.raw_hash);
+    assert_eq!(nested_hash, receipt.validated_transaction.raw_hash);
+}
+
+#[test]
+fn blueprint_sees_index_of_calling_instruction() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "core")))
+        .unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "CoreTest", "instruction_index", args![])
+        .call_function(package, "CoreTest", "instruction_index", args![])
+        .call_function(package, "CoreTest", "nested_instruction_index", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    let indices: Vec<Option<usize>> = receipt
+        .outputs
+        .iter()
+        .map(|output| scrypto_decode(&output.raw).unwrap())
+        .collect();
+    assert_eq!(indices, vec![Some(0), Some(1), None]);
+}
+
+#[test]
+fn blueprint_sees_transaction_signers() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "core")))
+        .unwrap();
+    let (pk, sk) = executor.new_key_pair();
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "CoreTest", "transaction_signers", args![])
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    let signers: Vec<EcdsaPublicKey> = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    assert_eq!(signers, vec![pk]);
+}
+
+#[test]
+fn logs_are_attributed_to_the_emitting_component() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "core")))
+        .unwrap();
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "Logger", "new", args!["a".to_owned()])
+        .call_function(package, "Logger", "new", args!["b".to_owned()])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    let a = receipt.new_component_addresses[0];
+    let b = receipt.new_component_addresses[1];
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(b, "log", args![])
+        .call_method(a, "log", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    assert_eq!(
+        receipt.logs,
+        vec![
+            (Level::Info, b, "Hello from b".to_owned()),
+            (Level::Info, a, "Hello from a".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn logs_past_the_limit_are_truncated_in_receipt() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "core")))
+        .unwrap();
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "Logger", "new", args!["a".to_owned()])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    let a = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(a, "log_many", args![DEFAULT_MAX_LOG_COUNT as u32 + 1])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    assert_eq!(receipt.logs.len(), DEFAULT_MAX_LOG_COUNT + 1);
+    assert_eq!(
+        receipt.logs[DEFAULT_MAX_LOG_COUNT - 1],
+        (
+            Level::Info,
+            a,
+            format!("Message {} from a", DEFAULT_MAX_LOG_COUNT - 1)
+        )
+    );
+    assert_eq!(
+        receipt.logs[DEFAULT_MAX_LOG_COUNT],
+        (
+            Level::Warn,
+            NO_LOG_COMPONENT,
+            LOGS_TRUNCATED_MESSAGE.to_owned()
+        )
+    );
+}
+
+#[test]
+fn logs_are_recorded_regardless_of_trace_level() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::with_trace_level(&mut ledger, Some(Level::Error));
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "core")))
+        .unwrap();
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "Logger", "new", args!["a".to_owned()])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    let a = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(a, "log", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    assert_eq!(
+        receipt.logs,
+        vec![(Level::Info, a, "Hello from a".to_owned())]
+    );
+}
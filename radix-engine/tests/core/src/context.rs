@@ -12,5 +12,40 @@ blueprint! {
                 Runtime::generate_uuid(),
             )
         }
+
+        pub fn block_context() -> (u64, Hash, u64, Hash) {
+            let rtn = Runtime::call_function(
+                Runtime::package_address(),
+                "CoreTest",
+                "query",
+                args![],
+            );
+            let (_, nested_hash, nested_epoch, _): (PackageAddress, Hash, u64, u128) =
+                scrypto_decode(&rtn).unwrap();
+            (
+                Runtime::current_epoch(),
+                Runtime::transaction_hash(),
+                nested_epoch,
+                nested_hash,
+            )
+        }
+
+        pub fn transaction_signers() -> Vec<EcdsaPublicKey> {
+            Runtime::transaction_signers()
+        }
+
+        pub fn instruction_index() -> Option<usize> {
+            Runtime::instruction_index()
+        }
+
+        pub fn nested_instruction_index() -> Option<usize> {
+            let rtn = Runtime::call_function(
+                Runtime::package_address(),
+                "CoreTest",
+                "instruction_index",
+                args![],
+            );
+            scrypto_decode(&rtn).unwrap()
+        }
     }
 }
@@ -1,2 +1,3 @@
 pub mod call;
 pub mod context;
+pub mod logger;
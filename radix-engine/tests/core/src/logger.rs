@@ -0,0 +1,23 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Logger {
+        name: String,
+    }
+
+    impl Logger {
+        pub fn new(name: String) -> ComponentAddress {
+            Self { name }.instantiate().globalize()
+        }
+
+        pub fn log(&self) {
+            info!("Hello from {}", self.name);
+        }
+
+        pub fn log_many(&self, count: u32) {
+            for i in 0..count {
+                info!("Message {} from {}", i, self.name);
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use radix_engine::model::{Bucket, Proof, ResourceContainer, Vault};
+use scrypto::prelude::*;
+
+pub fn bucket_of(resource_address: ResourceAddress) -> Bucket {
+    Bucket::new(ResourceContainer::new_fungible(
+        resource_address,
+        18,
+        Decimal::one(),
+    ))
+}
+
+pub fn proof_of(resource_address: ResourceAddress) -> Proof {
+    let mut bucket = bucket_of(resource_address);
+    bucket.create_proof(1024).unwrap()
+}
+
+pub fn new_empty_vault() -> Vault {
+    Vault::new(ResourceContainer::new_empty(
+        RADIX_TOKEN,
+        ResourceType::Fungible { divisibility: 18 },
+    ))
+}
@@ -1,6 +1,7 @@
 use radix_engine::errors::ResourceFailure;
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::*;
+use radix_engine::model::Component;
 use radix_engine::transaction::*;
 use scrypto::prelude::*;
 
@@ -228,3 +229,109 @@ fn create_lazy_map_and_put() {
     // Assert
     assert!(receipt.result.is_ok());
 }
+
+#[derive(TypeId, Decode)]
+struct LazyMapTestState {
+    map: LazyMap<String, String>,
+    _vector: Vec<LazyMap<String, String>>,
+    _lazy_maps: LazyMap<String, LazyMap<String, String>>,
+}
+
+#[test]
+fn corrupt_lazy_map_entry_should_fail_with_key() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "lazy_map")))
+        .unwrap();
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "LazyMapTest", "new_lazy_map_with_put", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    let component_address = receipt.new_component_addresses[0];
+    let (component, _): (Component, _) = executor
+        .substate_store()
+        .get_decoded_substate(&component_address)
+        .unwrap()
+        .unwrap();
+    let state: LazyMapTestState = scrypto_decode(component.state()).unwrap();
+    let key = scrypto_encode(&"hello".to_owned());
+    executor
+        .substate_store_mut()
+        .put_encoded_grand_child_substate(
+            &component_address,
+            &state.map.id,
+            &key,
+            &[0xff],
+            (hash([]), 0),
+        );
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(component_address, "get_from_map", args!["hello".to_owned()])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert_eq!(
+        runtime_error,
+        RuntimeError::InvalidLazyMapEntry(state.map.id, key)
+    );
+}
+
+#[test]
+fn creating_too_many_substates_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "lazy_map")))
+        .unwrap();
+    executor.set_max_created_substates(10);
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(
+            package,
+            "LazyMapTest",
+            "new_lazy_map_with_many_puts",
+            args![20u32],
+        )
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    assert_eq!(receipt.result, Err(RuntimeError::TooManySubstatesCreated));
+    assert!(receipt.commit_receipt.is_none());
+}
+
+#[test]
+fn creating_substates_up_to_limit_should_succeed() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "lazy_map")))
+        .unwrap();
+    executor.set_max_created_substates(10);
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(
+            package,
+            "LazyMapTest",
+            "new_lazy_map_with_many_puts",
+            args![5u32],
+        )
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    assert!(receipt.result.is_ok());
+}
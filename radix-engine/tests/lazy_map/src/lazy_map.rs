@@ -84,6 +84,24 @@ blueprint! {
             .globalize()
         }
 
+        pub fn new_lazy_map_with_many_puts(count: u32) -> ComponentAddress {
+            let map = LazyMap::new();
+            for i in 0..count {
+                map.insert(i.to_string(), i.to_string());
+            }
+            LazyMapTest {
+                map,
+                vector: Vec::new(),
+                lazy_maps: LazyMap::new(),
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        pub fn get_from_map(&self, key: String) -> Option<String> {
+            self.map.get(&key)
+        }
+
         pub fn overwrite_lazy_map(&mut self) -> () {
             self.lazy_maps.insert("hello".to_owned(), LazyMap::new())
         }
@@ -0,0 +1,48 @@
+pub mod fixtures;
+
+use crate::fixtures::proof_of;
+use radix_engine::model::*;
+use scrypto::prelude::*;
+
+#[test]
+fn failed_rule_identifies_failing_all_of_clause() {
+    // Arrange
+    let authorization = MethodAuthorization::Protected(HardAuthRule::AllOf(vec![
+        HardAuthRule::ProofRule(HardProofRule::This(HardResourceOrNonFungible::Resource(
+            RADIX_TOKEN,
+        ))),
+        HardAuthRule::ProofRule(HardProofRule::This(HardResourceOrNonFungible::Resource(
+            ECDSA_TOKEN,
+        ))),
+    ]));
+    let auth_zone = AuthZone::new_with_proofs(vec![proof_of(RADIX_TOKEN)]);
+
+    // Act
+    let result = authorization.check(&[&auth_zone]);
+    let failed_rule = authorization.failed_rule(&[&auth_zone]);
+
+    // Assert
+    assert_eq!(result, Err(MethodAuthorizationError::NotAuthorized));
+    assert_eq!(
+        failed_rule,
+        Some(format!(
+            "AllOf[1]: require({:?})",
+            HardResourceOrNonFungible::Resource(ECDSA_TOKEN)
+        ))
+    );
+}
+
+#[test]
+fn satisfied_rule_has_no_failed_rule() {
+    // Arrange
+    let authorization = MethodAuthorization::Protected(HardAuthRule::ProofRule(
+        HardProofRule::This(HardResourceOrNonFungible::Resource(RADIX_TOKEN)),
+    ));
+    let auth_zone = AuthZone::new_with_proofs(vec![proof_of(RADIX_TOKEN)]);
+
+    // Act
+    let failed_rule = authorization.failed_rule(&[&auth_zone]);
+
+    // Assert
+    assert_eq!(failed_rule, None);
+}
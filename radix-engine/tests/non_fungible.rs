@@ -2,7 +2,9 @@
 pub mod test_runner;
 
 use crate::test_runner::TestRunner;
+use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::*;
+use radix_engine::model::ResourceManagerError;
 use radix_engine::transaction::*;
 use scrypto::prelude::*;
 
@@ -141,3 +143,262 @@ fn test_singleton_non_fungible() {
     println!("{:?}", receipt);
     assert!(receipt.result.is_ok());
 }
+
+#[test]
+fn can_burn_non_fungibles_by_id() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package = test_runner.publish_package("non_fungible");
+    let ids = BTreeSet::from([NonFungibleId::from_u32(1), NonFungibleId::from_u32(2)]);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "burn_non_fungibles_by_id",
+            args![ids],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+}
+
+#[test]
+fn cannot_burn_non_existent_non_fungible_by_id() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package = test_runner.publish_package("non_fungible");
+    let ids = BTreeSet::from([NonFungibleId::from_u32(1), NonFungibleId::from_u32(9)]);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "burn_non_fungibles_by_id",
+            args![ids],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect_err("Should be an error.");
+}
+
+#[test]
+fn can_take_non_fungibles_matching_predicate_from_vault() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package = test_runner.publish_package("non_fungible");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "NonFungibleTest", "create_hero_vault", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(
+            component_address,
+            "take_heroes_of_level_at_least",
+            args![5u32],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    let (taken_ids, _): (BTreeSet<NonFungibleId>, Bucket) =
+        scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    assert_eq!(
+        taken_ids,
+        BTreeSet::from([NonFungibleId::from_u32(2), NonFungibleId::from_u32(3)])
+    );
+}
+
+#[test]
+fn can_mint_non_fungible_with_data_from_manifest() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let package = test_runner.publish_package("non_fungible");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "create_non_fungible_mutable",
+            vec![],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let mint_badge = receipt.new_resource_addresses[0];
+    let resource_address = receipt.new_resource_addresses[1];
+    let id = NonFungibleId::from_u32(1);
+    let immutable_data = scrypto_encode(&"Club".to_owned());
+    let mutable_data = scrypto_encode(&true);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(mint_badge, account)
+        .mint_non_fungible(
+            resource_address,
+            id.clone(),
+            immutable_data.clone(),
+            mutable_data.clone(),
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    let (non_fungible, _): (Option<radix_engine::model::NonFungible>, _) = substate_store
+        .get_decoded_child_substate(&resource_address, &id)
+        .unwrap()
+        .unwrap();
+    let non_fungible = non_fungible.unwrap();
+    assert_eq!(non_fungible.immutable_data(), immutable_data);
+    assert_eq!(non_fungible.mutable_data(), mutable_data);
+}
+
+#[test]
+fn can_mint_non_fungibles_up_to_batch_size() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package = test_runner.publish_package("non_fungible");
+    test_runner.set_max_mint_batch_size(3);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "create_non_fungibles",
+            args![3u32],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+}
+
+#[test]
+fn cannot_mint_non_fungibles_over_batch_size() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package = test_runner.publish_package("non_fungible");
+    test_runner.set_max_mint_batch_size(3);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "create_non_fungibles",
+            args![4u32],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        receipt.result,
+        Err(RuntimeError::ResourceManagerError(
+            ResourceManagerError::MintBatchTooLarge
+        ))
+    );
+}
+
+#[test]
+fn can_update_non_fungible_with_current_version() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package = test_runner.publish_package("non_fungible");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "update_non_fungible_if_version",
+            args![false],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+}
+
+#[test]
+fn cannot_update_non_fungible_with_stale_version() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package = test_runner.publish_package("non_fungible");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "update_non_fungible_if_version",
+            args![true],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert!(matches!(
+        receipt.result,
+        Err(RuntimeError::ResourceManagerError(
+            ResourceManagerError::VersionConflict(_)
+        ))
+    ));
+}
@@ -7,6 +7,13 @@ pub struct Sandwich {
     pub available: bool,
 }
 
+#[derive(NonFungibleData)]
+pub struct Hero {
+    pub name: String,
+    #[scrypto(mutable)]
+    pub level: u32,
+}
+
 blueprint! {
     struct NonFungibleTest {
         vault: Vault,
@@ -63,6 +70,51 @@ blueprint! {
                 ])
         }
 
+        pub fn create_hero_vault() -> ComponentAddress {
+            let heroes = ResourceBuilder::new_non_fungible()
+                .metadata("name", "Heroes")
+                .initial_supply([
+                    (
+                        NonFungibleId::from_u32(1),
+                        Hero {
+                            name: "Novice".to_owned(),
+                            level: 1,
+                        },
+                    ),
+                    (
+                        NonFungibleId::from_u32(2),
+                        Hero {
+                            name: "Veteran".to_owned(),
+                            level: 5,
+                        },
+                    ),
+                    (
+                        NonFungibleId::from_u32(3),
+                        Hero {
+                            name: "Champion".to_owned(),
+                            level: 9,
+                        },
+                    ),
+                ]);
+
+            Self {
+                vault: Vault::with_bucket(heroes),
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        pub fn take_heroes_of_level_at_least(
+            &mut self,
+            level: u32,
+        ) -> (BTreeSet<NonFungibleId>, Bucket) {
+            let heroes = self.vault.take_non_fungibles_where::<Hero>(
+                SchemaPath::from_str("level").unwrap(),
+                NonFungiblePredicate::GreaterThanOrEqual(level.into()),
+            );
+            (heroes.non_fungible_ids(), heroes)
+        }
+
         pub fn create_non_fungible_fixed() -> Bucket {
             ResourceBuilder::new_non_fungible()
                 .metadata("name", "Katz's Sandwiches")
@@ -117,6 +169,31 @@ blueprint! {
             (mint_badge, bucket)
         }
 
+        pub fn update_non_fungible_if_version(stale: bool) -> (Bucket, Bucket) {
+            let (mint_badge, resource_address, bucket) = Self::create_non_fungible_mutable();
+            let resource_manager = borrow_resource_manager!(resource_address);
+            let id = NonFungibleId::from_u32(0);
+            let version = resource_manager.get_non_fungible_version(&id);
+            let mut data: Sandwich = resource_manager.get_non_fungible_data(&id);
+
+            data.available = true;
+            mint_badge.authorize(|| {
+                if stale {
+                    resource_manager.update_non_fungible_data(
+                        &id,
+                        Sandwich {
+                            name: data.name.clone(),
+                            available: false,
+                        },
+                    );
+                }
+                resource_manager.update_non_fungible_data_if_version(&id, version, data);
+            });
+
+            assert_eq!(resource_manager.get_non_fungible_version(&id), version + 1);
+            (mint_badge, bucket)
+        }
+
         pub fn non_fungible_exists() -> (Bucket, Bucket) {
             let (mint_badge, resource_address, bucket) = Self::create_non_fungible_mutable();
             assert_eq!(
@@ -239,5 +316,58 @@ blueprint! {
             vault.put(bucket);
             NonFungibleTest { vault }.instantiate().globalize();
         }
+
+        pub fn burn_non_fungibles_by_id(ids: BTreeSet<NonFungibleId>) -> Bucket {
+            let bucket = ResourceBuilder::new_non_fungible()
+                .metadata("name", "Katz's Sandwiches")
+                .burnable(rule!(allow_all), LOCKED)
+                .initial_supply([
+                    (
+                        NonFungibleId::from_u32(1),
+                        Sandwich {
+                            name: "One".to_owned(),
+                            available: true,
+                        },
+                    ),
+                    (
+                        NonFungibleId::from_u32(2),
+                        Sandwich {
+                            name: "Two".to_owned(),
+                            available: true,
+                        },
+                    ),
+                    (
+                        NonFungibleId::from_u32(3),
+                        Sandwich {
+                            name: "Three".to_owned(),
+                            available: true,
+                        },
+                    ),
+                ]);
+            let resource_manager = borrow_resource_manager!(bucket.resource_address());
+
+            let bucket = resource_manager.burn_non_fungibles(bucket, &ids);
+
+            assert_eq!(resource_manager.total_supply(), (3 - ids.len()).into());
+            assert_eq!(bucket.amount(), (3 - ids.len()).into());
+            for id in &ids {
+                assert_eq!(resource_manager.non_fungible_exists(id), false);
+            }
+            bucket
+        }
+
+        pub fn create_non_fungibles(count: u32) -> Bucket {
+            ResourceBuilder::new_non_fungible()
+                .metadata("name", "Katz's Sandwiches")
+                .initial_supply((0..count).map(|i| {
+                    (
+                        NonFungibleId::from_u32(i),
+                        Sandwich {
+                            name: i.to_string(),
+                            available: true,
+                        },
+                    )
+                }))
+        }
     }
 }
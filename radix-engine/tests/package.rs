@@ -2,11 +2,12 @@
 pub mod test_runner;
 
 use crate::test_runner::TestRunner;
-use radix_engine::errors::RuntimeError;
 use radix_engine::errors::WasmValidationError::NoValidMemoryExport;
+use radix_engine::errors::{RuntimeError, WasmValidationError};
 use radix_engine::ledger::InMemorySubstateStore;
 use scrypto::prelude::*;
-use radix_engine::model::PackageError;
+use radix_engine::model::{Package, PackageError, MAX_PACKAGE_CODE_SIZE};
+use scrypto::values::ScryptoValue;
 
 #[test]
 fn missing_memory_should_cause_error() {
@@ -101,3 +102,127 @@ fn zero_return_len_should_cause_data_validation_error() {
         panic!("{} should be data validation error", error);
     }
 }
+
+#[test]
+fn component_state_is_migrated_after_package_upgrade() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package = test_runner.publish_package("state_migration_v1");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "Counter", "new", args![5u32])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let component = receipt.new_component_addresses[0];
+    test_runner.overwrite_package(package, "state_migration_v2");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component, "increment", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    assert_eq!(
+        receipt.outputs[0],
+        ScryptoValue::from_value(&(6u32, "migrated".to_owned()))
+    );
+}
+
+#[test]
+fn package_code_hash_should_match_published_code() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let code = TestRunner::compile("package");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .publish_package(&code)
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let package_address = receipt.new_package_addresses[0];
+
+    // Act
+    let package = test_runner.package(package_address);
+
+    // Assert
+    assert_eq!(package.code_hash(), hash(&code));
+}
+
+#[test]
+fn oversized_code_should_cause_error() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let code = vec![0u8; MAX_PACKAGE_CODE_SIZE + 1];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .publish_package(&code)
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be error.");
+    assert_eq!(
+        error,
+        RuntimeError::PackageError(PackageError::WasmValidationError(
+            WasmValidationError::CodeTooLarge {
+                size: MAX_PACKAGE_CODE_SIZE + 1,
+                max: MAX_PACKAGE_CODE_SIZE,
+            }
+        ))
+    );
+}
+
+#[test]
+fn validate_many_should_report_each_module() {
+    // Arrange
+    let good_code: Vec<u8> = wabt::wat2wasm(
+        r#"
+            (module
+                (memory $0 1)
+                (export "memory" (memory $0))
+            )
+            "#,
+    )
+    .expect("failed to parse wat");
+    let missing_memory_code: Vec<u8> = wabt::wat2wasm(
+        r#"
+            (module
+                (func (export "test") (result i32)
+                    i32.const 1337
+                )
+            )
+            "#,
+    )
+    .expect("failed to parse wat");
+    let invalid_code = vec![0u8; 16];
+
+    // Act
+    let results = Package::validate_many(&[
+        good_code.as_slice(),
+        missing_memory_code.as_slice(),
+        invalid_code.as_slice(),
+        good_code.as_slice(),
+    ]);
+
+    // Assert
+    assert_eq!(
+        results,
+        vec![
+            Ok(()),
+            Err(NoValidMemoryExport),
+            Err(WasmValidationError::InvalidModule),
+            Ok(()),
+        ]
+    );
+}
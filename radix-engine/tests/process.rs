@@ -0,0 +1,98 @@
+use radix_engine::engine::*;
+use radix_engine::errors::RuntimeError;
+use radix_engine::ledger::*;
+use scrypto::core::{SNodeRef, ScryptoActor};
+use scrypto::prelude::*;
+use scrypto::rust::cell::RefCell;
+use scrypto::rust::rc::Rc;
+
+#[test]
+fn accessing_value_outside_component_is_invalid_data_access() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+    let process = Process::new(0, None, &mut track, None, None, HashMap::new(), HashMap::new());
+    let lazy_map_id: LazyMapId = (hash([0u8]), 1024);
+
+    // Act
+    let result = process.assert_visible(ValueId::LazyMap(lazy_map_id));
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(RuntimeError::InvalidDataAccess(ValueId::LazyMap(lazy_map_id)))
+    );
+}
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Enter(String, usize),
+    Exit(usize, bool),
+}
+
+struct RecordingObserver {
+    events: Rc<RefCell<Vec<Event>>>,
+}
+
+impl InvocationObserver for RecordingObserver {
+    fn on_enter(&mut self, _snode_ref: &SNodeRef, function: &str, depth: usize) {
+        self.events
+            .borrow_mut()
+            .push(Event::Enter(function.to_owned(), depth));
+    }
+
+    fn on_exit(&mut self, depth: usize, succeeded: bool, _elapsed: Option<u128>) {
+        self.events.borrow_mut().push(Event::Exit(depth, succeeded));
+    }
+}
+
+#[test]
+fn invocation_observer_sees_nested_calls() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+    let events = Rc::new(RefCell::new(Vec::new()));
+    track.set_invocation_observer(Box::new(RecordingObserver {
+        events: events.clone(),
+    }));
+    let mut process = track.start_process(None);
+
+    // Act
+    let result = process.invoke_snode(
+        SNodeRef::Scrypto(ScryptoActor::Component(SYSTEM_COMPONENT)),
+        "free_xrd".to_owned(),
+        vec![],
+    );
+
+    // Assert
+    assert!(result.is_ok());
+    let events = events.borrow();
+    assert_eq!(events.first(), Some(&Event::Enter("free_xrd".to_owned(), 0)));
+    assert!(events.contains(&Event::Enter("take_from_vault".to_owned(), 1)));
+    assert_eq!(events.last(), Some(&Event::Exit(0, true)));
+    let enters = events
+        .iter()
+        .filter(|e| matches!(e, Event::Enter(..)))
+        .count();
+    assert_eq!(enters * 2, events.len());
+}
+
+#[test]
+fn debug_messages_are_only_traced_at_debug_level() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+
+    // Act
+    let debug_process = track.start_process(Some(Level::Debug));
+    let debug_traced = debug_process.is_traced(Level::Debug);
+    let warn_process = track.start_process(Some(Level::Warn));
+    let warn_traced = (
+        warn_process.is_traced(Level::Debug),
+        warn_process.is_traced(Level::Warn),
+    );
+
+    // Assert
+    assert!(debug_traced);
+    assert_eq!(warn_traced, (false, true));
+}
@@ -5,6 +5,7 @@ use crate::test_runner::TestRunner;
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::InMemorySubstateStore;
 use scrypto::prelude::*;
+use scrypto::values::ScryptoValue;
 
 #[test]
 fn can_create_clone_and_drop_bucket_proof() {
@@ -111,6 +112,83 @@ fn can_create_clone_and_drop_vault_proof_by_amount() {
     receipt.result.expect("Should be okay.");
 }
 
+#[test]
+fn vault_proof_locks_part_of_the_vault_amount() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let resource_address =
+        test_runner.create_fungible_resource(100.into(), DIVISIBILITY_MAXIMUM, account);
+    let package_address = test_runner.publish_package("proof");
+    let component_address = test_runner.instantiate_component(
+        package_address,
+        "VaultProof",
+        "new",
+        vec![format!("3,{}", resource_address)],
+        account,
+        pk,
+        &sk,
+    );
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method_with_abi(
+            component_address,
+            "check_locked_and_available_amounts",
+            vec!["3".to_owned(), "1".to_owned()],
+            None,
+            &test_runner.export_abi_by_component(component_address),
+        )
+        .unwrap()
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    println!("{:?}", receipt);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+}
+
+#[test]
+fn vault_stays_locked_until_all_proof_clones_are_dropped() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let resource_address =
+        test_runner.create_fungible_resource(100.into(), DIVISIBILITY_MAXIMUM, account);
+    let package_address = test_runner.publish_package("proof");
+    let component_address = test_runner.instantiate_component(
+        package_address,
+        "VaultProof",
+        "new",
+        vec![format!("3,{}", resource_address)],
+        account,
+        pk,
+        &sk,
+    );
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method_with_abi(
+            component_address,
+            "clone_proof_and_check_locked_amount",
+            vec!["1".to_owned()],
+            None,
+            &test_runner.export_abi_by_component(component_address),
+        )
+        .unwrap()
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+}
+
 #[test]
 fn can_create_clone_and_drop_vault_proof_by_ids() {
     // Arrange
@@ -257,6 +335,86 @@ fn can_create_proof_from_account_and_pass_on() {
     receipt.result.expect("Should be okay.");
 }
 
+#[test]
+fn blueprint_can_read_resource_address_and_amount_of_proof() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let resource_address =
+        test_runner.create_fungible_resource(100.into(), DIVISIBILITY_MAXIMUM, account);
+    let package_address = test_runner.publish_package("proof");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function_with_abi(
+            package_address,
+            "Receiver",
+            "read_proof",
+            vec![format!("5,{}", resource_address)],
+            Some(account),
+            &test_runner.export_abi(package_address, "Receiver"),
+        )
+        .unwrap()
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    let expected = ScryptoValue::from_value(&(resource_address, Decimal::from(5)));
+    assert!(receipt.outputs.contains(&expected));
+}
+
+#[test]
+fn can_pass_vault_proof_to_another_component() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let resource_address =
+        test_runner.create_fungible_resource(100.into(), DIVISIBILITY_MAXIMUM, account);
+    let package_address = test_runner.publish_package("proof");
+    let component_address = test_runner.instantiate_component(
+        package_address,
+        "VaultProof",
+        "new",
+        vec![format!("3,{}", resource_address)],
+        account,
+        pk,
+        &sk,
+    );
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "ProofValidator",
+            "new",
+            args![resource_address, Decimal::from(2)],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let validator_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(
+            component_address,
+            "pass_vault_proof_to",
+            args![validator_address, Decimal::from(2)],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    println!("{:?}", receipt);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+}
+
 #[test]
 fn cant_move_restricted_proof() {
     // Arrange
@@ -499,3 +657,118 @@ fn can_create_auth_zone_proof_by_amount_from_non_fungibles() {
     // Assert
     assert!(receipt.result.is_ok());
 }
+
+#[test]
+fn can_create_worktop_proof_by_amount_spanning_two_buckets() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let resource_address =
+        test_runner.create_fungible_resource(100.into(), DIVISIBILITY_MAXIMUM, account);
+    let package_address = test_runner.publish_package("proof");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_amount(3.into(), resource_address, account)
+        .withdraw_from_account_by_amount(2.into(), resource_address, account)
+        .create_proof_from_worktop_by_amount(5.into(), resource_address, |builder, proof_id| {
+            builder.call_function(
+                package_address,
+                "Receiver",
+                "assert_amount",
+                args!(Proof(proof_id), Decimal::from(5), resource_address),
+            )
+        })
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    println!("{:?}", receipt);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+}
+
+#[test]
+fn blueprint_can_count_proofs_in_auth_zone() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let package_address = test_runner.publish_package("proof");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "BucketProof",
+            "count_auth_zone_proofs",
+            args![],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    let (count, _): (usize, Bucket) = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn blueprint_can_check_several_rules_against_one_proof_set() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let package_address = test_runner.publish_package("proof");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "BucketProof",
+            "check_rules_with_bucket_proof",
+            args![],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    let (results, _): (Vec<bool>, Bucket) = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    assert_eq!(results, vec![true, false, false]);
+}
+
+#[test]
+fn dropping_proof_returns_unit() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(RADIX_TOKEN, account)
+        .pop_from_auth_zone(|builder, proof_id| builder.drop_proof(proof_id))
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    let output = &receipt.outputs[2];
+    assert_eq!(output, &ScryptoValue::from_value(&()));
+    assert!(output.bucket_ids.is_empty());
+    assert!(output.proof_ids.is_empty());
+    assert!(output.vault_ids.is_empty());
+}
@@ -24,5 +24,24 @@ blueprint! {
 
             bucket
         }
+
+        pub fn count_auth_zone_proofs() -> (usize, Bucket) {
+            let bucket = ResourceBuilder::new_fungible().initial_supply(1);
+            let count = bucket.authorize(|| bucket.authorize(ComponentAuthZone::len));
+            (count, bucket)
+        }
+
+        pub fn check_rules_with_bucket_proof() -> (Vec<bool>, Bucket) {
+            let bucket = ResourceBuilder::new_fungible().initial_supply(1);
+            let proofs = vec![bucket.create_proof()];
+            let rules = [
+                rule!(require(bucket.resource_address())),
+                rule!(deny_all),
+                rule!(require(RADIX_TOKEN)),
+            ];
+            let results = AccessRule::check_all(&rules, &proofs);
+            proofs.into_iter().for_each(Proof::drop);
+            (results, bucket)
+        }
     }
 }
@@ -1,3 +1,4 @@
 pub mod bucket_proof;
+pub mod proof_validator;
 pub mod receiver;
 pub mod vault_proof;
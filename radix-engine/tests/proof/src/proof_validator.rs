@@ -0,0 +1,23 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct ProofValidator {
+        resource_address: ResourceAddress,
+        amount: Decimal,
+    }
+
+    impl ProofValidator {
+        pub fn new(resource_address: ResourceAddress, amount: Decimal) -> ComponentAddress {
+            Self {
+                resource_address,
+                amount,
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        pub fn validate(&self, proof: Proof) {
+            assert!(proof.contains_resource(self.amount, self.resource_address));
+        }
+    }
+}
@@ -19,5 +19,9 @@ blueprint! {
             assert_eq!(proof.non_fungible_ids(), ids);
             assert_eq!(proof.resource_address(), resource_address);
         }
+
+        pub fn read_proof(proof: Proof) -> (ResourceAddress, Decimal) {
+            (proof.resource_address(), proof.amount())
+        }
     }
 }
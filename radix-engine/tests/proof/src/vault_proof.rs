@@ -42,6 +42,37 @@ blueprint! {
             proof.drop();
         }
 
+        pub fn check_locked_and_available_amounts(
+            &self,
+            total_amount: Decimal,
+            proof_amount: Decimal,
+        ) {
+            let proof = self.vault.create_proof_by_amount(proof_amount);
+
+            assert_eq!(self.vault.amount(), total_amount);
+            assert_eq!(self.vault.locked_amount(), proof_amount);
+            assert_eq!(self.vault.available_amount(), total_amount - proof_amount);
+
+            proof.drop();
+
+            assert_eq!(self.vault.locked_amount(), Decimal::zero());
+            assert_eq!(self.vault.available_amount(), total_amount);
+        }
+
+        pub fn clone_proof_and_check_locked_amount(&self, proof_amount: Decimal) {
+            let proof = self.vault.create_proof_by_amount(proof_amount);
+            let clone = proof.clone_proof();
+
+            assert_eq!(proof.amount(), proof_amount);
+            assert_eq!(clone.amount(), proof_amount);
+
+            proof.drop();
+            assert_eq!(self.vault.locked_amount(), proof_amount);
+
+            clone.drop();
+            assert_eq!(self.vault.locked_amount(), Decimal::zero());
+        }
+
         pub fn create_clone_drop_vault_proof_by_ids(
             &self,
             total_ids: BTreeSet<NonFungibleId>,
@@ -64,6 +95,13 @@ blueprint! {
             });
         }
 
+        pub fn pass_vault_proof_to(&self, validator: ComponentAddress, amount: Decimal) {
+            let proof = self.vault.create_proof_by_amount(amount);
+            borrow_component!(validator).call::<()>("validate", args![proof]);
+
+            assert_eq!(self.vault.locked_amount(), Decimal::zero());
+        }
+
         pub fn receive_proof(_proof: Proof) {
             // auto dropped here
         }
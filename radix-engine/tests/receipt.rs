@@ -0,0 +1,165 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+use scrypto::values::ScryptoValue;
+
+fn run_balance_query() -> Receipt {
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut substate_store, false);
+    let (pk, sk, account) = executor.new_account();
+    let transaction = TransactionBuilder::new()
+        .call_method(account, "balance", args![RADIX_TOKEN])
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    executor.validate_and_execute(&transaction).unwrap()
+}
+
+#[test]
+fn identical_runs_have_no_diff() {
+    // Arrange
+    let receipt1 = run_balance_query();
+    let receipt2 = run_balance_query();
+
+    // Act
+    let diff = receipt1.diff(&receipt2);
+
+    // Assert
+    assert!(diff.is_empty());
+    assert_eq!(receipt1, receipt2);
+}
+
+#[test]
+fn altered_run_reports_differing_field() {
+    // Arrange
+    let receipt1 = run_balance_query();
+    let mut receipt2 = run_balance_query();
+    receipt2.outputs[0] = ScryptoValue::from_value(&Decimal::zero());
+
+    // Act
+    let diff = receipt1.diff(&receipt2);
+
+    // Assert
+    assert_eq!(diff.fields, vec![ReceiptField::Output(0)]);
+    assert_ne!(receipt1, receipt2);
+}
+
+#[test]
+fn mint_and_burn_are_reported_as_net_supply_change() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (token_address, mint_auth, burn_auth, _, _) = test_runner.create_restricted_token(account);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(mint_auth, account)
+        .create_proof_from_account(burn_auth, account)
+        .mint(100.into(), token_address)
+        .burn(30.into(), token_address)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    assert_eq!(
+        receipt.supply_changes.get(&token_address),
+        Some(&Decimal::from(70))
+    );
+}
+
+#[test]
+fn transfer_is_recorded_in_resource_movements() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_amount(dec!("100"), RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    assert!(receipt.resource_movements.iter().any(|movement| matches!(
+        movement,
+        ResourceMovement::Withdraw { resource_address, amount, .. }
+            if *resource_address == RADIX_TOKEN && *amount == dec!("100")
+    )));
+    assert!(receipt.resource_movements.iter().any(|movement| matches!(
+        movement,
+        ResourceMovement::Deposit { resource_address, amount, .. }
+            if *resource_address == RADIX_TOKEN && *amount == dec!("100")
+    )));
+}
+
+#[test]
+fn initial_supply_is_recorded_as_mint_movement() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .new_token_fixed(HashMap::new(), dec!("500"))
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    let token_address = receipt.new_resource_addresses[0];
+    assert!(receipt
+        .resource_movements
+        .contains(&ResourceMovement::Mint {
+            resource_address: token_address,
+            amount: dec!("500"),
+        }));
+}
+
+#[test]
+fn burn_is_recorded_in_resource_movements() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (token_address, mint_auth, burn_auth, _, _) = test_runner.create_restricted_token(account);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(mint_auth, account)
+        .create_proof_from_account(burn_auth, account)
+        .mint(100.into(), token_address)
+        .burn(30.into(), token_address)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    assert!(receipt
+        .resource_movements
+        .contains(&ResourceMovement::Burn {
+            resource_address: token_address,
+            amount: dec!("30"),
+        }));
+}
@@ -1,6 +1,8 @@
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::*;
-use radix_engine::model::ResourceManagerError;
+use radix_engine::model::{
+    BucketError, ResourceContainerError, ResourceManagerError, VaultError, WorktopError,
+};
 use radix_engine::transaction::*;
 use scrypto::prelude::*;
 
@@ -94,3 +96,181 @@ fn mint_too_much_should_fail() {
         RuntimeError::ResourceManagerError(ResourceManagerError::MaxMintAmountExceeded)
     );
 }
+
+#[test]
+fn burn_should_fail_if_burning_is_denied_to_all() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let (pk, sk, account) = executor.new_account();
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "resource")))
+        .unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "ResourceTest", "burn_with_burning_denied", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert!(matches!(
+        runtime_error,
+        RuntimeError::AuthorizationError { .. }
+    ));
+}
+
+#[test]
+fn blueprint_can_list_resources_created_in_transaction() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let (pk, sk, account) = executor.new_account();
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "resource")))
+        .unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(
+            package,
+            "ResourceTest",
+            "create_two_and_list_new_resources",
+            vec![],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    let (new_resources, _, _): (Vec<ResourceAddress>, Bucket, Bucket) =
+        scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    assert_eq!(new_resources.len(), 2);
+    assert_eq!(new_resources, receipt.new_resource_addresses);
+}
+
+fn create_indivisible_token(
+    executor: &mut TransactionExecutor<InMemorySubstateStore>,
+    package: PackageAddress,
+    account: ComponentAddress,
+) -> ResourceAddress {
+    let transaction = TransactionBuilder::new()
+        .call_function(
+            package,
+            "ResourceTest",
+            "create_fungible_and_mint",
+            args![0u8, dec!("10")],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    let (_, _, token_address): (Bucket, Bucket, ResourceAddress) =
+        scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    token_address
+}
+
+#[test]
+fn take_fractional_amount_from_vault_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let (pk, sk, account) = executor.new_account();
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "resource")))
+        .unwrap();
+    let token_address = create_indivisible_token(&mut executor, package, account);
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account_by_amount(dec!("1.5"), token_address, account)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert_eq!(
+        runtime_error,
+        RuntimeError::VaultError(VaultError::ResourceContainerError(
+            ResourceContainerError::InvalidAmount(dec!("1.5"), 0)
+        ))
+    );
+}
+
+#[test]
+fn take_fractional_amount_from_bucket_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let (pk, sk, account) = executor.new_account();
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "resource")))
+        .unwrap();
+    let token_address = create_indivisible_token(&mut executor, package, account);
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account_by_amount(dec!("10"), token_address, account)
+        .take_from_worktop(token_address, |builder, bucket_id| {
+            builder.call_function(
+                package,
+                "ResourceTest",
+                "take_from_bucket",
+                args![scrypto::resource::Bucket(bucket_id), dec!("1.5")],
+            )
+        })
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert_eq!(
+        runtime_error,
+        RuntimeError::BucketError(BucketError::ResourceContainerError(
+            ResourceContainerError::InvalidAmount(dec!("1.5"), 0)
+        ))
+    );
+}
+
+#[test]
+fn take_fractional_amount_from_worktop_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let (pk, sk, account) = executor.new_account();
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "resource")))
+        .unwrap();
+    let token_address = create_indivisible_token(&mut executor, package, account);
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .take_from_worktop_by_amount(dec!("1.5"), token_address, |builder, bucket_id| {
+            builder.call_method(
+                account,
+                "deposit",
+                args![scrypto::resource::Bucket(bucket_id)],
+            )
+        })
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert_eq!(
+        runtime_error,
+        RuntimeError::WorktopError(WorktopError::ResourceContainerError(
+            ResourceContainerError::InvalidAmount(dec!("1.5"), 0)
+        ))
+    );
+}
@@ -84,6 +84,33 @@ blueprint! {
             badge
         }
 
+        pub fn burn_with_burning_denied() -> Bucket {
+            let badge = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_NONE)
+                .initial_supply(1);
+            let resource_address = ResourceBuilder::new_fungible()
+                .mintable(rule!(require(badge.resource_address())), LOCKED)
+                .deny_all(ResourceMethod::Burn)
+                .no_initial_supply();
+            let resource_manager = borrow_resource_manager!(resource_address);
+            badge.authorize(|| {
+                let bucket: Bucket = resource_manager.mint(1);
+                resource_manager.burn(bucket)
+            });
+            badge
+        }
+
+        pub fn create_two_and_list_new_resources() -> (Vec<ResourceAddress>, Bucket, Bucket) {
+            let first = ResourceBuilder::new_fungible().initial_supply(1);
+            let second = ResourceBuilder::new_fungible().initial_supply(2);
+            (Runtime::new_resources_this_txn(), first, second)
+        }
+
+        pub fn take_from_bucket(mut bucket: Bucket, amount: Decimal) -> (Bucket, Bucket) {
+            let taken = bucket.take(amount);
+            (bucket, taken)
+        }
+
         pub fn update_resource_metadata() -> Bucket {
             let badge = ResourceBuilder::new_fungible()
                 .divisibility(DIVISIBILITY_NONE)
@@ -0,0 +1,113 @@
+use radix_engine::model::{
+    HardAuthRule, HardProofRule, HardResourceOrNonFungible, MethodAuthorization, ResourceManager,
+    ResourceManagerError,
+};
+use scrypto::prelude::*;
+
+#[test]
+fn fixed_supply_resource_is_not_mintable() {
+    // Arrange
+    let resource_manager = ResourceManager::new(
+        ResourceType::Fungible { divisibility: 18 },
+        HashMap::new(),
+        HashMap::new(),
+    )
+    .unwrap();
+
+    // Act
+    let mintable = resource_manager.mintable();
+    let burnable = resource_manager.burnable();
+
+    // Assert
+    assert!(!mintable);
+    assert!(!burnable);
+}
+
+#[test]
+fn resource_with_mint_rule_is_mintable() {
+    // Arrange
+    let mut auth = HashMap::new();
+    auth.insert(ResourceMethod::Mint, (rule!(allow_all), LOCKED));
+    auth.insert(ResourceMethod::Burn, (rule!(deny_all), MUTABLE(rule!(allow_all))));
+    let resource_manager =
+        ResourceManager::new(ResourceType::Fungible { divisibility: 18 }, HashMap::new(), auth)
+            .unwrap();
+
+    // Act
+    let mintable = resource_manager.mintable();
+    let burnable = resource_manager.burnable();
+
+    // Assert
+    assert!(mintable);
+    assert!(burnable);
+}
+
+#[test]
+fn auth_config_lists_distinct_mint_and_burn_rules() {
+    // Arrange
+    let mut auth = HashMap::new();
+    auth.insert(ResourceMethod::Mint, (rule!(allow_all), LOCKED));
+    auth.insert(ResourceMethod::Burn, (rule!(require(RADIX_TOKEN)), LOCKED));
+    let resource_manager =
+        ResourceManager::new(ResourceType::Fungible { divisibility: 18 }, HashMap::new(), auth)
+            .unwrap();
+
+    // Act
+    let auth_config = resource_manager.auth_config();
+
+    // Assert
+    assert_eq!(auth_config.get("Mint"), Some(&MethodAuthorization::AllowAll));
+    assert_eq!(
+        auth_config.get("Burn"),
+        Some(&MethodAuthorization::Protected(HardAuthRule::ProofRule(
+            HardProofRule::This(HardResourceOrNonFungible::Resource(RADIX_TOKEN))
+        )))
+    );
+}
+
+#[test]
+fn minting_up_to_max_supply_succeeds() {
+    // Arrange
+    let mut resource_manager = ResourceManager::new_with_max_supply(
+        ResourceType::Fungible { divisibility: 18 },
+        HashMap::new(),
+        HashMap::new(),
+        Some(10.into()),
+    )
+    .unwrap();
+
+    // Act
+    let first = resource_manager.mint_fungible(6.into(), RADIX_TOKEN);
+    let second = resource_manager.mint_fungible(4.into(), RADIX_TOKEN);
+
+    // Assert
+    assert!(first.is_ok());
+    assert!(second.is_ok());
+    assert_eq!(resource_manager.total_supply(), 10.into());
+    assert_eq!(resource_manager.max_supply(), Some(10.into()));
+}
+
+#[test]
+fn minting_past_max_supply_fails() {
+    // Arrange
+    let mut resource_manager = ResourceManager::new_with_max_supply(
+        ResourceType::Fungible { divisibility: 18 },
+        HashMap::new(),
+        HashMap::new(),
+        Some(10.into()),
+    )
+    .unwrap();
+    resource_manager
+        .mint_fungible(6.into(), RADIX_TOKEN)
+        .unwrap();
+
+    // Act
+    let straddling = resource_manager.mint_fungible(5.into(), RADIX_TOKEN);
+
+    // Assert
+    assert_eq!(
+        straddling.err(),
+        Some(ResourceManagerError::MaxSupplyExceeded)
+    );
+    assert_eq!(resource_manager.total_supply(), 6.into());
+}
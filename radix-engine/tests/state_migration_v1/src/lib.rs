@@ -0,0 +1,18 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Counter {
+        count: u32,
+    }
+
+    impl Counter {
+        pub fn new(count: u32) -> ComponentAddress {
+            Self { count }.instantiate().globalize()
+        }
+
+        pub fn increment(&mut self) -> u32 {
+            self.count += 1;
+            self.count
+        }
+    }
+}
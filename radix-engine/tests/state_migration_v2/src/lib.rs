@@ -0,0 +1,37 @@
+use scrypto::prelude::*;
+
+#[derive(TypeId, Decode)]
+struct CounterV1 {
+    count: u32,
+}
+
+blueprint! {
+    struct Counter {
+        count: u32,
+        label: String,
+    }
+
+    impl Counter {
+        pub fn new(count: u32) -> ComponentAddress {
+            Self {
+                count,
+                label: "new".to_owned(),
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        pub fn migrate_state(old_state: Vec<u8>) -> Vec<u8> {
+            let old: CounterV1 = scrypto_decode(&old_state).unwrap();
+            scrypto_encode(&Self {
+                count: old.count,
+                label: "migrated".to_owned(),
+            })
+        }
+
+        pub fn increment(&mut self) -> (u32, String) {
+            self.count += 1;
+            (self.count, self.label.clone())
+        }
+    }
+}
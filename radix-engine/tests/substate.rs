@@ -0,0 +1,249 @@
+use radix_engine::engine::Track;
+use radix_engine::ledger::*;
+use radix_engine::model::{Component, Package};
+use radix_engine::transaction::*;
+use sbor::{DecodeError, Type};
+use scrypto::prelude::*;
+
+/// The layout packages were stored with before versioning.
+#[derive(TypeId, Encode)]
+struct LegacyPackage {
+    code: Vec<u8>,
+    blueprints: HashMap<String, Type>,
+}
+
+/// The layout components were stored with before versioning.
+#[derive(TypeId, Encode)]
+struct LegacyComponent {
+    package_address: PackageAddress,
+    blueprint_name: String,
+    auths: Vec<AccessRules>,
+    state: Vec<u8>,
+}
+
+#[test]
+fn encoded_substate_is_prefixed_with_schema_version() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::new();
+    let address = ComponentAddress([1u8; 26]);
+
+    // Act
+    substate_store.put_encoded_substate(&address, &"value".to_owned(), (hash([]), 0));
+    let raw = substate_store.get_substate(&address).unwrap().value;
+    let decoded: Option<(String, _)> = substate_store.get_decoded_substate(&address).unwrap();
+
+    // Assert
+    assert_eq!(raw[0], SUBSTATE_SCHEMA_VERSION);
+    assert_eq!(&raw[1..], scrypto_encode(&"value".to_owned()).as_slice());
+    assert_eq!(decoded.map(|(value, _)| value), Some("value".to_owned()));
+}
+
+#[test]
+fn lazy_map_entry_is_prefixed_with_schema_version() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::new();
+    let address = ComponentAddress([1u8; 26]);
+    let lazy_map_id: LazyMapId = (hash([]), 1);
+    let key = scrypto_encode(&1u32);
+    let value = scrypto_encode(&"value".to_owned());
+
+    // Act
+    substate_store.put_encoded_grand_child_substate(
+        &address,
+        &lazy_map_id,
+        &key,
+        &value,
+        (hash([]), 0),
+    );
+    let mut child_key = scrypto_encode(&lazy_map_id);
+    child_key.extend(key.clone());
+    let raw = substate_store
+        .get_child_substate(&address, &child_key)
+        .unwrap()
+        .value;
+    let decoded = substate_store
+        .get_decoded_grand_child_substate(&address, &lazy_map_id, &key)
+        .unwrap();
+
+    // Assert
+    assert_eq!(raw, encode_substate_bytes(&value));
+    assert_eq!(decoded.map(|(value, _)| value), Some(value));
+}
+
+#[test]
+fn legacy_unprefixed_substate_is_decoded_as_v1() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::new();
+    let address = ComponentAddress([1u8; 26]);
+    substate_store.put_substate(
+        &address,
+        Substate {
+            value: scrypto_encode(&"legacy".to_owned()),
+            phys_id: (hash([]), 0),
+        },
+    );
+
+    // Act
+    let decoded: Option<(String, _)> = substate_store.get_decoded_substate(&address).unwrap();
+
+    // Assert
+    assert_eq!(decoded.map(|(value, _)| value), Some("legacy".to_owned()));
+}
+
+#[test]
+fn unknown_schema_version_is_rejected() {
+    // Arrange
+    let mut bytes = vec![0xffu8];
+    bytes.extend(scrypto_encode(&"value".to_owned()));
+
+    // Act
+    let result: Result<String, DecodeError> = decode_substate_value(&bytes);
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(DecodeError::InvalidType {
+            expected: Some(SUBSTATE_SCHEMA_VERSION),
+            actual: 0xff,
+        })
+    );
+}
+
+#[test]
+fn legacy_package_layout_is_decoded() {
+    // Arrange
+    let legacy = LegacyPackage {
+        code: vec![0u8, 1, 2],
+        blueprints: HashMap::new(),
+    };
+
+    // Act
+    let package: Package = decode_substate_value(&scrypto_encode(&legacy)).unwrap();
+
+    // Assert
+    assert_eq!(package.code(), &[0u8, 1, 2]);
+    assert_eq!(package.code_hash(), hash([0u8, 1, 2]));
+    assert_eq!(package.version(), 0);
+}
+
+#[test]
+fn legacy_component_layout_is_decoded() {
+    // Arrange
+    let legacy = LegacyComponent {
+        package_address: SYSTEM_PACKAGE,
+        blueprint_name: "System".to_owned(),
+        auths: Vec::new(),
+        state: vec![1u8],
+    };
+
+    // Act
+    let component: Component = decode_substate_value(&scrypto_encode(&legacy)).unwrap();
+
+    // Assert
+    assert_eq!(component.package_address(), SYSTEM_PACKAGE);
+    assert_eq!(component.state(), &[1u8]);
+    assert_eq!(component.package_version(), 0);
+    assert!(!component.is_frozen());
+}
+
+#[test]
+fn downed_substate_can_be_read_by_phys_id() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let address = vec![0xffu8, 0x01];
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+    track.put_raw_substate(address.clone(), vec![1u8]).unwrap();
+    track.commit();
+    let mut track = Track::new(&mut substate_store, hash([2u8]), Vec::new());
+    track.borrow_raw_substate_mut(&address).unwrap();
+    track.return_borrowed_raw_substate(&address, vec![2u8]);
+    let receipt = track.commit();
+    let downed = *receipt.down_substates.iter().next().unwrap();
+
+    // Act
+    let previous = substate_store.get_substate_by_phys_id(downed);
+
+    // Assert
+    assert_eq!(previous.map(|s| s.value), Some(vec![1u8]));
+    assert_eq!(
+        substate_store
+            .get_substate(&RawSubstateAddress(address))
+            .map(|s| s.value),
+        Some(vec![2u8])
+    );
+}
+
+#[test]
+fn only_the_latest_downed_substate_is_kept_by_phys_id() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let address = vec![0xffu8, 0x01];
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+    track.put_raw_substate(address.clone(), vec![1u8]).unwrap();
+    track.commit();
+    let mut downed = Vec::new();
+    for (i, value) in [2u8, 3u8].iter().enumerate() {
+        let mut track = Track::new(&mut substate_store, hash([i as u8 + 2]), Vec::new());
+        track.borrow_raw_substate_mut(&address).unwrap();
+        track.return_borrowed_raw_substate(&address, vec![*value]);
+        let receipt = track.commit();
+        downed.push(*receipt.down_substates.iter().next().unwrap());
+    }
+
+    // Act
+    let first = substate_store.get_substate_by_phys_id(downed[0]);
+    let second = substate_store.get_substate_by_phys_id(downed[1]);
+
+    // Assert
+    assert!(first.is_none());
+    assert_eq!(substate_store.get_address_by_phys_id(downed[0]), None);
+    assert_eq!(second.map(|s| s.value), Some(vec![2u8]));
+}
+
+#[test]
+fn staged_transactions_are_readable_before_commit() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut staging_store = StagingStore::new(&mut substate_store);
+    let mut executor = TransactionExecutor::new(&mut staging_store, false);
+
+    // Act
+    let (pk, sk, account) = executor.new_account();
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    let staged: Option<(Component, _)> = staging_store.get_decoded_substate(&account).unwrap();
+    let base: Option<(Component, _)> = staging_store.base().get_decoded_substate(&account).unwrap();
+    assert!(staged.is_some());
+    assert!(base.is_none());
+    let nonce = staging_store.get_nonce();
+    staging_store.commit().unwrap();
+    let committed: Option<(Component, _)> = substate_store.get_decoded_substate(&account).unwrap();
+    assert!(committed.is_some());
+    assert_eq!(substate_store.get_nonce(), nonce);
+}
+
+#[test]
+fn dropped_staging_store_leaves_base_untouched() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let nonce = substate_store.get_nonce();
+
+    // Act
+    let account = {
+        let mut staging_store = StagingStore::new(&mut substate_store);
+        let mut executor = TransactionExecutor::new(&mut staging_store, false);
+        executor.new_account().2
+    };
+
+    // Assert
+    let base: Option<(Component, _)> = substate_store.get_decoded_substate(&account).unwrap();
+    assert!(base.is_none());
+    assert_eq!(substate_store.get_nonce(), nonce);
+}
@@ -1,8 +1,10 @@
+use radix_engine::engine::{DanglingPolicy, ReentrantMethod};
 use radix_engine::ledger::*;
-use radix_engine::model::{Component, Receipt, SignedTransaction};
+use radix_engine::model::{Component, Package, Receipt, SignedTransaction};
 use radix_engine::transaction::*;
 use scrypto::abi;
 use scrypto::prelude::*;
+use scrypto::values::ScryptoValue;
 
 pub struct TestRunner<'l> {
     executor: TransactionExecutor<'l, InMemorySubstateStore>,
@@ -15,6 +17,26 @@ impl<'l> TestRunner<'l> {
         Self { executor }
     }
 
+    pub fn set_dangling_policy(&mut self, dangling_policy: DanglingPolicy) {
+        self.executor.set_dangling_policy(dangling_policy);
+    }
+
+    pub fn set_max_created_values(&mut self, max_created_values: usize) {
+        self.executor.set_max_created_values(max_created_values);
+    }
+
+    pub fn set_max_mint_batch_size(&mut self, max_mint_batch_size: usize) {
+        self.executor.set_max_mint_batch_size(max_mint_batch_size);
+    }
+
+    pub fn set_max_worktop_resources(&mut self, max_worktop_resources: usize) {
+        self.executor.set_max_worktop_resources(max_worktop_resources);
+    }
+
+    pub fn set_allowed_reentrant_methods(&mut self, allowed_reentrant_methods: Vec<ReentrantMethod>) {
+        self.executor.set_allowed_reentrant_methods(allowed_reentrant_methods);
+    }
+
     pub fn new_transaction_builder(&self) -> TransactionBuilder {
         TransactionBuilder::new()
     }
@@ -46,10 +68,67 @@ impl<'l> TestRunner<'l> {
         self.executor.validate_and_execute(transaction).unwrap()
     }
 
+    /// Runs a transaction and returns the state of a component before and after it,
+    /// read back from the substate the transaction downed and the one it put up.
+    pub fn inspect_substate_change(
+        &mut self,
+        transaction: &SignedTransaction,
+        component_address: ComponentAddress,
+    ) -> (ScryptoValue, ScryptoValue) {
+        let (_, down_id): (Component, (Hash, u32)) = self
+            .executor
+            .substate_store()
+            .get_decoded_substate(&component_address)
+            .unwrap()
+            .unwrap();
+        let receipt = self.validate_and_execute(transaction);
+        let commit_receipt = receipt.commit_receipt.expect("Should be committed.");
+        assert!(commit_receipt.down_substates.contains(&down_id));
+
+        let substate_store = self.executor.substate_store();
+        let before: Component = decode_substate_value(
+            &substate_store.get_substate_by_phys_id(down_id).unwrap().value,
+        )
+        .unwrap();
+        let (after, up_id): (Component, (Hash, u32)) = substate_store
+            .get_decoded_substate(&component_address)
+            .unwrap()
+            .unwrap();
+        assert!(commit_receipt.up_substates.contains(&up_id));
+
+        (
+            ScryptoValue::from_slice(before.state()).unwrap(),
+            ScryptoValue::from_slice(after.state()).unwrap(),
+        )
+    }
+
+    /// Runs a transaction and asserts that it leaves the state of a component unchanged.
+    ///
+    /// Components called by a transaction are always written back, so this compares the
+    /// state before and after rather than looking for a new substate.
+    pub fn assert_state_unchanged(
+        &mut self,
+        component_address: ComponentAddress,
+        transaction: &SignedTransaction,
+    ) {
+        let (before, after) = self.inspect_substate_change(transaction, component_address);
+        assert_eq!(
+            before, after,
+            "State of component {} was changed",
+            component_address
+        );
+    }
+
     pub fn publish_package(&mut self, name: &str) -> PackageAddress {
         self.executor.publish_package(&Self::compile(name)).unwrap()
     }
 
+    pub fn overwrite_package(&mut self, package_address: PackageAddress, name: &str) {
+        self.executor
+            .overwrite_package(package_address, Self::compile(name))
+            .unwrap()
+    }
+
     pub fn compile(name: &str) -> Vec<u8> {
         compile_package!(format!("./tests/{}", name))
     }
@@ -58,10 +137,27 @@ impl<'l> TestRunner<'l> {
         self.executor
             .substate_store()
             .get_decoded_substate(&component_address)
+            .unwrap()
             .map(|(component, _)| component)
             .unwrap()
     }
 
+    pub fn package(&self, package_address: PackageAddress) -> Package {
+        self.executor
+            .substate_store()
+            .get_decoded_substate(&package_address)
+            .unwrap()
+            .map(|(package, _)| package)
+            .unwrap()
+    }
+
+    pub fn put_component(&mut self, component_address: ComponentAddress, component: Component) {
+        let substate_store = self.executor.substate_store_mut();
+        let tx_hash = hash(substate_store.get_and_increase_nonce().to_le_bytes());
+        let mut id_gen = SubstateIdGenerator::new(tx_hash);
+        substate_store.put_encoded_substate(&component_address, &component, id_gen.next());
+    }
+
     pub fn export_abi(
         &self,
         package_address: PackageAddress,
@@ -269,7 +365,8 @@ macro_rules! assert_auth_error {
             RuntimeError::AuthorizationError {
                 authorization: _,
                 function: _,
-                error: ::radix_engine::model::MethodAuthorizationError::NotAuthorized
+                error: ::radix_engine::model::MethodAuthorizationError::NotAuthorized,
+                ..
             }
         ) {
             panic!("Expected auth error but got: {:?}", $error);
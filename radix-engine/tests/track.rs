@@ -0,0 +1,334 @@
+use radix_engine::engine::*;
+use radix_engine::errors::RuntimeError;
+use radix_engine::ledger::*;
+use scrypto::prelude::*;
+
+#[test]
+fn raw_substate_round_trips_unchanged() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let address = vec![0xffu8, 0x01, 0x02];
+    let value = vec![0xdeu8, 0xad, 0xbe, 0xef];
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+    track
+        .put_raw_substate(address.clone(), value.clone())
+        .unwrap();
+    track.commit();
+
+    // Act
+    let mut track = Track::new(&mut substate_store, hash([2u8]), Vec::new());
+    let borrowed = track.borrow_raw_substate_mut(&address).unwrap();
+    track.return_borrowed_raw_substate(&address, borrowed);
+    let loaded = track.get_raw_substate(&address).map(|v| v.to_vec());
+    track.commit();
+
+    // Assert
+    assert_eq!(loaded, Some(value.clone()));
+    assert_eq!(
+        substate_store
+            .get_substate(&RawSubstateAddress(address))
+            .map(|s| s.value),
+        Some(value)
+    );
+}
+
+#[test]
+fn raw_substate_does_not_collide_with_typed_substate() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let address = vec![0xffu8, 0x01, 0x02];
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+
+    // Act
+    track.put_raw_substate(address.clone(), vec![1u8]).unwrap();
+    track.commit();
+
+    // Assert
+    assert!(substate_store.get_substate(&address).is_none());
+    assert!(substate_store
+        .get_substate(&RawSubstateAddress(address))
+        .is_some());
+}
+
+#[test]
+fn raw_substate_with_version_like_prefix_is_stored_verbatim() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let address = vec![0xffu8, 0x03];
+    let value = vec![0xf2u8, 0x00, 0x01];
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+
+    // Act
+    track
+        .put_raw_substate(address.clone(), value.clone())
+        .unwrap();
+    track.commit();
+
+    // Assert
+    let mut track = Track::new(&mut substate_store, hash([2u8]), Vec::new());
+    assert_eq!(
+        track.get_raw_substate(&address).map(|v| v.to_vec()),
+        Some(value)
+    );
+}
+
+#[test]
+fn logs_past_the_limit_are_truncated() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+    track.set_log_limits(2, 1024);
+
+    // Act
+    for i in 0..5 {
+        track.add_log(Level::Info, NO_LOG_COMPONENT, format!("message {}", i));
+    }
+
+    // Assert
+    assert_eq!(
+        track.logs(),
+        &vec![
+            (Level::Info, NO_LOG_COMPONENT, "message 0".to_owned()),
+            (Level::Info, NO_LOG_COMPONENT, "message 1".to_owned()),
+            (
+                Level::Warn,
+                NO_LOG_COMPONENT,
+                LOGS_TRUNCATED_MESSAGE.to_owned()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn logs_past_the_byte_limit_are_truncated() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+    track.set_log_limits(1024, 12);
+
+    // Act
+    track.add_log(Level::Info, NO_LOG_COMPONENT, "0123456789".to_owned());
+    track.add_log(Level::Info, NO_LOG_COMPONENT, "0123456789".to_owned());
+    track.add_log(Level::Info, NO_LOG_COMPONENT, "0".to_owned());
+
+    // Assert
+    assert_eq!(
+        track.logs(),
+        &vec![
+            (Level::Info, NO_LOG_COMPONENT, "0123456789".to_owned()),
+            (
+                Level::Warn,
+                NO_LOG_COMPONENT,
+                LOGS_TRUNCATED_MESSAGE.to_owned()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn exhausted_id_allocator_returns_error() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+    track.set_id_allocator(IdAllocator::with_range(1024..1024));
+
+    // Act
+    let result = track.new_vault_id();
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(RuntimeError::IdAllocatorError(IdAllocatorError::OutOfID))
+    );
+}
+
+#[test]
+fn mutable_borrow_of_readonly_substate_is_rejected() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+    track.declare_readonly(&[RADIX_TOKEN]);
+
+    // Act
+    let read = track.get_resource_manager(&RADIX_TOKEN).unwrap().is_some();
+    let result = track.borrow_global_mut_resource_manager(RADIX_TOKEN);
+
+    // Assert
+    assert!(read);
+    assert_eq!(
+        result.err(),
+        Some(RuntimeError::ReadOnlyViolation(scrypto_encode(&RADIX_TOKEN)))
+    );
+}
+
+#[test]
+fn put_of_readonly_raw_substate_is_rejected() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let address = vec![0xffu8, 0x01, 0x02];
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+    track.declare_readonly(&[RawSubstateAddress(address.clone())]);
+
+    // Act
+    let result = track.put_raw_substate(address.clone(), vec![1u8]);
+
+    // Assert
+    assert_eq!(
+        result.err(),
+        Some(RuntimeError::ReadOnlyViolation(scrypto_encode(
+            &RawSubstateAddress(address)
+        )))
+    );
+}
+
+fn put_unknown_substate(
+    substate_store: &mut InMemorySubstateStore,
+    component_address: ComponentAddress,
+) -> Vec<u8> {
+    let value = vec![0xfeu8, 0xde, 0xad];
+    substate_store.put_substate(
+        &component_address,
+        Substate {
+            value: value.clone(),
+            phys_id: (hash([9u8]), 0),
+        },
+    );
+    value
+}
+
+#[test]
+fn unknown_substate_is_rejected_under_policy() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let component_address = ComponentAddress([2u8; 26]);
+    let value = put_unknown_substate(&mut substate_store, component_address);
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+    track.set_unknown_substate_policy(UnknownSubstatePolicy::Reject);
+
+    // Act
+    let result = track.borrow_global_mut_component(component_address);
+
+    // Assert
+    assert_eq!(
+        result.err(),
+        Some(RuntimeError::UnknownSubstate {
+            address: scrypto_encode(&component_address),
+            value,
+        })
+    );
+}
+
+#[test]
+fn unknown_substate_is_rejected_on_read_under_policy() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let component_address = ComponentAddress([2u8; 26]);
+    let value = put_unknown_substate(&mut substate_store, component_address);
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+    track.set_unknown_substate_policy(UnknownSubstatePolicy::Reject);
+
+    // Act
+    let result = track.get_component(component_address);
+
+    // Assert
+    assert_eq!(
+        result.err(),
+        Some(RuntimeError::UnknownSubstate {
+            address: scrypto_encode(&component_address),
+            value,
+        })
+    );
+}
+
+#[test]
+fn unknown_lazy_map_entry_is_rejected_under_policy() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let component_address = ComponentAddress([2u8; 26]);
+    let lazy_map_id: LazyMapId = (hash([3u8]), 1024);
+    let key = scrypto_encode(&1u32);
+    let value = vec![0xfeu8, 0xde, 0xad];
+    let mut child_key = scrypto_encode(&lazy_map_id);
+    child_key.extend(key.clone());
+    substate_store.put_child_substate(
+        &component_address,
+        &child_key,
+        Substate {
+            value: value.clone(),
+            phys_id: (hash([9u8]), 0),
+        },
+    );
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+    track.set_unknown_substate_policy(UnknownSubstatePolicy::Reject);
+
+    // Act
+    let result = track.get_lazy_map_entry(component_address, &lazy_map_id, &key);
+
+    // Assert
+    let mut address = scrypto_encode(&component_address);
+    address.extend(child_key);
+    assert_eq!(
+        result,
+        Err(RuntimeError::UnknownSubstate { address, value })
+    );
+}
+
+#[test]
+fn unknown_substate_is_rejected_by_default() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let component_address = ComponentAddress([2u8; 26]);
+    let value = put_unknown_substate(&mut substate_store, component_address);
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+
+    // Act
+    let result = track.borrow_global_mut_component(component_address);
+
+    // Assert
+    assert_eq!(
+        result.err(),
+        Some(RuntimeError::UnknownSubstate {
+            address: scrypto_encode(&component_address),
+            value,
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "Attempting to borrow unsupported value")]
+fn unknown_substate_panics_under_policy() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let component_address = ComponentAddress([2u8; 26]);
+    put_unknown_substate(&mut substate_store, component_address);
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+    track.set_unknown_substate_policy(UnknownSubstatePolicy::Panic);
+
+    // Act
+    let _ = track.borrow_global_mut_component(component_address);
+}
+
+#[test]
+#[cfg(feature = "unknown_substates_as_raw")]
+fn unknown_substate_is_loaded_as_raw_under_policy() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let component_address = ComponentAddress([2u8; 26]);
+    let value = put_unknown_substate(&mut substate_store, component_address);
+    let mut track = Track::new(&mut substate_store, hash([1u8]), Vec::new());
+    track.set_unknown_substate_policy(UnknownSubstatePolicy::LoadAsRaw);
+
+    // Act
+    let result = track.borrow_global_mut_component(component_address);
+
+    // Assert
+    let address = scrypto_encode(&component_address);
+    assert_eq!(
+        result.err(),
+        Some(RuntimeError::UnknownSubstate {
+            address: address.clone(),
+            value: value.clone(),
+        })
+    );
+    assert_eq!(track.get_unknown_substate(&address), Some(value.as_slice()));
+}
@@ -0,0 +1,258 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+#[cfg(feature = "sweep_dangling")]
+use radix_engine::engine::DanglingPolicy;
+use radix_engine::errors::RuntimeError;
+use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::{AuthZoneError, Condition, Instruction};
+use radix_engine::transaction::*;
+use sbor::Value;
+use scrypto::prelude::*;
+use scrypto::values::ScryptoValue;
+
+#[test]
+fn empty_transaction_is_a_successful_no_op() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .build_with_no_nonce()
+        .sign([]);
+
+    // Act
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    assert!(receipt.outputs.is_empty());
+    let commit_receipt = receipt.commit_receipt.expect("Should be committed.");
+    assert!(commit_receipt.down_substates.is_empty());
+    assert!(commit_receipt.up_substates.is_empty());
+}
+
+#[test]
+#[cfg(feature = "sweep_dangling")]
+fn dangling_bucket_is_swept_into_target_account() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (target_pk, target_sk, target_account) = test_runner.new_account();
+    test_runner.set_dangling_policy(DanglingPolicy::SweepTo(target_account));
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .take_from_worktop(RADIX_TOKEN, |builder, _| builder)
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+
+    // Act
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(target_account, "balance", args![RADIX_TOKEN])
+        .build(test_runner.get_nonce([target_pk]))
+        .sign([&target_sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    assert_eq!(
+        receipt.outputs[0],
+        ScryptoValue::from_value(&Decimal::from(2000000))
+    );
+}
+
+#[test]
+fn conditional_branch_takes_then_path_when_resource_is_on_worktop() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .conditional_branch(
+            Condition::WorktopContains {
+                resource_address: RADIX_TOKEN,
+            },
+            vec![Instruction::CallMethodWithAllResources {
+                component_address: account,
+                method: "deposit_batch".to_owned(),
+            }],
+            vec![Instruction::AssertWorktopContains {
+                resource_address: RADIX_TOKEN,
+            }],
+        )
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+
+    // Act
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    assert_eq!(receipt.outputs.len(), 2);
+    assert_eq!(
+        receipt.outputs[1].dom,
+        Value::Tuple {
+            elements: vec![
+                Value::Bool { value: true },
+                Value::Tuple {
+                    elements: vec![ScryptoValue::from_value(&()).dom],
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn conditional_branch_takes_else_path_when_resource_is_not_on_worktop() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .conditional_branch(
+            Condition::WorktopContains {
+                resource_address: RADIX_TOKEN,
+            },
+            vec![Instruction::AssertWorktopContains {
+                resource_address: RADIX_TOKEN,
+            }],
+            vec![Instruction::CallMethod {
+                component_address: account,
+                method: "balance".to_owned(),
+                args: args![RADIX_TOKEN],
+            }],
+        )
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+
+    // Act
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    assert_eq!(receipt.outputs.len(), 1);
+    assert_eq!(
+        receipt.outputs[0].dom,
+        Value::Tuple {
+            elements: vec![
+                Value::Bool { value: false },
+                Value::Tuple {
+                    elements: vec![ScryptoValue::from_value(&Decimal::from(1000000)).dom],
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn asserting_empty_auth_zone_fails_when_proof_is_left_behind() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(RADIX_TOKEN, account)
+        .assert_auth_zone_empty()
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_eq!(error, RuntimeError::AuthZoneError(AuthZoneError::NotEmpty));
+}
+
+#[test]
+fn asserting_empty_auth_zone_succeeds_after_clearing_it() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(RADIX_TOKEN, account)
+        .clear_auth_zone()
+        .assert_auth_zone_empty()
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+}
+
+#[test]
+fn failed_transaction_in_batch_does_not_stop_the_rest() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut substate_store, false);
+    let (pk, sk, account) = executor.new_account();
+    let nonce = executor.get_nonce([pk]);
+    let query = TransactionBuilder::new()
+        .call_method(account, "balance", args![RADIX_TOKEN])
+        .build(nonce)
+        .sign([&sk]);
+    let unauthorized_withdraw = TransactionBuilder::new()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(nonce)
+        .sign([]);
+    let transactions = vec![
+        query.validate().unwrap(),
+        unauthorized_withdraw.validate().unwrap(),
+        query.validate().unwrap(),
+    ];
+
+    // Act
+    let receipts = executor.execute_batch(transactions, BatchMode::ContinueOnError);
+
+    // Assert
+    assert_eq!(receipts.len(), 3);
+    assert!(receipts[0].result.is_ok());
+    assert!(receipts[0].commit_receipt.is_some());
+    assert!(receipts[1].result.is_err());
+    assert!(receipts[1].commit_receipt.is_none());
+    assert!(receipts[2].result.is_ok());
+    assert!(receipts[2].commit_receipt.is_some());
+    assert_eq!(executor.get_nonce([pk]), nonce + 2);
+}
+
+#[test]
+fn abort_fails_transaction_without_state_changes() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_amount(1.into(), RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .abort("Price moved")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        receipt.result,
+        Err(RuntimeError::ManifestAborted("Price moved".to_owned()))
+    );
+    assert!(receipt.commit_receipt.is_none());
+    assert!(receipt.balance_changes.per_account.is_empty());
+}
@@ -3,6 +3,7 @@ use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::*;
 use radix_engine::transaction::*;
 use scrypto::prelude::*;
+use scrypto::values::ScryptoValue;
 
 #[test]
 fn non_existent_vault_in_component_creation_should_fail() {
@@ -462,3 +463,91 @@ fn create_mutable_vault_with_get_resource_manager() {
     // Assert
     receipt.result.expect("Should be okay");
 }
+
+#[test]
+fn vault_resource_type_matches_its_resource_manager() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "vault")))
+        .unwrap();
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "VaultTest", "new_vault_with_get_amount", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let (component, _): (radix_engine::model::Component, _) = ledger
+        .get_decoded_substate(&component_address)
+        .unwrap()
+        .unwrap();
+    let vault_id = ScryptoValue::from_slice(component.state())
+        .unwrap()
+        .vault_ids
+        .into_iter()
+        .next()
+        .unwrap();
+    let (vault, _): (radix_engine::model::Vault, _) = ledger
+        .get_decoded_child_substate(&component_address, &vault_id)
+        .unwrap()
+        .unwrap();
+    let (resource_manager, _): (radix_engine::model::ResourceManager, _) = ledger
+        .get_decoded_substate(&vault.resource_address())
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert_eq!(vault.resource_type(), ResourceType::NonFungible);
+    assert_eq!(vault.resource_type(), resource_manager.resource_type());
+}
+
+fn up_substates_of_new_vault_into_vector(code: &[u8]) -> Vec<Vec<u8>> {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_package(code).unwrap();
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "VaultTest", "new_vault_into_vector", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    receipt
+        .commit_receipt
+        .unwrap()
+        .up_substates
+        .into_iter()
+        .map(|phys_id| ledger.get_substate_by_phys_id(phys_id).unwrap().value)
+        .collect()
+}
+
+#[test]
+fn vaults_are_put_up_in_the_same_order_across_runs() {
+    // Arrange
+    let code = compile_package!(format!("./tests/{}", "vault"));
+
+    // Act
+    let first = up_substates_of_new_vault_into_vector(&code);
+    let second = up_substates_of_new_vault_into_vector(&code);
+
+    // Assert
+    assert_eq!(first, second);
+}
+
+#[test]
+fn vault_contents_eq_compares_fungible_amount() {
+    // Arrange
+    let resource_address = ResourceAddress([1u8; 26]);
+
+    // Act
+    let vault = radix_engine::model::Vault::new(
+        radix_engine::model::ResourceContainer::new_fungible(resource_address, 18, 5.into()),
+    );
+
+    // Assert
+    assert!(vault.contents_eq(Some(5.into()), None));
+    assert!(vault.contents_eq(None, None));
+    assert!(!vault.contents_eq(Some(4.into()), None));
+    assert!(!vault.contents_eq(None, Some(BTreeSet::new())));
+}
@@ -0,0 +1,48 @@
+pub mod fixtures;
+
+use crate::fixtures::bucket_of;
+use radix_engine::model::*;
+use scrypto::prelude::*;
+
+#[test]
+fn putting_too_many_resources_should_fail() {
+    // Arrange
+    let mut worktop = Worktop::with_max_resources(1);
+    worktop.put(bucket_of(RADIX_TOKEN)).unwrap();
+
+    // Act
+    let same_resource = worktop.put(bucket_of(RADIX_TOKEN));
+    let new_resource = worktop.put(bucket_of(ECDSA_TOKEN));
+
+    // Assert
+    assert_eq!(same_resource, Ok(()));
+    assert_eq!(new_resource, Err(WorktopError::TooManyResources(1)));
+}
+
+#[test]
+fn put_batch_merges_buckets_of_the_same_resource() {
+    // Arrange
+    let mut worktop = Worktop::new();
+    let mut buckets = Vec::new();
+    for i in 0..10 {
+        if i % 2 == 0 {
+            buckets.push(bucket_of(RADIX_TOKEN));
+        } else {
+            buckets.push(bucket_of(ECDSA_TOKEN));
+        }
+    }
+
+    // Act
+    let result = worktop.put_batch(buckets);
+
+    // Assert
+    assert_eq!(result, Ok(()));
+    let resource_addresses: HashSet<ResourceAddress> =
+        worktop.resource_addresses().into_iter().collect();
+    assert_eq!(
+        resource_addresses,
+        HashSet::from([RADIX_TOKEN, ECDSA_TOKEN])
+    );
+    assert_eq!(worktop.total_amount(RADIX_TOKEN), 5.into());
+    assert_eq!(worktop.total_amount(ECDSA_TOKEN), 5.into());
+}
@@ -42,6 +42,20 @@ impl LocalComponent {
         let output: CreateComponentOutput = call_engine(CREATE_COMPONENT, input);
         output.component_address
     }
+
+    /// Globalizes this component at an address derived from the package address and `name`.
+    ///
+    /// Panics if a component with the same name already exists in this package.
+    pub fn globalize_with_name(self, name: &str) -> ComponentAddress {
+        let input = CreateNamedComponentInput {
+            name: name.to_owned(),
+            blueprint_name: self.blueprint_name,
+            state: self.state,
+            access_rules_list: self.access_rules_list,
+        };
+        let output: CreateComponentOutput = call_engine(CREATE_NAMED_COMPONENT, input);
+        output.component_address
+    }
 }
 
 /// Represents the state of a component.
@@ -84,6 +98,26 @@ impl Component {
         let _: PutComponentStateOutput = call_engine(PUT_COMPONENT_STATE, input);
     }
 
+    /// Freezes this component, so that all method calls fail except `unfreeze`.
+    ///
+    /// Only the component itself may freeze or unfreeze it.
+    pub fn freeze(&self) {
+        let input = SetComponentFrozenInput {
+            component_address: self.0,
+            frozen: true,
+        };
+        let _: SetComponentFrozenOutput = call_engine(SET_COMPONENT_FROZEN, input);
+    }
+
+    /// Unfreezes this component; must be called from its `unfreeze` method.
+    pub fn unfreeze(&self) {
+        let input = SetComponentFrozenInput {
+            component_address: self.0,
+            frozen: false,
+        };
+        let _: SetComponentFrozenOutput = call_engine(SET_COMPONENT_FROZEN, input);
+    }
+
     /// Returns the package ID of this component.
     pub fn package_address(&self) -> PackageAddress {
         let input = GetComponentInfoInput {
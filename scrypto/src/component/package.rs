@@ -1,7 +1,9 @@
 use sbor::*;
 
+use crate::abi;
 use crate::buffer::*;
 use crate::core::*;
+use crate::engine::{api::*, call_engine};
 use crate::misc::*;
 use crate::rust::borrow::ToOwned;
 use crate::rust::fmt;
@@ -27,6 +29,16 @@ impl Package {
 
         scrypto_decode(&output).unwrap()
     }
+
+    /// Returns the ABI of a blueprint in this package, if it exists.
+    pub fn blueprint_abi(&self, blueprint_name: &str) -> Option<abi::Blueprint> {
+        let input = GetBlueprintAbiInput {
+            package_address: self.0,
+            blueprint_name: blueprint_name.to_owned(),
+        };
+        let output: GetBlueprintAbiOutput = call_engine(GET_BLUEPRINT_ABI, input);
+        output.abi
+    }
 }
 
 //========
@@ -1,7 +1,7 @@
 use sbor::*;
 
-/// Represents the level of a log message.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, TypeId, Encode, Decode, Describe)]
+/// Represents the level of a log message, ordered from least to most verbose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, TypeId, Encode, Decode, Describe)]
 pub enum Level {
     Error,
     Warn,
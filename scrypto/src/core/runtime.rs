@@ -2,6 +2,7 @@ use crate::component::*;
 use crate::core::*;
 use crate::crypto::*;
 use crate::engine::{api::*, call_engine};
+use crate::resource::ResourceAddress;
 use crate::rust::borrow::ToOwned;
 use crate::rust::vec::Vec;
 
@@ -76,10 +77,32 @@ impl Runtime {
         output.transaction_hash
     }
 
+    /// Returns the public keys which signed the transaction.
+    pub fn transaction_signers() -> Vec<EcdsaPublicKey> {
+        let input = GetTransactionSignersInput {};
+        let output: GetTransactionSignersOutput = call_engine(GET_TRANSACTION_SIGNERS, input);
+        output.transaction_signers
+    }
+
     /// Returns the current epoch number.
     pub fn current_epoch() -> u64 {
         let input = GetCurrentEpochInput {};
         let output: GetCurrentEpochOutput = call_engine(GET_CURRENT_EPOCH, input);
         output.current_epoch
     }
+
+    /// Returns the addresses of all resources created so far in this transaction.
+    pub fn new_resources_this_txn() -> Vec<ResourceAddress> {
+        let input = GetNewResourcesInput {};
+        let output: GetNewResourcesOutput = call_engine(GET_NEW_RESOURCES, input);
+        output.resource_addresses
+    }
+
+    /// Returns the index of the transaction instruction which called the current blueprint,
+    /// or `None` if it was called by another blueprint.
+    pub fn instruction_index() -> Option<usize> {
+        let input = GetInstructionIndexInput {};
+        let output: GetInstructionIndexOutput = call_engine(GET_INSTRUCTION_INDEX, input);
+        output.instruction_index
+    }
 }
@@ -1,3 +1,4 @@
+use crate::abi;
 use crate::core::SNodeRef;
 use sbor::*;
 use scrypto::prelude::{AccessRule, AccessRules};
@@ -20,6 +21,12 @@ pub const GET_COMPONENT_INFO: u32 = 0x11;
 pub const GET_COMPONENT_STATE: u32 = 0x12;
 /// Update component state
 pub const PUT_COMPONENT_STATE: u32 = 0x13;
+/// Create a component at an address derived from its name
+pub const CREATE_NAMED_COMPONENT: u32 = 0x14;
+/// Freeze or unfreeze the running component
+pub const SET_COMPONENT_FROZEN: u32 = 0x15;
+/// Retrieve the ABI of a blueprint
+pub const GET_BLUEPRINT_ABI: u32 = 0x16;
 
 /// Create a lazy map
 pub const CREATE_LAZY_MAP: u32 = 0x20;
@@ -48,6 +55,14 @@ pub const GET_ACTOR: u32 = 0xf5;
 
 /// Check that an access rule is satisfied
 pub const CHECK_ACCESS_RULE: u32 = 0xf6;
+/// Retrieve the resources created in this transaction
+pub const GET_NEW_RESOURCES: u32 = 0xf7;
+/// Check several access rules against the same proofs
+pub const CHECK_ACCESS_RULES: u32 = 0xf8;
+/// Retrieve the index of the transaction instruction being executed
+pub const GET_INSTRUCTION_INDEX: u32 = 0xf9;
+/// Retrieve the public keys which signed the transaction
+pub const GET_TRANSACTION_SIGNERS: u32 = 0xfa;
 
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct InvokeSNodeInput {
@@ -77,6 +92,14 @@ pub struct CreateComponentOutput {
     pub component_address: ComponentAddress,
 }
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct CreateNamedComponentInput {
+    pub name: String,
+    pub blueprint_name: String,
+    pub state: Vec<u8>,
+    pub access_rules_list: Vec<AccessRules>,
+}
+
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct GetComponentInfoInput {
     pub component_address: ComponentAddress,
@@ -104,6 +127,26 @@ pub struct PutComponentStateInput {
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct PutComponentStateOutput {}
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct SetComponentFrozenInput {
+    pub component_address: ComponentAddress,
+    pub frozen: bool,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct SetComponentFrozenOutput {}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct GetBlueprintAbiInput {
+    pub package_address: PackageAddress,
+    pub blueprint_name: String,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct GetBlueprintAbiOutput {
+    pub abi: Option<abi::Blueprint>,
+}
+
 //==========
 // LazyMap
 //==========
@@ -192,6 +235,11 @@ pub struct GetTransactionHashOutput {
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct GetTransactionSignersInput {}
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct GetTransactionSignersOutput {
+    pub transaction_signers: Vec<EcdsaPublicKey>,
+}
+
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct GenerateUuidInput {}
 
@@ -200,6 +248,22 @@ pub struct GenerateUuidOutput {
     pub uuid: u128,
 }
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct GetNewResourcesInput {}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct GetNewResourcesOutput {
+    pub resource_addresses: Vec<ResourceAddress>,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct GetInstructionIndexInput {}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct GetInstructionIndexOutput {
+    pub instruction_index: Option<usize>,
+}
+
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct GetActorInput {}
 
@@ -218,3 +282,14 @@ pub struct CheckAccessRuleInput {
 pub struct CheckAccessRuleOutput {
     pub is_authorized: bool
 }
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct CheckAccessRulesInput {
+    pub access_rules: Vec<AccessRule>,
+    pub proof_ids: Vec<ProofId>,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct CheckAccessRulesOutput {
+    pub results: Vec<bool>,
+}
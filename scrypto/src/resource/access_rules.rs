@@ -27,6 +27,11 @@ impl AccessRules {
             .unwrap_or(&self.default_auth)
     }
 
+    /// Returns whether a rule was set explicitly for the method, rather than falling back to the default.
+    pub fn contains(&self, method_name: &str) -> bool {
+        self.method_auth.contains_key(method_name)
+    }
+
     pub fn get_default(&self) -> &AccessRule {
         &self.default_auth
     }
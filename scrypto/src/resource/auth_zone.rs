@@ -37,6 +37,22 @@ impl ComponentAuthZone {
         scrypto_decode(&output.rtn).unwrap()
     }
 
+    /// Returns the number of proofs in the auth zone.
+    pub fn len() -> usize {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::AuthZoneRef,
+            function: "len".to_string(),
+            args: args![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Checks if the auth zone is empty.
+    pub fn is_empty() -> bool {
+        Self::len() == 0
+    }
+
     pub fn create_proof(resource_address: ResourceAddress) -> Proof {
         let input = InvokeSNodeInput {
             snode_ref: SNodeRef::AuthZoneRef,
@@ -6,6 +6,7 @@ mod non_fungible;
 mod non_fungible_address;
 mod non_fungible_data;
 mod non_fungible_id;
+mod non_fungible_predicate;
 mod proof;
 mod proof_rule;
 mod resource_builder;
@@ -23,6 +24,7 @@ pub use non_fungible::NonFungible;
 pub use non_fungible_address::{NonFungibleAddress, ParseNonFungibleAddressError};
 pub use non_fungible_data::NonFungibleData;
 pub use non_fungible_id::{NonFungibleId, ParseNonFungibleIdError};
+pub use non_fungible_predicate::NonFungiblePredicate;
 pub use proof::{ParseProofError, Proof};
 pub use proof_rule::{
     require, require_all_of, require_amount, require_any_of, require_n_of, AccessRuleNode,
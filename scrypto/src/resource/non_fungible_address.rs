@@ -92,6 +92,14 @@ impl FromStr for NonFungibleAddress {
     type Err = ParseNonFungibleAddressError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((resource_address, non_fungible_id)) = s.split_once(':') {
+            let resource_address = ResourceAddress::from_str(resource_address)
+                .map_err(|_| ParseNonFungibleAddressError::InvalidResourceDefId)?;
+            let non_fungible_id = NonFungibleId::from_str(non_fungible_id)
+                .map_err(|_| ParseNonFungibleAddressError::InvalidNonFungibleId)?;
+            return Ok(Self::new(resource_address, non_fungible_id));
+        }
+
         let bytes =
             hex::decode(s).map_err(|_| ParseNonFungibleAddressError::InvalidHex(s.to_owned()))?;
         if bytes.get(0) != Some(&3u8) {
@@ -103,9 +111,9 @@ impl FromStr for NonFungibleAddress {
 
 impl fmt::Display for NonFungibleAddress {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        // Note that if the non-fungible ID is empty, the non-fungible address won't be distinguishable from resource address.
-        // TODO: figure out what's best for the users
-        write!(f, "{}", hex::encode(combine(3, &self.to_vec())))
+        // The `resource:id` form stays distinguishable from a resource address even if the
+        // non-fungible ID is empty. The legacy hex form is still accepted by `from_str`.
+        write!(f, "{}:{}", self.resource_address, self.non_fungible_id)
     }
 }
 
@@ -127,10 +135,28 @@ mod tests {
         let public_key = private_key.public_key();
         let auth_address =
             NonFungibleAddress::new(ECDSA_TOKEN, NonFungibleId::from_bytes(public_key.to_vec()));
-        let s1 = auth_address.to_string();
-        let auth_address2 = NonFungibleAddress::from_str(&s1).unwrap();
-        let s2 = auth_address2.to_string();
-        assert_eq!(s1, expected);
-        assert_eq!(s2, expected);
+        let auth_address2 = NonFungibleAddress::from_str(expected).unwrap();
+        assert_eq!(auth_address2, auth_address);
+    }
+
+    #[test]
+    fn test_non_fungible_address_display() {
+        let address = NonFungibleAddress::new(ECDSA_TOKEN, NonFungibleId::from_u32(7));
+        let s = address.to_string();
+        assert_eq!(s, format!("{}:{}", ECDSA_TOKEN, NonFungibleId::from_u32(7)));
+        assert_eq!(NonFungibleAddress::from_str(&s), Ok(address));
+    }
+
+    #[test]
+    fn test_non_fungible_address_malformed_composite_string() {
+        let id = NonFungibleId::from_u32(7);
+        assert_eq!(
+            NonFungibleAddress::from_str(&format!("03zz:{}", id)),
+            Err(ParseNonFungibleAddressError::InvalidResourceDefId)
+        );
+        assert_eq!(
+            NonFungibleAddress::from_str(&format!("{}:zz", ECDSA_TOKEN)),
+            Err(ParseNonFungibleAddressError::InvalidNonFungibleId)
+        );
     }
 }
@@ -1,6 +1,7 @@
 use sbor::{describe::*, *};
 
 use crate::rust::vec::Vec;
+use crate::values::ScryptoValue;
 
 /// Represents the data structure of a non-fungible.
 pub trait NonFungibleData {
@@ -9,6 +10,17 @@ pub trait NonFungibleData {
     where
         Self: Sized;
 
+    /// Decodes `Self` from already parsed immutable and mutable parts, failing on malformed data.
+    fn try_from_scrypto_values(
+        immutable_data: &ScryptoValue,
+        mutable_data: &ScryptoValue,
+    ) -> Result<Self, DecodeError>
+    where
+        Self: Sized,
+    {
+        Self::decode(&immutable_data.raw, &mutable_data.raw)
+    }
+
     /// Returns the serialization of the immutable data part.
     fn immutable_data(&self) -> Vec<u8>;
 
@@ -21,3 +33,58 @@ pub trait NonFungibleData {
     /// Returns the schema of the mutable data.
     fn mutable_data_schema() -> Type;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::{scrypto_decode, scrypto_encode};
+    use crate::rust::string::String;
+
+    #[derive(Debug, PartialEq)]
+    struct Sample {
+        id: u32,
+        name: String,
+    }
+
+    impl NonFungibleData for Sample {
+        fn decode(immutable_data: &[u8], mutable_data: &[u8]) -> Result<Self, DecodeError> {
+            Ok(Self {
+                id: scrypto_decode(immutable_data)?,
+                name: scrypto_decode(mutable_data)?,
+            })
+        }
+
+        fn immutable_data(&self) -> Vec<u8> {
+            scrypto_encode(&self.id)
+        }
+
+        fn mutable_data(&self) -> Vec<u8> {
+            scrypto_encode(&self.name)
+        }
+
+        fn immutable_data_schema() -> Type {
+            Type::U32
+        }
+
+        fn mutable_data_schema() -> Type {
+            Type::String
+        }
+    }
+
+    #[test]
+    fn test_try_from_scrypto_values() {
+        let sample = Sample {
+            id: 5,
+            name: String::from("Sample"),
+        };
+        let immutable_data = ScryptoValue::from_slice(&sample.immutable_data()).unwrap();
+        let mutable_data = ScryptoValue::from_slice(&sample.mutable_data()).unwrap();
+        assert_eq!(
+            Sample::try_from_scrypto_values(&immutable_data, &mutable_data),
+            Ok(sample)
+        );
+
+        let malformed = ScryptoValue::from_slice(&scrypto_encode(&String::from("5"))).unwrap();
+        assert!(Sample::try_from_scrypto_values(&malformed, &mutable_data).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use sbor::any::Value;
+use sbor::*;
+
+/// A comparison of an integer field in non-fungible data against a constant.
+#[derive(Debug, Clone, Copy, TypeId, Encode, Decode, Describe, Eq, PartialEq)]
+pub enum NonFungiblePredicate {
+    Equal(i128),
+    NotEqual(i128),
+    LessThan(i128),
+    LessThanOrEqual(i128),
+    GreaterThan(i128),
+    GreaterThanOrEqual(i128),
+}
+
+impl NonFungiblePredicate {
+    /// Returns whether the value satisfies this predicate.
+    ///
+    /// Values which aren't integers, or don't fit into `i128`, never match.
+    pub fn matches(&self, value: &Value) -> bool {
+        let value = match value {
+            Value::I8 { value } => *value as i128,
+            Value::I16 { value } => *value as i128,
+            Value::I32 { value } => *value as i128,
+            Value::I64 { value } => *value as i128,
+            Value::I128 { value } => *value,
+            Value::U8 { value } => *value as i128,
+            Value::U16 { value } => *value as i128,
+            Value::U32 { value } => *value as i128,
+            Value::U64 { value } => *value as i128,
+            Value::U128 { value } => match i128::try_from(*value) {
+                Ok(value) => value,
+                Err(_) => return false,
+            },
+            _ => return false,
+        };
+
+        match self {
+            NonFungiblePredicate::Equal(other) => value == *other,
+            NonFungiblePredicate::NotEqual(other) => value != *other,
+            NonFungiblePredicate::LessThan(other) => value < *other,
+            NonFungiblePredicate::LessThanOrEqual(other) => value <= *other,
+            NonFungiblePredicate::GreaterThan(other) => value > *other,
+            NonFungiblePredicate::GreaterThanOrEqual(other) => value >= *other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integer_comparison() {
+        let predicate = NonFungiblePredicate::GreaterThanOrEqual(5);
+        assert!(predicate.matches(&Value::U8 { value: 5 }));
+        assert!(predicate.matches(&Value::I64 { value: 7 }));
+        assert!(!predicate.matches(&Value::U32 { value: 4 }));
+    }
+
+    #[test]
+    fn test_non_integer_never_matches() {
+        let predicate = NonFungiblePredicate::NotEqual(0);
+        assert!(!predicate.matches(&Value::Bool { value: true }));
+        assert!(!predicate.matches(&Value::U128 { value: u128::MAX }));
+    }
+}
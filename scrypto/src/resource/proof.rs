@@ -20,6 +20,15 @@ pub struct Proof(pub ProofId);
 
 impl Clone for Proof {
     fn clone(&self) -> Self {
+        self.clone_proof()
+    }
+}
+
+impl Proof {
+    /// Creates another proof over the same locked resources.
+    ///
+    /// Both proofs can be dropped independently; the resources stay locked until all are dropped.
+    pub fn clone_proof(&self) -> Proof {
         let input = InvokeSNodeInput {
             snode_ref: SNodeRef::ProofRef(self.0),
             function: "clone".to_string(),
@@ -28,9 +37,7 @@ impl Clone for Proof {
         let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
         scrypto_decode(&output.rtn).unwrap()
     }
-}
 
-impl Proof {
     /// Whether this proof includes an ownership proof of any of the given resource.
     pub fn contains(&self, resource_address: ResourceAddress) -> bool {
         self.resource_address() == resource_address
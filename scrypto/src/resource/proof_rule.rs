@@ -1,4 +1,7 @@
-use crate::engine::api::{CheckAccessRuleInput, CheckAccessRuleOutput, CHECK_ACCESS_RULE};
+use crate::engine::api::{
+    CheckAccessRuleInput, CheckAccessRuleOutput, CheckAccessRulesInput, CheckAccessRulesOutput,
+    CHECK_ACCESS_RULE, CHECK_ACCESS_RULES,
+};
 use crate::engine::call_engine;
 use crate::resource::AccessRuleNode::{AllOf, AnyOf};
 use crate::resource::*;
@@ -363,6 +366,17 @@ impl AccessRule {
 
         output.is_authorized
     }
+
+    /// Checks each of the given rules against the same proofs, in a single engine call.
+    pub fn check_all(rules: &[AccessRule], proofs: &[Proof]) -> Vec<bool> {
+        let input = CheckAccessRulesInput {
+            access_rules: rules.to_vec(),
+            proof_ids: proofs.iter().map(|proof| proof.0).collect(),
+        };
+        let output: CheckAccessRulesOutput = call_engine(CHECK_ACCESS_RULES, input);
+
+        output.results
+    }
 }
 
 #[macro_export]
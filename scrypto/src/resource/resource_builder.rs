@@ -17,11 +17,13 @@ pub struct FungibleResourceBuilder {
     divisibility: u8,
     metadata: HashMap<String, String>,
     authorization: HashMap<ResourceMethod, (AccessRule, Mutability)>,
+    max_supply: Option<Decimal>,
 }
 
 pub struct NonFungibleResourceBuilder {
     metadata: HashMap<String, String>,
     authorization: HashMap<ResourceMethod, (AccessRule, Mutability)>,
+    max_supply: Option<Decimal>,
 }
 
 impl ResourceBuilder {
@@ -42,6 +44,7 @@ impl FungibleResourceBuilder {
             divisibility: DIVISIBILITY_MAXIMUM,
             metadata: HashMap::new(),
             authorization: HashMap::new(),
+            max_supply: None,
         }
     }
 
@@ -63,6 +66,25 @@ impl FungibleResourceBuilder {
         self
     }
 
+    /// Caps the total supply of this resource; minting beyond the cap fails.
+    pub fn max_supply<T: Into<Decimal>>(&mut self, max_supply: T) -> &mut Self {
+        self.max_supply = Some(max_supply.into());
+        self
+    }
+
+    /// Allows anyone to perform the given method, locking that rule.
+    pub fn allow_all(&mut self, method: ResourceMethod) -> &mut Self {
+        self.authorization
+            .insert(method, (rule!(allow_all), LOCKED));
+        self
+    }
+
+    /// Denies everyone the given method, locking that rule.
+    pub fn deny_all(&mut self, method: ResourceMethod) -> &mut Self {
+        self.authorization.insert(method, (rule!(deny_all), LOCKED));
+        self
+    }
+
     pub fn mintable(&mut self, method_auth: AccessRule, mutability: Mutability) -> &mut Self {
         self.authorization.insert(Mint, (method_auth, mutability));
         self
@@ -126,13 +148,14 @@ impl FungibleResourceBuilder {
             authorization.insert(Withdraw, (rule!(allow_all), LOCKED));
         }
 
-        resource_system().new_resource(
+        resource_system().new_resource_with_max_supply(
             ResourceType::Fungible {
                 divisibility: self.divisibility,
             },
             self.metadata.clone(),
             authorization,
             mint_params,
+            self.max_supply,
         )
     }
 }
@@ -142,6 +165,7 @@ impl NonFungibleResourceBuilder {
         Self {
             metadata: HashMap::new(),
             authorization: HashMap::new(),
+            max_supply: None,
         }
     }
 
@@ -154,6 +178,25 @@ impl NonFungibleResourceBuilder {
         self
     }
 
+    /// Caps the total supply of this resource; minting beyond the cap fails.
+    pub fn max_supply<T: Into<Decimal>>(&mut self, max_supply: T) -> &mut Self {
+        self.max_supply = Some(max_supply.into());
+        self
+    }
+
+    /// Allows anyone to perform the given method, locking that rule.
+    pub fn allow_all(&mut self, method: ResourceMethod) -> &mut Self {
+        self.authorization
+            .insert(method, (rule!(allow_all), LOCKED));
+        self
+    }
+
+    /// Denies everyone the given method, locking that rule.
+    pub fn deny_all(&mut self, method: ResourceMethod) -> &mut Self {
+        self.authorization.insert(method, (rule!(deny_all), LOCKED));
+        self
+    }
+
     pub fn mintable(&mut self, method_auth: AccessRule, mutability: Mutability) -> &mut Self {
         self.authorization.insert(Mint, (method_auth, mutability));
         self
@@ -236,11 +279,12 @@ impl NonFungibleResourceBuilder {
             authorization.insert(Withdraw, (rule!(allow_all), LOCKED));
         }
 
-        resource_system().new_resource(
+        resource_system().new_resource_with_max_supply(
             ResourceType::NonFungible,
             self.metadata.clone(),
             authorization,
             mint_params,
+            self.max_supply,
         )
     }
 }
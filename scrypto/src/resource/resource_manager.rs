@@ -3,11 +3,13 @@ use sbor::*;
 use crate::args;
 use crate::buffer::scrypto_decode;
 use crate::core::SNodeRef;
+use crate::crypto::hash;
 use crate::engine::{api::*, call_engine};
 use crate::math::*;
 use crate::misc::*;
 use crate::resource::*;
 use crate::rust::borrow::ToOwned;
+use crate::rust::collections::BTreeSet;
 use crate::rust::collections::HashMap;
 use crate::rust::fmt;
 use crate::rust::str::FromStr;
@@ -233,6 +235,17 @@ impl ResourceManager {
         scrypto_decode(&output.rtn).unwrap()
     }
 
+    /// Returns the maximum supply of this resource, if capped.
+    pub fn max_supply(&self) -> Option<Decimal> {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "get_max_supply".to_string(),
+            args: args![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Returns the data of a non-fungible unit, both the immutable and mutable parts.
     ///
     /// # Panics
@@ -261,6 +274,53 @@ impl ResourceManager {
         let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
     }
 
+    /// Returns the version of a non-fungible unit, which is increased by every update of its
+    /// mutable part.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible resource or the specified non-fungible is not found.
+    pub fn get_non_fungible_version(&self, id: &NonFungibleId) -> u64 {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "get_non_fungible_version".to_string(),
+            args: args![id.clone()],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Updates the mutable part of a non-fungible unit, if its version is still the expected one.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible resource, the specified non-fungible is not found or
+    /// its version has changed.
+    pub fn update_non_fungible_data_if_version<T: NonFungibleData>(
+        &self,
+        id: &NonFungibleId,
+        expected_version: u64,
+        new_data: T,
+    ) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "update_non_fungible_mutable_data_if_version".to_string(),
+            args: args![id.clone(), expected_version, new_data.mutable_data()],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
+    /// Burns the non-fungible units with the given ids from a bucket, and returns the rest.
+    ///
+    /// Fails without burning anything if any of the ids isn't in the bucket.
+    pub fn burn_non_fungibles(&self, bucket: Bucket, ids: &BTreeSet<NonFungibleId>) -> Bucket {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "burn_non_fungibles".to_string(),
+            args: args![bucket, ids.clone()],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Checks if non-fungible unit, with certain key exists or not.
     ///
     pub fn non_fungible_exists(&self, id: &NonFungibleId) -> bool {
@@ -294,6 +354,7 @@ pub enum ParseResourceAddressError {
     InvalidHex(String),
     InvalidLength(usize),
     InvalidPrefix,
+    InvalidChecksum,
 }
 
 #[cfg(not(feature = "alloc"))]
@@ -334,6 +395,24 @@ scrypto_type!(ResourceAddress, ScryptoType::ResourceAddress, Vec::new());
 //======
 
 // Before Bech32, we use a fixed prefix for text representation.
+//
+// A checksummed form, which appends the first 4 bytes of the hash of the prefixed address,
+// is also accepted so that typos in user input are caught.
+
+const CHECKSUM_LENGTH: usize = 4;
+
+fn checksum(prefixed: &[u8]) -> Vec<u8> {
+    hash(prefixed).to_vec()[..CHECKSUM_LENGTH].to_vec()
+}
+
+impl ResourceAddress {
+    /// Returns the text representation of this address, with checksum appended.
+    pub fn to_checksummed_string(&self) -> String {
+        let mut bytes = combine(3, &self.0);
+        bytes.extend(checksum(&bytes));
+        hex::encode(bytes)
+    }
+}
 
 impl FromStr for ResourceAddress {
     type Err = ParseResourceAddressError;
@@ -344,7 +423,17 @@ impl FromStr for ResourceAddress {
         if bytes.get(0) != Some(&3u8) {
             return Err(ParseResourceAddressError::InvalidPrefix);
         }
-        Self::try_from(&bytes[1..])
+        match bytes.len() {
+            27 => Self::try_from(&bytes[1..]),
+            31 => {
+                let (prefixed, sum) = bytes.split_at(27);
+                if checksum(prefixed) != sum {
+                    return Err(ParseResourceAddressError::InvalidChecksum);
+                }
+                Self::try_from(&prefixed[1..])
+            }
+            _ => Err(ParseResourceAddressError::InvalidLength(bytes.len() - 1)),
+        }
     }
 }
 
@@ -359,3 +448,46 @@ impl fmt::Debug for ResourceAddress {
         write!(f, "{}", self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::RADIX_TOKEN;
+
+    #[test]
+    fn test_resource_address_from_str() {
+        let plain = RADIX_TOKEN.to_string();
+        let checksummed = RADIX_TOKEN.to_checksummed_string();
+        assert_eq!(ResourceAddress::from_str(&plain), Ok(RADIX_TOKEN));
+        assert_eq!(ResourceAddress::from_str(&checksummed), Ok(RADIX_TOKEN));
+    }
+
+    #[test]
+    fn test_resource_address_invalid_length() {
+        let s = &RADIX_TOKEN.to_string()[..50];
+        assert_eq!(
+            ResourceAddress::from_str(s),
+            Err(ParseResourceAddressError::InvalidLength(24))
+        );
+    }
+
+    #[test]
+    fn test_resource_address_invalid_prefix() {
+        let s = format!("02{}", &RADIX_TOKEN.to_string()[2..]);
+        assert_eq!(
+            ResourceAddress::from_str(&s),
+            Err(ParseResourceAddressError::InvalidPrefix)
+        );
+    }
+
+    #[test]
+    fn test_resource_address_invalid_checksum() {
+        let mut s = RADIX_TOKEN.to_checksummed_string();
+        let last = if s.ends_with('0') { "1" } else { "0" };
+        s.replace_range(s.len() - 1.., last);
+        assert_eq!(
+            ResourceAddress::from_str(&s),
+            Err(ParseResourceAddressError::InvalidChecksum)
+        );
+    }
+}
@@ -2,6 +2,7 @@ use crate::args;
 use crate::buffer::scrypto_decode;
 use crate::core::SNodeRef;
 use crate::engine::{api::*, call_engine};
+use crate::math::*;
 use crate::resource::*;
 use crate::rust::collections::HashMap;
 use crate::rust::string::String;
@@ -44,11 +45,29 @@ impl ResourceSystem {
         metadata: HashMap<String, String>,
         authorization: HashMap<ResourceMethod, (AccessRule, Mutability)>,
         mint_params: Option<MintParams>,
+    ) -> (ResourceAddress, Option<Bucket>) {
+        self.new_resource_with_max_supply(resource_type, metadata, authorization, mint_params, None)
+    }
+
+    /// Creates a resource whose total supply may never exceed `max_supply`, if set.
+    pub fn new_resource_with_max_supply(
+        &mut self,
+        resource_type: ResourceType,
+        metadata: HashMap<String, String>,
+        authorization: HashMap<ResourceMethod, (AccessRule, Mutability)>,
+        mint_params: Option<MintParams>,
+        max_supply: Option<Decimal>,
     ) -> (ResourceAddress, Option<Bucket>) {
         let input = InvokeSNodeInput {
             snode_ref: SNodeRef::ResourceStatic,
             function: "create".to_string(),
-            args: args![resource_type, metadata, authorization, mint_params],
+            args: args![
+                resource_type,
+                metadata,
+                authorization,
+                mint_params,
+                max_supply
+            ],
         };
         let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
         scrypto_decode(&output.rtn).unwrap()
@@ -91,6 +91,25 @@ impl Vault {
         scrypto_decode(&output.rtn).unwrap()
     }
 
+    /// Takes all non-fungibles from this vault whose mutable data field at `path` satisfies
+    /// the predicate. The comparison is done by the engine, without loading the data into wasm.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible vault or the path doesn't exist in the data schema.
+    pub fn take_non_fungibles_where<T: NonFungibleData>(
+        &mut self,
+        path: SchemaPath,
+        predicate: NonFungiblePredicate,
+    ) -> Bucket {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::VaultRef(self.0),
+            function: "take_non_fungibles_from_vault_where".to_string(),
+            args: args![T::mutable_data_schema(), path, predicate],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Creates an ownership proof of this vault.
     pub fn create_proof(&self) -> Proof {
         let input = InvokeSNodeInput {
@@ -143,6 +162,28 @@ impl Vault {
         scrypto_decode(&output.rtn).unwrap()
     }
 
+    /// Returns the amount of resources locked by proofs within this vault.
+    pub fn locked_amount(&self) -> Decimal {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::VaultRef(self.0),
+            function: "get_vault_locked_amount".to_string(),
+            args: vec![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Returns the amount of resources within this vault that are not locked by proofs.
+    pub fn available_amount(&self) -> Decimal {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::VaultRef(self.0),
+            function: "get_vault_available_amount".to_string(),
+            args: vec![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Returns the resource address.
     pub fn resource_address(&self) -> ResourceAddress {
         let input = InvokeSNodeInput {
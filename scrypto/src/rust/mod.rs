@@ -3,6 +3,8 @@ extern crate alloc;
 #[cfg(feature = "alloc")]
 pub use alloc::borrow;
 #[cfg(feature = "alloc")]
+pub use alloc::boxed;
+#[cfg(feature = "alloc")]
 pub use alloc::fmt;
 #[cfg(feature = "alloc")]
 pub use alloc::format;
@@ -30,6 +32,8 @@ pub use core::ptr;
 #[cfg(not(feature = "alloc"))]
 pub use std::borrow;
 #[cfg(not(feature = "alloc"))]
+pub use std::boxed;
+#[cfg(not(feature = "alloc"))]
 pub use std::cell;
 #[cfg(not(feature = "alloc"))]
 pub use std::convert;
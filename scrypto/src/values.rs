@@ -1,5 +1,6 @@
 use sbor::type_id::*;
 use sbor::{any::*, *};
+use sbor::describe::Fields;
 use sbor::path::{MutableSborPath, SborPath};
 
 use crate::buffer::*;
@@ -119,6 +120,16 @@ impl ScryptoValue {
     ) -> String {
         ScryptoValueFormatter::format_value(&self.dom, bucket_ids, proof_ids)
     }
+
+    /// Formats this value over multiple lines, indenting each nesting level by `indent` spaces.
+    pub fn pretty(&self, indent: usize) -> String {
+        ScryptoValueFormatter::format_value_pretty(&self.dom, None, indent, 0)
+    }
+
+    /// Formats this value over multiple lines, naming struct and enum fields after the given type.
+    pub fn pretty_with_type(&self, ty: &Type, indent: usize) -> String {
+        ScryptoValueFormatter::format_value_pretty(&self.dom, Some(ty), indent, 0)
+    }
 }
 
 impl fmt::Debug for ScryptoValue {
@@ -368,6 +379,146 @@ impl ScryptoValueFormatter {
         }
     }
 
+    /// Formats a value over multiple lines, using the type, if any, to name fields.
+    pub fn format_value_pretty(
+        value: &Value,
+        ty: Option<&Type>,
+        indent: usize,
+        depth: usize,
+    ) -> String {
+        match value {
+            Value::Struct { fields } => {
+                let (name, field_types) = match ty {
+                    Some(Type::Struct { name, fields }) => (name.as_str(), Some(fields)),
+                    _ => ("Struct", None),
+                };
+                Self::format_fields_pretty(name, fields, field_types, indent, depth)
+            }
+            Value::Enum { name, fields } => {
+                let field_types = match ty {
+                    Some(Type::Enum { variants, .. }) => {
+                        variants.iter().find(|v| &v.name == name).map(|v| &v.fields)
+                    }
+                    _ => None,
+                };
+                Self::format_fields_pretty(name, fields, field_types, indent, depth)
+            }
+            Value::Option { value } => match (value.borrow(), ty) {
+                (Some(x), Some(Type::Option { value })) => format!(
+                    "Some({})",
+                    Self::format_value_pretty(x, Some(value), indent, depth)
+                ),
+                (Some(x), _) => format!(
+                    "Some({})",
+                    Self::format_value_pretty(x, None, indent, depth)
+                ),
+                (None, _) => "None".to_string(),
+            },
+            Value::Tuple { elements } => {
+                let entries = elements
+                    .iter()
+                    .enumerate()
+                    .map(|(i, e)| {
+                        let element_type = match ty {
+                            Some(Type::Tuple { elements }) => elements.get(i),
+                            _ => None,
+                        };
+                        Self::format_value_pretty(e, element_type, indent, depth + 1)
+                    })
+                    .collect();
+                Self::format_block("Tuple(", ")", entries, indent, depth)
+            }
+            Value::Array {
+                element_type_id,
+                elements,
+            }
+            | Value::Vec {
+                element_type_id,
+                elements,
+            } if *element_type_id != TYPE_U8 => {
+                let element_type = match ty {
+                    Some(Type::Array { element, .. }) | Some(Type::Vec { element }) => {
+                        Some(element.as_ref())
+                    }
+                    _ => None,
+                };
+                let entries = elements
+                    .iter()
+                    .map(|e| Self::format_value_pretty(e, element_type, indent, depth + 1))
+                    .collect();
+                let open = match value {
+                    Value::Array { .. } => {
+                        format!("Array<{}>(", Self::format_type_id(*element_type_id))
+                    }
+                    _ => format!("Vec<{}>(", Self::format_type_id(*element_type_id)),
+                };
+                Self::format_block(&open, ")", entries, indent, depth)
+            }
+            _ => Self::format_value(value, &HashMap::new(), &HashMap::new()),
+        }
+    }
+
+    fn format_fields_pretty(
+        name: &str,
+        fields: &[Value],
+        field_types: Option<&Fields>,
+        indent: usize,
+        depth: usize,
+    ) -> String {
+        match field_types {
+            Some(Fields::Named { named }) if named.len() == fields.len() => {
+                let entries = named
+                    .iter()
+                    .zip(fields)
+                    .map(|((n, t), v)| {
+                        format!(
+                            "{}: {}",
+                            n,
+                            Self::format_value_pretty(v, Some(t), indent, depth + 1)
+                        )
+                    })
+                    .collect();
+                Self::format_block(&format!("{} {{", name), "}", entries, indent, depth)
+            }
+            _ => {
+                let entries = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| {
+                        let field_type = match field_types {
+                            Some(Fields::Unnamed { unnamed }) => unnamed.get(i),
+                            _ => None,
+                        };
+                        Self::format_value_pretty(v, field_type, indent, depth + 1)
+                    })
+                    .collect();
+                Self::format_block(&format!("{}(", name), ")", entries, indent, depth)
+            }
+        }
+    }
+
+    fn format_block(
+        open: &str,
+        close: &str,
+        entries: Vec<String>,
+        indent: usize,
+        depth: usize,
+    ) -> String {
+        if entries.is_empty() {
+            return format!("{}{}", open, close);
+        }
+        let mut buf = String::from(open);
+        buf.push('\n');
+        for entry in entries {
+            buf.push_str(&" ".repeat(indent * (depth + 1)));
+            buf.push_str(&entry);
+            buf.push_str(",\n");
+        }
+        buf.push_str(&" ".repeat(indent * depth));
+        buf.push_str(close);
+        buf
+    }
+
     pub fn format_type_id(type_id: u8) -> String {
         if let Some(ty) = ScryptoType::from_id(type_id) {
             return ty.name();
@@ -511,4 +662,32 @@ mod tests {
             )
         );
     }
+
+    #[derive(TypeId, Encode, Decode, Describe)]
+    struct Inner {
+        amount: u32,
+    }
+
+    #[derive(TypeId, Encode, Decode, Describe)]
+    struct Outer {
+        name: String,
+        inner: Inner,
+    }
+
+    #[test]
+    fn pretty_value_should_show_field_names_and_indentation() {
+        let value = ScryptoValue::from_value(&Outer {
+            name: "test".to_string(),
+            inner: Inner { amount: 5 },
+        });
+
+        assert_eq!(
+            value.pretty_with_type(&Outer::describe(), 2),
+            "Outer {\n  name: \"test\",\n  inner: Inner {\n    amount: 5u32,\n  },\n}"
+        );
+        assert_eq!(
+            value.pretty(2),
+            "Struct(\n  \"test\",\n  Struct(\n    5u32,\n  ),\n)"
+        );
+    }
 }
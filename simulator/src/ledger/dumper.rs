@@ -1,20 +1,27 @@
 #![allow(unused_must_use)]
 use colored::*;
+use radix_engine::engine::CommitReceipt;
 use radix_engine::ledger::*;
 use radix_engine::model::*;
+use sbor::DecodeError;
 use scrypto::engine::types::*;
 use scrypto::rust::collections::HashSet;
 use scrypto::values::*;
 use std::collections::VecDeque;
 
+use crate::ledger::{decode_substate, substate_kind};
 use crate::utils::*;
 
+/// The default number of non-fungibles shown per vault.
+pub const DEFAULT_MAX_NON_FUNGIBLES_SHOWN: usize = 100;
+
 /// Represents an error when displaying an entity.
 #[derive(Debug, Clone)]
 pub enum DisplayError {
     PackageNotFound,
     ComponentNotFound,
     ResourceManagerNotFound,
+    InvalidSubstate(DecodeError),
 }
 
 /// Dump a package into console.
@@ -25,6 +32,7 @@ pub fn dump_package<T: SubstateStore, O: std::io::Write>(
 ) -> Result<(), DisplayError> {
     let package: Option<Package> = substate_store
         .get_decoded_substate(&package_address)
+        .map_err(DisplayError::InvalidSubstate)?
         .map(|(package, _)| package);
     match package {
         Some(b) => {
@@ -40,6 +48,7 @@ pub fn dump_package<T: SubstateStore, O: std::io::Write>(
                 "Code size".green().bold(),
                 b.code().len()
             );
+            writeln!(output, "{}: {}", "Code hash".green().bold(), b.code_hash());
             Ok(())
         }
         None => Err(DisplayError::PackageNotFound),
@@ -47,13 +56,17 @@ pub fn dump_package<T: SubstateStore, O: std::io::Write>(
 }
 
 /// Dump a component into console.
+///
+/// At most `max_non_fungibles_shown` non-fungibles are listed per vault.
 pub fn dump_component<T: SubstateStore + QueryableSubstateStore, O: std::io::Write>(
     component_address: ComponentAddress,
     substate_store: &T,
+    max_non_fungibles_shown: usize,
     output: &mut O,
 ) -> Result<(), DisplayError> {
     let component: Option<Component> = substate_store
         .get_decoded_substate(&component_address)
+        .map_err(DisplayError::InvalidSubstate)?
         .map(|(component, _)| component);
     match component {
         Some(c) => {
@@ -81,7 +94,19 @@ pub fn dump_component<T: SubstateStore + QueryableSubstateStore, O: std::io::Wri
 
             let state = c.state();
             let state_data = ScryptoValue::from_slice(state).unwrap();
-            writeln!(output, "{}: {}", "State".green().bold(), state_data);
+            let package: Option<Package> = substate_store
+                .get_decoded_substate(&c.package_address())
+                .map_err(DisplayError::InvalidSubstate)?
+                .map(|(package, _)| package);
+            // Field names come from the blueprint schema, if the package still has it
+            let state_text = match package
+                .as_ref()
+                .and_then(|p| p.load_blueprint_schema(c.blueprint_name()).ok())
+            {
+                Some(schema) => state_data.pretty_with_type(schema, 2),
+                None => state_data.pretty(2),
+            };
+            writeln!(output, "{}: {}", "State".green().bold(), state_text);
 
             // Find all vaults owned by the component, assuming a tree structure.
             let mut vaults_found: HashSet<VaultId> = state_data.vault_ids.iter().cloned().collect();
@@ -95,7 +120,13 @@ pub fn dump_component<T: SubstateStore + QueryableSubstateStore, O: std::io::Wri
             }
 
             // Dump resources
-            dump_resources(component_address, &vaults_found, substate_store, output)
+            dump_resources(
+                component_address,
+                &vaults_found,
+                substate_store,
+                max_non_fungibles_shown,
+                output,
+            )
         }
         None => Err(DisplayError::ComponentNotFound),
     }
@@ -109,7 +140,9 @@ fn dump_lazy_map<T: SubstateStore + QueryableSubstateStore, O: std::io::Write>(
 ) -> Result<(Vec<LazyMapId>, Vec<VaultId>), DisplayError> {
     let mut referenced_maps = Vec::new();
     let mut referenced_vaults = Vec::new();
-    let map = substate_store.get_lazy_map_entries(component_address, lazy_map_id);
+    let map = substate_store
+        .get_lazy_map_entries(component_address, lazy_map_id)
+        .map_err(DisplayError::InvalidSubstate)?;
     writeln!(
         output,
         "{}: {:?}{:?}",
@@ -117,7 +150,9 @@ fn dump_lazy_map<T: SubstateStore + QueryableSubstateStore, O: std::io::Write>(
         component_address,
         lazy_map_id
     );
-    for (last, (k, v)) in map.iter().identify_last() {
+    let mut entries: Vec<(&Vec<u8>, &Vec<u8>)> = map.iter().collect();
+    entries.sort();
+    for (last, (k, v)) in entries.into_iter().identify_last() {
         let k_validated = ScryptoValue::from_slice(k).unwrap();
         let v_validated = ScryptoValue::from_slice(v).unwrap();
         writeln!(
@@ -137,12 +172,14 @@ fn dump_resources<T: SubstateStore, O: std::io::Write>(
     component_address: ComponentAddress,
     vaults: &HashSet<VaultId>,
     substate_store: &T,
+    max_non_fungibles_shown: usize,
     output: &mut O,
 ) -> Result<(), DisplayError> {
     writeln!(output, "{}:", "Resources".green().bold());
     for (last, vault_id) in vaults.iter().identify_last() {
         let vault: Vault = substate_store
             .get_decoded_child_substate(&component_address, vault_id)
+            .map_err(DisplayError::InvalidSubstate)?
             .unwrap()
             .0;
 
@@ -150,6 +187,7 @@ fn dump_resources<T: SubstateStore, O: std::io::Write>(
         let resource_address = vault.resource_address();
         let resource_manager: ResourceManager = substate_store
             .get_decoded_substate(&resource_address)
+            .map_err(DisplayError::InvalidSubstate)?
             .map(|(resource, _)| resource)
             .unwrap();
         writeln!(
@@ -169,11 +207,13 @@ fn dump_resources<T: SubstateStore, O: std::io::Write>(
                 .map(|symbol| format!(", symbol: \"{}\"", symbol))
                 .unwrap_or(String::new()),
         );
-        if matches!(resource_manager.resource_type(), ResourceType::NonFungible) {
+        if matches!(vault.resource_type(), ResourceType::NonFungible) {
             let ids = vault.total_ids().unwrap();
-            for (inner_last, id) in ids.iter().identify_last() {
+            let hidden = ids.len().saturating_sub(max_non_fungibles_shown);
+            for (inner_last, id) in ids.iter().take(max_non_fungibles_shown).identify_last() {
                 let non_fungible: Option<NonFungible> = substate_store
                     .get_decoded_child_substate(&resource_address, id)
+                    .map_err(DisplayError::InvalidSubstate)?
                     .unwrap()
                     .0;
 
@@ -186,13 +226,22 @@ fn dump_resources<T: SubstateStore, O: std::io::Write>(
                         output,
                         "{}  {} NonFungible {{ id: {}, immutable_data: {}, mutable_data: {} }}",
                         if last { " " } else { "│" },
-                        list_item_prefix(inner_last),
+                        list_item_prefix(inner_last && hidden == 0),
                         id,
                         immutable_data,
                         mutable_data
                     );
                 }
             }
+            if hidden > 0 {
+                writeln!(
+                    output,
+                    "{}  {} … and {} more",
+                    if last { " " } else { "│" },
+                    list_item_prefix(true),
+                    hidden
+                );
+            }
         }
     }
     Ok(())
@@ -206,6 +255,7 @@ pub fn dump_resource_manager<T: SubstateStore, O: std::io::Write>(
 ) -> Result<(), DisplayError> {
     let resource_manager: Option<ResourceManager> = substate_store
         .get_decoded_substate(&resource_address)
+        .map_err(DisplayError::InvalidSubstate)?
         .map(|(resource, _)| resource);
     match resource_manager {
         Some(r) => {
@@ -236,8 +286,177 @@ pub fn dump_resource_manager<T: SubstateStore, O: std::io::Write>(
                 "Total Supply".green().bold(),
                 r.total_supply()
             );
+            let auth_config = r.auth_config();
+            writeln!(output, "{}: {}", "Auth".green().bold(), auth_config.len());
+            for (last, (method, auth)) in auth_config.iter().identify_last() {
+                writeln!(
+                    output,
+                    "{} {}: {:?}",
+                    list_item_prefix(last),
+                    method.green().bold(),
+                    auth
+                );
+            }
             Ok(())
         }
         None => Err(DisplayError::ResourceManagerNotFound),
     }
 }
+
+/// Dump the substate operations of a commit receipt into console.
+///
+/// Each operation is listed with the kind of the substate it touched, and ups with a summary
+/// of the written value.
+pub fn dump_substate_operations<T: QueryableSubstateStore, O: std::io::Write>(
+    commit_receipt: &CommitReceipt,
+    substate_store: &T,
+    output: &mut O,
+) {
+    let mut down_substates: Vec<(Hash, u32)> =
+        commit_receipt.down_substates.iter().cloned().collect();
+    down_substates.sort();
+    for phys_id in down_substates {
+        let kind = substate_store
+            .get_address_by_phys_id(phys_id)
+            .and_then(|address| substate_kind(&address));
+        match kind {
+            Some(kind) => writeln!(output, "{} {:?}", "Down".red().bold(), kind),
+            None => writeln!(output, "{} {:?}", "Down".red().bold(), phys_id),
+        };
+    }
+
+    for phys_id in &commit_receipt.up_substates {
+        let address = substate_store.get_address_by_phys_id(*phys_id);
+        let substate = substate_store.get_substate_by_phys_id(*phys_id);
+        match (address, substate) {
+            (Some(address), Some(substate)) => {
+                let summary = decode_substate(&address, &substate.value)
+                    .unwrap_or_else(|e| format!("{:?}", e));
+                writeln!(output, "{} {}", "Up".green().bold(), summary)
+            }
+            _ => writeln!(output, "{} {:?}", "Up".green().bold(), phys_id),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radix_engine::transaction::*;
+    use scrypto::constants::{ECDSA_TOKEN, RADIX_TOKEN};
+    use scrypto::crypto::hash;
+    use scrypto::resource::NonFungibleId;
+    use scrypto::rust::collections::BTreeSet;
+
+    #[test]
+    fn test_dump_resources_truncates_non_fungibles() {
+        let mut substate_store = InMemorySubstateStore::with_bootstrap();
+        let mut id_gen = SubstateIdGenerator::new(hash([]));
+        let component_address = ComponentAddress([1u8; 26]);
+        let vault_id: VaultId = (hash([]), 1024);
+        let ids: BTreeSet<NonFungibleId> = (0..1000).map(NonFungibleId::from_u32).collect();
+        for id in &ids {
+            let non_fungible = Some(NonFungible::new(
+                scrypto::buffer::scrypto_encode(&()),
+                scrypto::buffer::scrypto_encode(&()),
+            ));
+            substate_store.put_encoded_child_substate(
+                &ECDSA_TOKEN,
+                id,
+                &non_fungible,
+                id_gen.next(),
+            );
+        }
+        let vault = Vault::new(ResourceContainer::new_non_fungible(ECDSA_TOKEN, ids));
+        substate_store.put_encoded_child_substate(
+            &component_address,
+            &vault_id,
+            &vault,
+            id_gen.next(),
+        );
+
+        let mut output = Vec::new();
+        let vaults: HashSet<VaultId> = [vault_id].into_iter().collect();
+        dump_resources(component_address, &vaults, &substate_store, 10, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.matches("NonFungible {").count(), 10);
+        assert!(output.contains("… and 990 more"));
+    }
+
+    #[test]
+    fn test_dump_lazy_map_is_independent_of_insertion_order() {
+        let component_address = ComponentAddress([1u8; 26]);
+        let lazy_map_id: LazyMapId = (hash([]), 1024);
+        let dump = |keys: Vec<u32>| {
+            let mut substate_store = InMemorySubstateStore::new();
+            let mut id_gen = SubstateIdGenerator::new(hash([]));
+            for key in keys {
+                substate_store.put_encoded_grand_child_substate(
+                    &component_address,
+                    &lazy_map_id,
+                    &scrypto::buffer::scrypto_encode(&key),
+                    &scrypto::buffer::scrypto_encode(&key),
+                    id_gen.next(),
+                );
+            }
+            let mut output = Vec::new();
+            dump_lazy_map(
+                component_address,
+                &lazy_map_id,
+                &substate_store,
+                &mut output,
+            )
+            .unwrap();
+            output
+        };
+
+        let ascending = dump((0..20).collect());
+        let descending = dump((0..20).rev().collect());
+
+        assert_eq!(ascending, descending);
+    }
+
+    #[test]
+    fn test_dump_component_shows_state_field_names() {
+        let mut substate_store = InMemorySubstateStore::with_bootstrap();
+        let mut executor = TransactionExecutor::new(&mut substate_store, false);
+        let (_, _, account) = executor.new_account();
+
+        let mut output = Vec::new();
+        dump_component(account, &substate_store, 10, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Account {"));
+        assert!(output.contains("vaults: "));
+    }
+
+    #[test]
+    fn test_dump_substate_operations_of_transfer() {
+        let mut substate_store = InMemorySubstateStore::with_bootstrap();
+        let mut executor = TransactionExecutor::new(&mut substate_store, false);
+        let (pk, sk, sender) = executor.new_account();
+        let (_, _, receiver) = executor.new_account();
+        let transaction = TransactionBuilder::new()
+            .withdraw_from_account(RADIX_TOKEN, sender)
+            .call_method_with_all_resources(receiver, "deposit_batch")
+            .build(executor.get_nonce([pk]))
+            .sign([&sk]);
+        let receipt = executor.validate_and_execute(&transaction).unwrap();
+        let commit_receipt = receipt.commit_receipt.expect("Should be committed.");
+
+        let mut output = Vec::new();
+        dump_substate_operations(&commit_receipt, &substate_store, &mut output);
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            output.lines().count(),
+            commit_receipt.down_substates.len() + commit_receipt.up_substates.len()
+        );
+        assert!(output
+            .lines()
+            .any(|line| line.contains("Down") && line.ends_with("Vault")));
+        assert!(output.contains(&format!("Vault {{ component_address: {}", sender)));
+        assert!(output.contains(&format!("Vault {{ component_address: {}", receiver)));
+    }
+}
@@ -1,5 +1,7 @@
 mod dumper;
 mod radix_engine_db;
+mod substate_decoder;
 
 pub use dumper::*;
 pub use radix_engine_db::RadixEngineDB;
+pub use substate_decoder::*;
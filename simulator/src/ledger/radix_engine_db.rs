@@ -1,20 +1,54 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
 
 use radix_engine::ledger::*;
-use rocksdb::{DBWithThreadMode, Direction, IteratorMode, SingleThreaded, DB};
-use sbor::{Decode, Encode};
+use radix_engine::model::Component;
+use rocksdb::{
+    ColumnFamily, DBWithThreadMode, Direction, IteratorMode, Options, SingleThreaded, WriteOptions,
+    DB, DEFAULT_COLUMN_FAMILY_NAME,
+};
+use sbor::{Decode, DecodeError, Encode};
 use scrypto::buffer::*;
 use scrypto::engine::types::*;
 
+/// The column family of the secondary index from physical id to substate.
+///
+/// The index keeps every substate version ever written, including downed ones, until pruned
+/// with `RadixEngineDB::prune_phys_id_index`.
+const SUBSTATES_BY_PHYS_ID: &str = "substates_by_phys_id";
+
+/// The column family of the secondary index from physical id to raw address.
+const ADDRESSES_BY_PHYS_ID: &str = "addresses_by_phys_id";
+
 pub struct RadixEngineDB {
     db: DBWithThreadMode<SingleThreaded>,
+    sync_writes: bool,
 }
 
 impl RadixEngineDB {
     pub fn new(root: PathBuf) -> Self {
-        let db = DB::open_default(root.as_path()).unwrap();
-        Self { db }
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+        let db = DB::open_cf(
+            &options,
+            root.as_path(),
+            [SUBSTATES_BY_PHYS_ID, ADDRESSES_BY_PHYS_ID],
+        )
+        .unwrap();
+        Self {
+            db,
+            sync_writes: false,
+        }
+    }
+
+    /// Sets whether every write waits for the write-ahead log to be synced to disk.
+    ///
+    /// Syncing survives machine crashes, at the cost of write throughput.
+    pub fn set_sync_writes(&mut self, sync_writes: bool) {
+        self.sync_writes = sync_writes;
     }
 
     pub fn with_bootstrap(root: PathBuf) -> Self {
@@ -23,6 +57,69 @@ impl RadixEngineDB {
         ledger
     }
 
+    pub fn with_bootstrap_config(root: PathBuf, config: BootstrapConfig) -> Self {
+        let mut ledger = Self::new(root);
+        ledger.bootstrap_with_config(config);
+        ledger
+    }
+
+    /// Exports every entry of this database (substates, the physical id index, epoch and nonce)
+    /// to a file, as a stream of length-prefixed SBOR encoded `(key, value)` pairs.
+    pub fn export_archive<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        for (key, value) in self.db.iterator(IteratorMode::Start) {
+            Self::write_archive_entry(&mut writer, key.to_vec(), value.to_vec())?;
+        }
+        for (phys_id_key, value) in self
+            .db
+            .iterator_cf(self.cf(SUBSTATES_BY_PHYS_ID), IteratorMode::Start)
+        {
+            let phys_id: (Hash, u32) = scrypto_decode(&phys_id_key).unwrap();
+            Self::write_archive_entry(&mut writer, Self::phys_id_key(phys_id), value.to_vec())?;
+        }
+        for (phys_id_key, address) in self
+            .db
+            .iterator_cf(self.cf(ADDRESSES_BY_PHYS_ID), IteratorMode::Start)
+        {
+            let phys_id: (Hash, u32) = scrypto_decode(&phys_id_key).unwrap();
+            Self::write_archive_entry(
+                &mut writer,
+                Self::phys_id_address_key(phys_id),
+                address.to_vec(),
+            )?;
+        }
+        writer.flush()
+    }
+
+    /// Imports all entries of an archive created by `export_archive`, overwriting any entry with
+    /// the same key.
+    pub fn import_archive<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut len = [0u8; 4];
+        loop {
+            match reader.read_exact(&mut len) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e),
+            }
+            let mut entry = vec![0u8; u32::from_le_bytes(len) as usize];
+            reader.read_exact(&mut entry)?;
+            let (key, value): (Vec<u8>, Vec<u8>) = scrypto_decode(&entry)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", e)))?;
+            match scrypto_decode::<(String, (Hash, u32))>(&key) {
+                Ok((index, phys_id)) if index == "phys_id" => {
+                    self.write_cf(SUBSTATES_BY_PHYS_ID, &scrypto_encode(&phys_id), &value)
+                }
+                Ok((index, phys_id)) if index == "phys_id_address" => {
+                    self.write_cf(ADDRESSES_BY_PHYS_ID, &scrypto_encode(&phys_id), &value)
+                }
+                _ => self.write(&key, &value),
+            }
+        }
+        self.flush()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
     pub fn list_packages(&self) -> Vec<PackageAddress> {
         let start = &scrypto_encode(&PackageAddress([0; 26]));
         let end = &scrypto_encode(&PackageAddress([255; 26]));
@@ -57,13 +154,85 @@ impl RadixEngineDB {
         items
     }
 
+    /// Removes the physical id index entries of all downed substates.
+    ///
+    /// Afterwards, only substates which are still up can be looked up by physical id.
+    pub fn prune_phys_id_index(&mut self) {
+        let mut downed = Vec::new();
+        for (phys_id_key, address) in self
+            .db
+            .iterator_cf(self.cf(ADDRESSES_BY_PHYS_ID), IteratorMode::Start)
+        {
+            let phys_id: (Hash, u32) = scrypto_decode(&phys_id_key).unwrap();
+            let is_up = self
+                .read(&address)
+                .map(|b| scrypto_decode::<Substate>(&b).unwrap().phys_id == phys_id)
+                .unwrap_or(false);
+            if !is_up {
+                downed.push(phys_id_key);
+            }
+        }
+
+        for phys_id_key in downed {
+            self.db
+                .delete_cf(self.cf(SUBSTATES_BY_PHYS_ID), &phys_id_key)
+                .unwrap();
+            self.db
+                .delete_cf(self.cf(ADDRESSES_BY_PHYS_ID), &phys_id_key)
+                .unwrap();
+        }
+    }
+
+    /// The archive key of a physical id index entry pointing to a substate.
+    fn phys_id_key(phys_id: (Hash, u32)) -> Vec<u8> {
+        scrypto_encode(&("phys_id", phys_id))
+    }
+
+    /// The archive key of a physical id index entry pointing to a raw address.
+    fn phys_id_address_key(phys_id: (Hash, u32)) -> Vec<u8> {
+        scrypto_encode(&("phys_id_address", phys_id))
+    }
+
+    fn write_archive_entry<W: Write>(
+        writer: &mut W,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> io::Result<()> {
+        let entry = scrypto_encode(&(key, value));
+        writer.write_all(&(entry.len() as u32).to_le_bytes())?;
+        writer.write_all(&entry)
+    }
+
+    /// Indexes a substate written under the given raw address by its physical id.
+    fn index_phys_id(&self, address: &[u8], substate: &Substate, value: &[u8]) {
+        let phys_id_key = scrypto_encode(&substate.phys_id);
+        self.write_cf(SUBSTATES_BY_PHYS_ID, &phys_id_key, value);
+        self.write_cf(ADDRESSES_BY_PHYS_ID, &phys_id_key, address);
+    }
+
+    fn cf(&self, name: &str) -> &ColumnFamily {
+        self.db.cf_handle(name).unwrap()
+    }
+
     fn read(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.read_cf(DEFAULT_COLUMN_FAMILY_NAME, key)
+    }
+
+    fn read_cf(&self, name: &str, key: &[u8]) -> Option<Vec<u8>> {
         // TODO: Use get_pinned
-        self.db.get(key).unwrap()
+        self.db.get_cf(self.cf(name), key).unwrap()
     }
 
     fn write(&self, key: &[u8], value: &[u8]) {
-        self.db.put(key, value).unwrap();
+        self.write_cf(DEFAULT_COLUMN_FAMILY_NAME, key, value)
+    }
+
+    fn write_cf(&self, name: &str, key: &[u8], value: &[u8]) {
+        let mut options = WriteOptions::default();
+        options.set_sync(self.sync_writes);
+        self.db
+            .put_cf_opt(self.cf(name), key, value, &options)
+            .unwrap();
     }
 }
 
@@ -72,7 +241,7 @@ impl QueryableSubstateStore for RadixEngineDB {
         &self,
         component_address: ComponentAddress,
         lazy_map_id: &LazyMapId,
-    ) -> HashMap<Vec<u8>, Vec<u8>> {
+    ) -> Result<HashMap<Vec<u8>, Vec<u8>>, DecodeError> {
         let mut id = scrypto_encode(&component_address);
         id.extend(scrypto_encode(lazy_map_id));
         let key_size = id.len();
@@ -87,10 +256,34 @@ impl QueryableSubstateStore for RadixEngineDB {
             }
 
             let local_key = key.split_at(key_size).1.to_vec();
-            let substate: Substate = scrypto_decode(&value.to_vec()).unwrap();
-            items.insert(local_key, substate.value);
+            let substate: Substate = scrypto_decode(&value.to_vec())?;
+            items.insert(local_key, decode_substate_bytes(&substate.value)?.to_vec());
         }
-        items
+        Ok(items)
+    }
+
+    fn list_components_by_package(&self, package_address: PackageAddress) -> Vec<ComponentAddress> {
+        self.list_components()
+            .into_iter()
+            .filter(|component_address| {
+                self.get_decoded_substate(component_address)
+                    .ok()
+                    .flatten()
+                    .map(|(component, _): (Component, _)| {
+                        component.package_address() == package_address
+                    })
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    fn get_substate_by_phys_id(&self, phys_id: (Hash, u32)) -> Option<Substate> {
+        self.read_cf(SUBSTATES_BY_PHYS_ID, &scrypto_encode(&phys_id))
+            .map(|b| scrypto_decode(&b).unwrap())
+    }
+
+    fn get_address_by_phys_id(&self, phys_id: (Hash, u32)) -> Option<Vec<u8>> {
+        self.read_cf(ADDRESSES_BY_PHYS_ID, &scrypto_encode(&phys_id))
     }
 }
 
@@ -101,7 +294,10 @@ impl SubstateStore for RadixEngineDB {
     }
 
     fn put_substate<T: Encode>(&mut self, address: &T, substate: Substate) {
-        self.write(&scrypto_encode(address), &scrypto_encode(&substate));
+        let address = scrypto_encode(address);
+        let value = scrypto_encode(&substate);
+        self.index_phys_id(&address, &substate, &value);
+        self.write(&address, &value);
     }
 
     fn get_child_substate<T: Encode>(&self, address: &T, key: &[u8]) -> Option<Substate> {
@@ -113,7 +309,9 @@ impl SubstateStore for RadixEngineDB {
     fn put_child_substate<T: Encode>(&mut self, address: &T, key: &[u8], substate: Substate) {
         let mut id = scrypto_encode(address);
         id.extend(key.to_vec());
-        self.write(&id, &scrypto_encode(&substate));
+        let value = scrypto_encode(&substate);
+        self.index_phys_id(&id, &substate, &value);
+        self.write(&id, &value);
     }
 
     fn get_epoch(&self) -> u64 {
@@ -141,4 +339,82 @@ impl SubstateStore for RadixEngineDB {
         let value = scrypto_encode(&(self.get_nonce() + 1));
         self.write(&id, &value)
     }
+
+    fn flush(&mut self) -> Result<(), String> {
+        self.db.flush_wal(true).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radix_engine::transaction::TransactionExecutor;
+    use scrypto::crypto::hash;
+
+    #[test]
+    fn test_flushed_data_persists_across_reopen() {
+        let root = std::env::temp_dir().join(format!("radix-engine-db-{}", uuid::Uuid::new_v4()));
+
+        let mut db = RadixEngineDB::new(root.clone());
+        db.set_sync_writes(true);
+        db.set_epoch(7);
+        db.flush().unwrap();
+        drop(db);
+
+        let db = RadixEngineDB::new(root.clone());
+        assert_eq!(db.get_epoch(), 7);
+        drop(db);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_imported_archive_matches_exported_db() {
+        let root = std::env::temp_dir().join(format!("radix-engine-db-{}", uuid::Uuid::new_v4()));
+        let archive = root.join("ledger.archive");
+
+        let mut db = RadixEngineDB::with_bootstrap(root.join("source"));
+        let mut executor = TransactionExecutor::new(&mut db, false);
+        let (_, _, account) = executor.new_account();
+        db.export_archive(&archive).unwrap();
+
+        let mut imported = RadixEngineDB::new(root.join("target"));
+        imported.import_archive(&archive).unwrap();
+        assert_eq!(imported.list_components(), db.list_components());
+        assert_eq!(
+            imported.get_substate(&account).map(|s| s.value),
+            db.get_substate(&account).map(|s| s.value)
+        );
+        assert_eq!(imported.get_nonce(), db.get_nonce());
+        let phys_id = db.get_substate(&account).unwrap().phys_id;
+        assert_eq!(
+            imported.get_address_by_phys_id(phys_id),
+            Some(scrypto_encode(&account))
+        );
+        drop(db);
+        drop(imported);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_phys_id_index_is_kept_apart_from_substates() {
+        let root = std::env::temp_dir().join(format!("radix-engine-db-{}", uuid::Uuid::new_v4()));
+
+        let mut db = RadixEngineDB::new(root.clone());
+        let address = ComponentAddress([1u8; 26]);
+        let (first, second) = ((hash([1u8]), 0), (hash([2u8]), 0));
+        db.put_encoded_substate(&address, &1u32, first);
+        db.put_encoded_substate(&address, &2u32, second);
+        assert_eq!(db.list_components(), vec![address]);
+        assert!(db.get_substate_by_phys_id(first).is_some());
+
+        db.prune_phys_id_index();
+        assert!(db.get_substate_by_phys_id(first).is_none());
+        assert!(db.get_address_by_phys_id(first).is_none());
+        assert_eq!(
+            db.get_substate_by_phys_id(second).map(|s| s.value),
+            db.get_substate(&address).map(|s| s.value)
+        );
+        drop(db);
+        std::fs::remove_dir_all(root).unwrap();
+    }
 }
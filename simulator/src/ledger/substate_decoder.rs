@@ -0,0 +1,301 @@
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use sbor::{Decode, DecodeError};
+use scrypto::buffer::*;
+use scrypto::crypto::Hash;
+use scrypto::engine::types::*;
+use scrypto::types::ScryptoType;
+use scrypto::values::*;
+
+/// Represents an error when decoding a raw substate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeSubstateError {
+    /// The address doesn't match any known substate kind.
+    UnknownAddress,
+    /// The value can't be decoded as the substate kind of the address.
+    InvalidValue(DecodeError),
+    /// The value isn't a valid Scrypto value.
+    InvalidScryptoValue(ParseScryptoValueError),
+}
+
+/// The kind of a substate, as told by its raw storage address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubstateKind {
+    Package,
+    Component,
+    Vault,
+    LazyMapEntry,
+    ResourceManager,
+    NonFungible,
+}
+
+/// Returns the kind of substate stored under a raw storage address, without decoding it.
+///
+/// Packages, components and resource managers are stored under their encoded address, vaults
+/// and lazy map entries under the owning component, and non-fungibles under their resource.
+pub fn substate_kind(address: &[u8]) -> Option<SubstateKind> {
+    let address_len = scrypto_encode(&PackageAddress([0; 26])).len();
+    let id_len = scrypto_encode(&(Hash([0; 32]), 0u32)).len();
+    if address.len() < address_len {
+        return None;
+    }
+    let child_len = address.len() - address_len;
+
+    match ScryptoType::from_id(address[0]) {
+        Some(ScryptoType::PackageAddress) if child_len == 0 => Some(SubstateKind::Package),
+        Some(ScryptoType::ComponentAddress) if child_len == 0 => Some(SubstateKind::Component),
+        Some(ScryptoType::ComponentAddress) if child_len == id_len => Some(SubstateKind::Vault),
+        Some(ScryptoType::ComponentAddress) if child_len > id_len => {
+            Some(SubstateKind::LazyMapEntry)
+        }
+        Some(ScryptoType::ResourceAddress) if child_len == 0 => Some(SubstateKind::ResourceManager),
+        Some(ScryptoType::ResourceAddress) => Some(SubstateKind::NonFungible),
+        _ => None,
+    }
+}
+
+/// Decodes a substate from its raw storage address and value, and formats it for display.
+///
+/// The substate kind is inferred from the address, see `substate_kind`.
+pub fn decode_substate(address: &[u8], value: &[u8]) -> Result<String, DecodeSubstateError> {
+    let address_len = scrypto_encode(&PackageAddress([0; 26])).len();
+    let id_len = scrypto_encode(&(Hash([0; 32]), 0u32)).len();
+    let kind = substate_kind(address).ok_or(DecodeSubstateError::UnknownAddress)?;
+    let (parent, child) = address.split_at(address_len);
+
+    match kind {
+        SubstateKind::Package => {
+            let package_address: PackageAddress = decode_address(parent)?;
+            let package: Package = decode_value(value)?;
+            Ok(format!(
+                "Package {{ address: {}, code_hash: {}, code_size: {} }}",
+                package_address,
+                package.code_hash(),
+                package.code().len()
+            ))
+        }
+        SubstateKind::Component => {
+            let component_address: ComponentAddress = decode_address(parent)?;
+            let component: Component = decode_value(value)?;
+            let state = ScryptoValue::from_slice(component.state())
+                .map_err(DecodeSubstateError::InvalidScryptoValue)?;
+            Ok(format!(
+                "Component {{ address: {}, package_address: {}, blueprint_name: \"{}\", state: {} }}",
+                component_address,
+                component.package_address(),
+                component.blueprint_name(),
+                state
+            ))
+        }
+        SubstateKind::Vault => {
+            let component_address: ComponentAddress = decode_address(parent)?;
+            let vault_id: VaultId = decode_address(child)?;
+            let vault: Vault = decode_value(value)?;
+            Ok(format!(
+                "Vault {{ component_address: {}, id: {:?}, resource_address: {}, amount: {} }}",
+                component_address,
+                vault_id,
+                vault.resource_address(),
+                vault.total_amount()
+            ))
+        }
+        SubstateKind::LazyMapEntry => {
+            let component_address: ComponentAddress = decode_address(parent)?;
+            let (lazy_map_id, key) = child.split_at(id_len);
+            let lazy_map_id: LazyMapId = decode_address(lazy_map_id)?;
+            let key =
+                ScryptoValue::from_slice(key).map_err(DecodeSubstateError::InvalidScryptoValue)?;
+            let value = decode_substate_bytes(value).map_err(DecodeSubstateError::InvalidValue)?;
+            let value = ScryptoValue::from_slice(value)
+                .map_err(DecodeSubstateError::InvalidScryptoValue)?;
+            Ok(format!(
+                "LazyMapEntry {{ component_address: {}, lazy_map_id: {:?}, key: {}, value: {} }}",
+                component_address, lazy_map_id, key, value
+            ))
+        }
+        SubstateKind::ResourceManager => {
+            let resource_address: ResourceAddress = decode_address(parent)?;
+            let resource_manager: ResourceManager = decode_value(value)?;
+            Ok(format!(
+                "ResourceManager {{ address: {}, resource_type: {:?}, total_supply: {}, metadata: {:?} }}",
+                resource_address,
+                resource_manager.resource_type(),
+                resource_manager.total_supply(),
+                resource_manager.metadata()
+            ))
+        }
+        SubstateKind::NonFungible => {
+            let resource_address: ResourceAddress = decode_address(parent)?;
+            let id: NonFungibleId = decode_address(child)?;
+            let non_fungible: Option<NonFungible> = decode_value(value)?;
+            match non_fungible {
+                Some(non_fungible) => {
+                    let immutable_data = ScryptoValue::from_slice(&non_fungible.immutable_data())
+                        .map_err(DecodeSubstateError::InvalidScryptoValue)?;
+                    let mutable_data = ScryptoValue::from_slice(&non_fungible.mutable_data())
+                        .map_err(DecodeSubstateError::InvalidScryptoValue)?;
+                    Ok(format!(
+                        "NonFungible {{ resource_address: {}, id: {}, immutable_data: {}, mutable_data: {} }}",
+                        resource_address, id, immutable_data, mutable_data
+                    ))
+                }
+                None => Ok(format!(
+                    "NonFungible {{ resource_address: {}, id: {}, burnt }}",
+                    resource_address, id
+                )),
+            }
+        }
+    }
+}
+
+fn decode_address<T: Decode>(bytes: &[u8]) -> Result<T, DecodeSubstateError> {
+    scrypto_decode(bytes).map_err(|_| DecodeSubstateError::UnknownAddress)
+}
+
+fn decode_value<T: Decode>(bytes: &[u8]) -> Result<T, DecodeSubstateError> {
+    decode_substate_value(bytes).map_err(DecodeSubstateError::InvalidValue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scrypto::crypto::hash;
+    use scrypto::rust::collections::HashMap;
+
+    #[test]
+    fn test_decode_package() {
+        let package =
+            Package::new(include_bytes!("../../../assets/account.wasm").to_vec()).unwrap();
+
+        let decoded = decode_substate(
+            &scrypto_encode(&ACCOUNT_PACKAGE),
+            &encode_substate_value(&package),
+        )
+        .unwrap();
+
+        assert!(decoded.starts_with("Package {"));
+        assert!(decoded.contains(&package.code_hash().to_string()));
+    }
+
+    #[test]
+    fn test_decode_component() {
+        let component = Component::new(
+            ACCOUNT_PACKAGE,
+            "Account".to_owned(),
+            vec![],
+            scrypto_encode(&"secret".to_owned()),
+        );
+
+        let decoded = decode_substate(
+            &scrypto_encode(&SYSTEM_COMPONENT),
+            &encode_substate_value(&component),
+        )
+        .unwrap();
+
+        assert!(decoded.starts_with("Component {"));
+        assert!(decoded.contains("blueprint_name: \"Account\""));
+        assert!(decoded.contains("\"secret\""));
+    }
+
+    #[test]
+    fn test_decode_vault() {
+        let vault_id: VaultId = (hash([]), 1024);
+        let vault = Vault::new(ResourceContainer::new_fungible(
+            RADIX_TOKEN,
+            18,
+            Decimal::from(5),
+        ));
+        let mut address = scrypto_encode(&SYSTEM_COMPONENT);
+        address.extend(scrypto_encode(&vault_id));
+
+        let decoded = decode_substate(&address, &encode_substate_value(&vault)).unwrap();
+
+        assert!(decoded.starts_with("Vault {"));
+        assert!(decoded.contains("amount: 5"));
+    }
+
+    #[test]
+    fn test_decode_lazy_map_entry() {
+        let lazy_map_id: LazyMapId = (hash([]), 1025);
+        let mut address = scrypto_encode(&SYSTEM_COMPONENT);
+        address.extend(scrypto_encode(&lazy_map_id));
+        address.extend(scrypto_encode(&"key".to_owned()));
+
+        let decoded = decode_substate(&address, &scrypto_encode(&7u32)).unwrap();
+
+        assert!(decoded.starts_with("LazyMapEntry {"));
+        assert!(decoded.contains("key: \"key\", value: 7u32"));
+    }
+
+    #[test]
+    fn test_decode_resource_manager() {
+        let resource_manager = ResourceManager::new(
+            ResourceType::Fungible { divisibility: 18 },
+            HashMap::new(),
+            HashMap::new(),
+        )
+        .unwrap();
+
+        let decoded = decode_substate(
+            &scrypto_encode(&RADIX_TOKEN),
+            &encode_substate_value(&resource_manager),
+        )
+        .unwrap();
+
+        assert!(decoded.starts_with("ResourceManager {"));
+    }
+
+    #[test]
+    fn test_decode_non_fungible() {
+        let non_fungible = Some(NonFungible::new(scrypto_encode(&1u8), scrypto_encode(&2u8)));
+        let mut address = scrypto_encode(&ECDSA_TOKEN);
+        address.extend(scrypto_encode(&NonFungibleId::from_u32(3)));
+
+        let decoded = decode_substate(&address, &encode_substate_value(&non_fungible)).unwrap();
+
+        assert!(decoded.starts_with("NonFungible {"));
+        assert!(decoded.contains("immutable_data: 1u8, mutable_data: 2u8"));
+    }
+
+    #[test]
+    fn test_decode_unknown_address() {
+        assert_eq!(
+            decode_substate(&scrypto_encode(&"epoch"), &[]),
+            Err(DecodeSubstateError::UnknownAddress)
+        );
+    }
+
+    #[test]
+    fn test_substate_kind() {
+        let id: VaultId = (hash([]), 1024);
+        let mut vault_address = scrypto_encode(&SYSTEM_COMPONENT);
+        vault_address.extend(scrypto_encode(&id));
+        let mut lazy_map_entry_address = vault_address.clone();
+        lazy_map_entry_address.extend(scrypto_encode(&"key".to_owned()));
+        let mut non_fungible_address = scrypto_encode(&RADIX_TOKEN);
+        non_fungible_address.extend(scrypto_encode(&NonFungibleId::from_u32(1)));
+
+        assert_eq!(
+            substate_kind(&scrypto_encode(&ACCOUNT_PACKAGE)),
+            Some(SubstateKind::Package)
+        );
+        assert_eq!(
+            substate_kind(&scrypto_encode(&SYSTEM_COMPONENT)),
+            Some(SubstateKind::Component)
+        );
+        assert_eq!(substate_kind(&vault_address), Some(SubstateKind::Vault));
+        assert_eq!(
+            substate_kind(&lazy_map_entry_address),
+            Some(SubstateKind::LazyMapEntry)
+        );
+        assert_eq!(
+            substate_kind(&scrypto_encode(&RADIX_TOKEN)),
+            Some(SubstateKind::ResourceManager)
+        );
+        assert_eq!(
+            substate_kind(&non_fungible_address),
+            Some(SubstateKind::NonFungible)
+        );
+        assert_eq!(substate_kind(&scrypto_encode(&"epoch")), None);
+    }
+}
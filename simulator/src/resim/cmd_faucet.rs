@@ -0,0 +1,39 @@
+use clap::Parser;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+use crate::resim::*;
+
+/// Top up an account with XRD from the faucet
+#[derive(Parser, Debug)]
+pub struct Faucet {
+    /// The amount of XRD to give away
+    amount: Decimal,
+
+    /// The account to top up, or the default account if not specified
+    account: Option<ComponentAddress>,
+
+    /// Output a transaction manifest without execution
+    #[clap(short, long)]
+    manifest: Option<PathBuf>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl Faucet {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        let account = match self.account {
+            Some(account) => account,
+            None => get_default_account()?,
+        };
+
+        let transaction = TransactionBuilder::new()
+            .faucet_free_xrd(account, self.amount)
+            .build_with_no_nonce();
+        process_transaction(&mut executor, transaction, &None, &self.manifest, out)
+    }
+}
@@ -71,7 +71,7 @@ impl Publish {
             // Overwrite package
             executor
                 .overwrite_package(package_address, code.to_vec())
-                .map_err(|e| Error::PackageValidationError(e))?;
+                .map_err(Error::TransactionExecutionError)?;
             writeln!(out, "Package updated!").map_err(Error::IOError)?;
             Ok(())
         } else {
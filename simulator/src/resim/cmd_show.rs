@@ -10,6 +10,10 @@ use crate::resim::*;
 pub struct Show {
     /// The address of a package, component or resource manager
     address: String,
+
+    /// The maximum number of non-fungibles shown per vault
+    #[clap(long, default_value_t = DEFAULT_MAX_NON_FUNGIBLES_SHOWN)]
+    max_non_fungibles: usize,
 }
 
 impl Show {
@@ -19,7 +23,8 @@ impl Show {
         if let Ok(package_address) = PackageAddress::from_str(&self.address) {
             dump_package(package_address, &ledger, out).map_err(Error::LedgerDumpError)
         } else if let Ok(component_address) = ComponentAddress::from_str(&self.address) {
-            dump_component(component_address, &ledger, out).map_err(Error::LedgerDumpError)
+            dump_component(component_address, &ledger, self.max_non_fungibles, out)
+                .map_err(Error::LedgerDumpError)
         } else if let Ok(resource_address) = ResourceAddress::from_str(&self.address) {
             dump_resource_manager(resource_address, &ledger, out).map_err(Error::LedgerDumpError)
         } else {
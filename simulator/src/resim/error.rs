@@ -24,8 +24,6 @@ pub enum Error {
 
     CargoError(CargoExecutionError),
 
-    PackageValidationError(WasmValidationError),
-
     TransactionConstructionError(CallWithAbiError),
 
     TransactionValidationError(TransactionValidationError),
@@ -1,6 +1,7 @@
 mod cmd_call_function;
 mod cmd_call_method;
 mod cmd_export_abi;
+mod cmd_faucet;
 mod cmd_generate_key_pair;
 mod cmd_mint;
 mod cmd_new_account;
@@ -23,6 +24,7 @@ mod error;
 pub use cmd_call_function::*;
 pub use cmd_call_method::*;
 pub use cmd_export_abi::*;
+pub use cmd_faucet::*;
 pub use cmd_generate_key_pair::*;
 pub use cmd_mint::*;
 pub use cmd_new_account::*;
@@ -77,6 +79,7 @@ pub enum Command {
     CallFunction(CallFunction),
     CallMethod(CallMethod),
     ExportAbi(ExportAbi),
+    Faucet(Faucet),
     GenerateKeyPair(GenerateKeyPair),
     Mint(Mint),
     NewAccount(NewAccount),
@@ -104,6 +107,7 @@ pub fn run() -> Result<(), Error> {
         Command::CallFunction(cmd) => cmd.run(&mut out),
         Command::CallMethod(cmd) => cmd.run(&mut out),
         Command::ExportAbi(cmd) => cmd.run(&mut out),
+        Command::Faucet(cmd) => cmd.run(&mut out),
         Command::GenerateKeyPair(cmd) => cmd.run(&mut out),
         Command::Mint(cmd) => cmd.run(&mut out),
         Command::NewAccount(cmd) => cmd.run(&mut out),
@@ -50,6 +50,8 @@ pub enum Instruction {
 
     ClearAuthZone,
 
+    AssertAuthZoneEmpty,
+
     CreateProofFromAuthZone {
         resource_address: Value,
         new_proof: Value,
@@ -67,6 +69,12 @@ pub enum Instruction {
         new_proof: Value,
     },
 
+    CreateProofFromWorktopByAmount {
+        amount: Value,
+        resource_address: Value,
+        new_proof: Value,
+    },
+
     CreateProofFromBucket {
         bucket: Value,
         new_proof: Value,
@@ -102,6 +110,10 @@ pub enum Instruction {
     PublishPackage {
         code: Value,
     },
+
+    Abort {
+        reason: Value,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -8,6 +8,7 @@ use scrypto::values::*;
 pub enum DecompileError {
     IdValidatorError(IdValidatorError),
     ParseScryptoValueError(ParseScryptoValueError),
+    UnsupportedInstruction(Instruction),
 }
 
 pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
@@ -123,6 +124,9 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
             Instruction::ClearAuthZone => {
                 buf.push_str("CLEAR_AUTH_ZONE;\n");
             }
+            Instruction::AssertAuthZoneEmpty => {
+                buf.push_str("ASSERT_AUTH_ZONE_EMPTY;\n");
+            }
             Instruction::CreateProofFromAuthZone { resource_address } => {
                 let proof_id = id_validator
                     .new_proof(ProofKind::AuthZoneProof)
@@ -166,6 +170,21 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
                     resource_address, name
                 ));
             }
+            Instruction::CreateProofFromWorktopByAmount {
+                amount,
+                resource_address,
+            } => {
+                let proof_id = id_validator
+                    .new_proof(ProofKind::WorktopProof)
+                    .map_err(DecompileError::IdValidatorError)?;
+                let name = format!("proof{}", proofs.len() + 1);
+                proofs.insert(proof_id, name.clone());
+                buf.push_str(&format!(
+                    "CREATE_PROOF_FROM_WORKTOP_BY_AMOUNT Decimal(\"{}\") ResourceAddress(\"{}\") Proof(\"{}\");\n",
+                    amount,
+                    resource_address, name
+                ));
+            }
             Instruction::CreateProofFromBucket { bucket_id } => {
                 let proof_id = id_validator
                     .new_proof(ProofKind::BucketProof(bucket_id))
@@ -267,6 +286,12 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
                     hex::encode(&code)
                 ));
             }
+            Instruction::Abort { reason } => {
+                buf.push_str(&format!("ABORT \"{}\";\n", escape_string(reason)));
+            }
+            Instruction::ConditionalBranch { .. } => {
+                return Err(DecompileError::UnsupportedInstruction(inst.clone()));
+            }
             Instruction::Nonce { .. } => {
                 // TODO: add support for this
             }
@@ -276,6 +301,25 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
     Ok(buf)
 }
 
+/// Escapes a string so that the manifest lexer reads it back unchanged.
+fn escape_string(s: &str) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\x08' => escaped.push_str("\\b"),
+            '\x0c' => escaped.push_str("\\f"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -290,4 +334,17 @@ mod tests {
 
         assert_eq!(compile(manifest).unwrap(), tx);
     }
+
+    #[test]
+    fn test_decompile_abort_with_quoted_reason() {
+        let tx = Transaction {
+            instructions: vec![Instruction::Abort {
+                reason: "say \"no\"\\\n\u{1}".to_owned(),
+            }],
+        };
+
+        let manifest = &decompile(&tx).unwrap();
+
+        assert_eq!(compile(manifest).unwrap(), tx);
+    }
 }
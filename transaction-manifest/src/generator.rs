@@ -203,6 +203,7 @@ pub fn generate_instruction(
             Instruction::PushToAuthZone { proof_id }
         }
         ast::Instruction::ClearAuthZone => Instruction::ClearAuthZone,
+        ast::Instruction::AssertAuthZoneEmpty => Instruction::AssertAuthZoneEmpty,
 
         ast::Instruction::CreateProofFromAuthZone {
             resource_address,
@@ -250,6 +251,23 @@ pub fn generate_instruction(
                 resource_address,
             }
         }
+        ast::Instruction::CreateProofFromWorktopByAmount {
+            amount,
+            resource_address,
+            new_proof,
+        } => {
+            let amount = generate_decimal(amount)?;
+            let resource_address = generate_resource_address(resource_address)?;
+            let proof_id = id_validator
+                .new_proof(ProofKind::WorktopProof)
+                .map_err(GeneratorError::IdValidatorError)?;
+            declare_proof(new_proof, resolver, proof_id)?;
+
+            Instruction::CreateProofFromWorktopByAmount {
+                amount,
+                resource_address,
+            }
+        }
         ast::Instruction::CreateProofFromBucket { bucket, new_proof } => {
             let bucket_id = generate_bucket(bucket, resolver)?;
             let proof_id = id_validator
@@ -328,6 +346,9 @@ pub fn generate_instruction(
         ast::Instruction::PublishPackage { code } => Instruction::PublishPackage {
             code: generate_bytes(code)?,
         },
+        ast::Instruction::Abort { reason } => Instruction::Abort {
+            reason: generate_string(reason)?,
+        },
     })
 }
 
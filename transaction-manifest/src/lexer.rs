@@ -88,9 +88,11 @@ pub enum TokenKind {
     PopFromAuthZone,
     PushToAuthZone,
     ClearAuthZone,
+    AssertAuthZoneEmpty,
     CreateProofFromAuthZone,
     CreateProofFromAuthZoneByAmount,
     CreateProofFromAuthZoneByIds,
+    CreateProofFromWorktopByAmount,
     CreateProofFromBucket,
     CloneProof,
     DropProof,
@@ -98,6 +100,7 @@ pub enum TokenKind {
     CallMethod,
     CallMethodWithAllResources,
     PublishPackage,
+    Abort,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -407,11 +410,13 @@ impl Lexer {
             "POP_FROM_AUTH_ZONE" => Ok(TokenKind::PopFromAuthZone),
             "PUSH_TO_AUTH_ZONE" => Ok(TokenKind::PushToAuthZone),
             "CLEAR_AUTH_ZONE" => Ok(TokenKind::ClearAuthZone),
+            "ASSERT_AUTH_ZONE_EMPTY" => Ok(TokenKind::AssertAuthZoneEmpty),
             "CREATE_PROOF_FROM_AUTH_ZONE" => Ok(TokenKind::CreateProofFromAuthZone),
             "CREATE_PROOF_FROM_AUTH_ZONE_BY_AMOUNT" => {
                 Ok(TokenKind::CreateProofFromAuthZoneByAmount)
             }
             "CREATE_PROOF_FROM_AUTH_ZONE_BY_IDS" => Ok(TokenKind::CreateProofFromAuthZoneByIds),
+            "CREATE_PROOF_FROM_WORKTOP_BY_AMOUNT" => Ok(TokenKind::CreateProofFromWorktopByAmount),
             "CREATE_PROOF_FROM_BUCKET" => Ok(TokenKind::CreateProofFromBucket),
             "CLONE_PROOF" => Ok(TokenKind::CloneProof),
             "DROP_PROOF" => Ok(TokenKind::DropProof),
@@ -419,6 +424,7 @@ impl Lexer {
             "CALL_METHOD" => Ok(TokenKind::CallMethod),
             "CALL_METHOD_WITH_ALL_RESOURCES" => Ok(TokenKind::CallMethodWithAllResources),
             "PUBLISH_PACKAGE" => Ok(TokenKind::PublishPackage),
+            "ABORT" => Ok(TokenKind::Abort),
 
             s @ _ => Err(LexerError::UnknownIdentifier(s.into())),
         }
@@ -106,6 +106,7 @@ impl Parser {
                 proof: self.parse_value()?,
             },
             TokenKind::ClearAuthZone => Instruction::ClearAuthZone,
+            TokenKind::AssertAuthZoneEmpty => Instruction::AssertAuthZoneEmpty,
             TokenKind::CreateProofFromAuthZone => Instruction::CreateProofFromAuthZone {
                 resource_address: self.parse_value()?,
                 new_proof: self.parse_value()?,
@@ -122,6 +123,13 @@ impl Parser {
                 resource_address: self.parse_value()?,
                 new_proof: self.parse_value()?,
             },
+            TokenKind::CreateProofFromWorktopByAmount => {
+                Instruction::CreateProofFromWorktopByAmount {
+                    amount: self.parse_value()?,
+                    resource_address: self.parse_value()?,
+                    new_proof: self.parse_value()?,
+                }
+            }
             TokenKind::CreateProofFromBucket => Instruction::CreateProofFromBucket {
                 bucket: self.parse_value()?,
                 new_proof: self.parse_value()?,
@@ -163,6 +171,9 @@ impl Parser {
             TokenKind::PublishPackage => Instruction::PublishPackage {
                 code: self.parse_value()?,
             },
+            TokenKind::Abort => Instruction::Abort {
+                reason: self.parse_value()?,
+            },
             _ => {
                 return Err(ParserError::UnexpectedToken(token));
             }